@@ -1,18 +1,91 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::mpsc;
-use chrono::Utc;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, NaiveDate, Utc};
 use rusqlite::Connection;
 
 use crate::config::Config;
-use crate::db::{init_database, Repository};
+use crate::db::{init_database, GroupColumn, OrderedTable, Repository};
 use crate::error::Result;
-use crate::models::{Project, Tag, Task, TaskPriority, TaskStatus};
-use crate::services::{GitHubData, GitHubIssue, GitHubService, TogglData, TogglService};
+use crate::models::{
+    is_internal_metadata_key, Project, Tag, Task, TaskKind, TaskPriority, TaskStatus,
+};
+use crate::services::{
+    copy_to_clipboard, filter_started_scheduled, format_estimate, merge_github_item, parse_due_date,
+    parse_estimate, parse_quick_add, ClipboardCopy, GitHubData, GitHubIssue, GitHubService,
+    TogglData, TogglService,
+};
+use crate::session::Session;
 use crate::ui::components::{
-    ConfirmModal, InputState, NotificationModal, SidebarCounts, SidebarState, TaskFormState,
+    ConfirmModal, DetailOptions, FormEnter, InputState, NotificationModal, ProjectSort,
+    ReviewProgress, RowAccent, SidebarCounts, SidebarLayout, SidebarState, TaskFormState, TaskListState,
+    TitleOverflow,
 };
-use crate::ui::theme::SidebarItem;
+use crate::ui::theme::{SidebarItem, Theme};
 use crate::ui::views::*;
 
+/// How long a failed GitHub/Toggl fetch is shown before view entry retries it
+const FETCH_ERROR_COOLDOWN: Duration = Duration::from_secs(60);
+
+fn in_error_cooldown(failed_at: Option<Instant>) -> bool {
+    failed_at.is_some_and(|t| t.elapsed() < FETCH_ERROR_COOLDOWN)
+}
+
+/// The moment `days` days ago, or None when that lies outside the representable range
+fn days_ago(days: i64) -> Option<DateTime<Utc>> {
+    Utc::now().checked_sub_signed(chrono::Duration::try_days(days)?)
+}
+
+/// Sidebar counts recounted from scratch
+fn count_all(tasks: &[Task], projects: &[Project], config: &Config) -> SidebarCounts {
+    use crate::services::*;
+
+    SidebarCounts {
+        inbox: filter_inbox(tasks).len() as i64,
+        active: filter_active(tasks).len() as i64,
+        scheduled: filter_scheduled(tasks).len() as i64,
+        today: filter_today(tasks, config.today_includes_overdue).len() as i64,
+        agenda: filter_agenda(tasks).len() as i64,
+        week: filter_this_week(tasks, config.week_includes_overdue).len() as i64,
+        upcoming: filter_upcoming(tasks).len() as i64,
+        anytime: filter_anytime(tasks).len() as i64,
+        flagged: filter_flagged(tasks).len() as i64,
+        deferred: filter_deferred(tasks).len() as i64,
+        completed: filter_completed(tasks).len() as i64,
+        review: filter_review(tasks).len() as i64,
+        projects: projects
+            .iter()
+            .map(|p| (p.id.clone(), filter_by_project(tasks, &p.id).len() as i64))
+            .collect(),
+    }
+}
+
+/// Add `delta` (1 or -1) to every sidebar count whose view lists `task`, using
+/// the same filters as a full recount
+fn count_task(counts: &mut SidebarCounts, task: &Task, delta: i64, config: &Config) {
+    use crate::services::*;
+
+    let one = std::slice::from_ref(task);
+    let hits = |n: usize| n as i64 * delta;
+    counts.inbox += hits(filter_inbox(one).len());
+    counts.active += hits(filter_active(one).len());
+    counts.scheduled += hits(filter_scheduled(one).len());
+    counts.today += hits(filter_today(one, config.today_includes_overdue).len());
+    counts.agenda += hits(filter_agenda(one).len());
+    counts.week += hits(filter_this_week(one, config.week_includes_overdue).len());
+    counts.upcoming += hits(filter_upcoming(one).len());
+    counts.anytime += hits(filter_anytime(one).len());
+    counts.flagged += hits(filter_flagged(one).len());
+    counts.deferred += hits(filter_deferred(one).len());
+    counts.completed += hits(filter_completed(one).len());
+    counts.review += hits(filter_review(one).len());
+    if let Some(ref project_id) = task.project_id {
+        if let Some(count) = counts.projects.get_mut(project_id) {
+            *count += hits(filter_by_project(one, project_id).len());
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AppMode {
     Normal,
@@ -20,6 +93,45 @@ pub enum AppMode {
     TaskForm,
     Confirm,
     Settings,
+    Triage,
+}
+
+/// Steps of the guided weekly review, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewStep {
+    EmptyInbox,
+    /// Visits every project in sidebar order
+    ReviewProjects,
+    CheckOverdue,
+    PlanUpcoming,
+}
+
+impl ReviewStep {
+    pub const ALL: [ReviewStep; 4] = [
+        ReviewStep::EmptyInbox,
+        ReviewStep::ReviewProjects,
+        ReviewStep::CheckOverdue,
+        ReviewStep::PlanUpcoming,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReviewStep::EmptyInbox => "Empty the inbox",
+            ReviewStep::ReviewProjects => "Review projects",
+            ReviewStep::CheckOverdue => "Check overdue tasks",
+            ReviewStep::PlanUpcoming => "Plan the coming week",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeeklyReview {
+    pub step: ReviewStep,
+    /// Index into `project_ids` during `ReviewProjects`
+    pub project_index: usize,
+    /// The sidebar's projects when the review started; the sidebar re-sorts them
+    /// as their counts change, which must not move the review along
+    pub project_ids: Vec<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -32,18 +144,111 @@ pub enum FocusArea {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CurrentView {
     Inbox,
+    Active,
+    Scheduled,
     Today,
+    Agenda,
+    Week,
     Upcoming,
     Anytime,
+    Flagged,
+    Deferred,
     Completed,
     Project,
     Tag,
     Review,
     GitHub,
     Toggl,
+    Stats,
     Settings,
 }
 
+impl CurrentView {
+    pub fn from_sidebar_item(item: SidebarItem) -> Self {
+        match item {
+            SidebarItem::Inbox => CurrentView::Inbox,
+            SidebarItem::Active => CurrentView::Active,
+            SidebarItem::Scheduled => CurrentView::Scheduled,
+            SidebarItem::Today => CurrentView::Today,
+            SidebarItem::Agenda => CurrentView::Agenda,
+            SidebarItem::Week => CurrentView::Week,
+            SidebarItem::Upcoming => CurrentView::Upcoming,
+            SidebarItem::Anytime => CurrentView::Anytime,
+            SidebarItem::Flagged => CurrentView::Flagged,
+            SidebarItem::Deferred => CurrentView::Deferred,
+            SidebarItem::Completed => CurrentView::Completed,
+            SidebarItem::Review => CurrentView::Review,
+            SidebarItem::GitHub => CurrentView::GitHub,
+            SidebarItem::Toggl => CurrentView::Toggl,
+            SidebarItem::Stats => CurrentView::Stats,
+            SidebarItem::Settings => CurrentView::Settings,
+        }
+    }
+
+    /// The sidebar entry showing this view; project and tag views have none
+    pub fn sidebar_item(&self) -> Option<SidebarItem> {
+        SidebarItem::all()
+            .iter()
+            .copied()
+            .find(|&item| Self::from_sidebar_item(item) == *self)
+    }
+
+    /// Stable name used in the session file
+    pub fn key(&self) -> &'static str {
+        match self {
+            CurrentView::Inbox => "inbox",
+            CurrentView::Active => "active",
+            CurrentView::Scheduled => "scheduled",
+            CurrentView::Today => "today",
+            CurrentView::Agenda => "agenda",
+            CurrentView::Week => "week",
+            CurrentView::Upcoming => "upcoming",
+            CurrentView::Anytime => "anytime",
+            CurrentView::Flagged => "flagged",
+            CurrentView::Deferred => "deferred",
+            CurrentView::Completed => "completed",
+            CurrentView::Project => "project",
+            CurrentView::Tag => "tag",
+            CurrentView::Review => "review",
+            CurrentView::GitHub => "github",
+            CurrentView::Toggl => "toggl",
+            CurrentView::Stats => "stats",
+            CurrentView::Settings => "settings",
+        }
+    }
+
+    /// Whether the view lists tasks in their manual order, rather than sorting or
+    /// grouping them by dates
+    pub fn is_manually_ordered(&self) -> bool {
+        !matches!(
+            self,
+            CurrentView::Today
+                | CurrentView::Agenda
+                | CurrentView::Week
+                | CurrentView::Deferred
+                | CurrentView::Completed
+        )
+    }
+}
+
+/// Views with a task list (see `App::task_list`)
+const TASK_LIST_VIEWS: [CurrentView; 14] = [
+    CurrentView::Inbox,
+    CurrentView::Active,
+    CurrentView::Scheduled,
+    CurrentView::Today,
+    CurrentView::Agenda,
+    CurrentView::Week,
+    CurrentView::Upcoming,
+    CurrentView::Anytime,
+    CurrentView::Flagged,
+    CurrentView::Deferred,
+    CurrentView::Completed,
+    CurrentView::Project,
+    CurrentView::Tag,
+    CurrentView::Review,
+];
+
 pub enum AsyncMessage {
     GitHubDataReady(std::result::Result<GitHubData, String>),
     TogglDataReady(std::result::Result<TogglData, String>),
@@ -55,6 +260,27 @@ pub struct App {
     pub focus: FocusArea,
     pub current_view: CurrentView,
     pub show_help: bool,
+    pub help_scroll: u16,
+    /// Full-screen read-only view of the selected task
+    pub show_preview: bool,
+    pub preview_scroll: u16,
+    /// List-only layout: sidebar, detail pane and status bar hidden (session only)
+    pub focus_mode: bool,
+    focus_before_focus_mode: FocusArea,
+    /// Detail pane at full width with the list hidden (session only)
+    pub detail_expanded: bool,
+    /// Detail panes without the metadata block (session only)
+    pub detail_metadata_collapsed: bool,
+    /// Inbox triage in progress (stays set while a triage prompt is open)
+    pub triage: bool,
+    /// Guided weekly review in progress
+    pub weekly_review: Option<WeeklyReview>,
+    pub last_review: Option<chrono::DateTime<Utc>>,
+    /// Project and kind of the last task created with the form, preselected for
+    /// the next one; forgotten on view switches unless the project is pinned
+    last_new_project: Option<String>,
+    last_new_kind: Option<TaskKind>,
+    pub project_pinned: bool,
 
     // Data
     pub tasks: Vec<Task>,
@@ -64,15 +290,22 @@ pub struct App {
     // UI State
     pub sidebar: SidebarState,
     pub inbox_view: InboxView,
+    pub active_view: ActiveView,
+    pub scheduled_view: ScheduledView,
     pub today_view: TodayView,
+    pub agenda_view: AgendaView,
+    pub week_view: WeekView,
     pub upcoming_view: UpcomingView,
     pub anytime_view: AnytimeView,
+    pub flagged_view: FlaggedView,
+    pub deferred_view: DeferredView,
     pub completed_view: CompletedView,
     pub project_view: ProjectView,
     pub tag_view: TagView,
     pub review_view: ReviewView,
     pub github_view: GitHubView,
     pub toggl_view: TogglView,
+    pub stats_view: StatsView,
     pub settings_view: SettingsView,
 
     // Input / Modals
@@ -81,6 +314,31 @@ pub struct App {
     pub confirm_modal: Option<ConfirmModal>,
     pub notification: Option<NotificationModal>,
     pub pending_delete_id: Option<String>,
+    pub pending_delete_project_id: Option<String>,
+    /// Project whose area the "Area:" prompt sets
+    pending_area_project_id: Option<String>,
+    pub pending_vacuum: bool,
+    pub pending_quit: bool,
+    /// Set once quitting is confirmed; the key handler then stops the main loop
+    pub quit_requested: bool,
+    /// Set by the key handler; the main loop suspends the TUI and opens the editor
+    pub edit_config_requested: bool,
+    /// Terminal window title summarizing today's load; the main loop applies changes
+    pub terminal_title: String,
+    /// Set when an error or overdue view should ring the bell; the main loop rings
+    /// it once and clears the flag
+    pub ring_bell: bool,
+    /// Main-loop iterations, drives the loading spinner
+    pub tick: usize,
+    /// Day `activate_started_tasks` last ran; cleared whenever the tasks change so
+    /// tasks saved with today's start date are swept too
+    started_sweep_day: Option<NaiveDate>,
+    /// Tasks just completed from a list, as they were before, kept listed (struck
+    /// out) until `completed_linger_seconds` after the completion
+    lingering: HashMap<String, (Task, Instant)>,
+    /// Set when tasks changed without `load_data`; the Stats view reloads its
+    /// counts when entered
+    stats_stale: bool,
 
     // Async
     pub async_rx: mpsc::Receiver<AsyncMessage>,
@@ -92,8 +350,13 @@ pub struct App {
 
 impl App {
     pub fn new(config: Config) -> Result<Self> {
-        let db_path = Config::database_path()?;
+        let mut app = Self::open(config, Config::database_path()?)?;
+        app.restore_session(Session::load());
+        Ok(app)
+    }
 
+    /// The app on the database at `db_path`, without the saved session
+    fn open(config: Config, db_path: std::path::PathBuf) -> Result<Self> {
         // Initialize database
         {
             let conn = Connection::open(&db_path)?;
@@ -108,6 +371,19 @@ impl App {
             focus: FocusArea::List,
             current_view: CurrentView::Inbox,
             show_help: false,
+            help_scroll: 0,
+            show_preview: false,
+            preview_scroll: 0,
+            focus_mode: false,
+            triage: false,
+            weekly_review: None,
+            last_review: None,
+            last_new_project: None,
+            last_new_kind: None,
+            project_pinned: false,
+            focus_before_focus_mode: FocusArea::List,
+            detail_expanded: false,
+            detail_metadata_collapsed: false,
 
             tasks: Vec::new(),
             projects: Vec::new(),
@@ -115,15 +391,22 @@ impl App {
 
             sidebar: SidebarState::default(),
             inbox_view: InboxView::new(),
+            active_view: ActiveView::new(),
+            scheduled_view: ScheduledView::new(),
             today_view: TodayView::new(),
+            agenda_view: AgendaView::new(),
+            week_view: WeekView::new(),
             upcoming_view: UpcomingView::new(),
             anytime_view: AnytimeView::new(),
+            flagged_view: FlaggedView::new(),
+            deferred_view: DeferredView::new(),
             completed_view: CompletedView::new(),
             project_view: ProjectView::new(),
             tag_view: TagView::new(),
             review_view: ReviewView::new(),
             github_view: GitHubView::new(),
             toggl_view: TogglView::new(),
+            stats_view: StatsView::new(),
             settings_view: SettingsView::new(config),
 
             input: InputState::new(""),
@@ -131,6 +414,18 @@ impl App {
             confirm_modal: None,
             notification: None,
             pending_delete_id: None,
+            pending_delete_project_id: None,
+            pending_area_project_id: None,
+            pending_vacuum: false,
+            pending_quit: false,
+            quit_requested: false,
+            edit_config_requested: false,
+            terminal_title: String::new(),
+            ring_bell: false,
+            tick: 0,
+            started_sweep_day: None,
+            lingering: HashMap::new(),
+            stats_stale: false,
 
             async_rx: rx,
             async_tx: tx,
@@ -138,20 +433,167 @@ impl App {
             db_path,
         };
 
+        app.auto_archive();
+
+        let show_notes = app.config.list_notes_preview;
+        let row_accent = RowAccent::from_str(&app.config.row_accent);
+        let title_overflow = TitleOverflow::from_str(&app.config.title_overflow);
+        let max_title_width = app.config.max_title_width;
+        app.for_each_task_list(|_, list| {
+            list.show_notes = show_notes;
+            list.row_accent = row_accent;
+            list.title_overflow = title_overflow;
+            list.max_title_width = max_title_width;
+        });
+        app.today_view.set_include_overdue(app.config.today_includes_overdue);
+        app.week_view.include_overdue = app.config.week_includes_overdue;
+        app.toggl_view.chart_state.week_start = app.config.first_weekday();
+        app.stats_view.week_start = app.config.first_weekday();
+        app.completed_view.week_start = app.config.first_weekday();
+        app.sidebar.project_sort = ProjectSort::from_str(&app.config.project_sort);
+        app.sidebar.layout = SidebarLayout::from_config(&app.config);
+        app.github_view.active_column = GitHubColumn::from_str(&app.config.github_start_column);
+
         app.load_data()?;
+        app.activate_started_tasks();
         Ok(app)
     }
 
+    /// An app on a fresh database file in the temp dir holding `tasks`, removed
+    /// when dropped
+    #[cfg(test)]
+    pub(crate) fn for_test(config: Config, tasks: &[Task]) -> TestApp {
+        let path = std::env::temp_dir().join(format!("phitodo-test-{}.db", uuid::Uuid::new_v4()));
+        let conn = Connection::open(&path).unwrap();
+        init_database(&conn).unwrap();
+        let repo = Repository::new(conn);
+        for task in tasks {
+            repo.insert_task(task).unwrap();
+        }
+        TestApp(Self::open(config, path).unwrap())
+    }
+
+    /// Make Scheduled tasks whose start date has arrived Active; runs at startup
+    /// and again from the main loop once the day or the tasks change
+    pub fn activate_started_tasks(&mut self) {
+        let today = Utc::now().date_naive();
+        if self.started_sweep_day == Some(today) {
+            return;
+        }
+        self.started_sweep_day = Some(today);
+
+        let now = Utc::now();
+        let started: Vec<Task> = filter_started_scheduled(&self.tasks, today)
+            .into_iter()
+            .cloned()
+            .map(|mut t| {
+                t.status = TaskStatus::Active;
+                t.updated_at = now;
+                t
+            })
+            .collect();
+        if started.is_empty() {
+            return;
+        }
+        match self.get_repo().and_then(|repo| repo.update_tasks(&started)) {
+            Ok(()) => {
+                self.show_info(format!(
+                    "Activated {} scheduled task(s) whose start date arrived",
+                    started.len()
+                ));
+                let _ = self.load_data();
+            }
+            Err(e) => self.show_error(format!("Failed to activate scheduled tasks: {}", e)),
+        }
+    }
+
+    /// Reopen the view and task selections saved by `save_session`, skipping
+    /// projects and tasks that no longer exist
+    fn restore_session(&mut self, session: Session) {
+        self.last_review = session.last_review;
+        self.sidebar.collapsed_areas = session.collapsed_areas.into_iter().collect();
+        self.review_view.set_last_review(session.last_review);
+
+        match session.view.as_deref() {
+            Some("project") => {
+                if let Some(id) = session
+                    .project_id
+                    .filter(|id| self.projects.iter().any(|p| &p.id == id))
+                {
+                    self.switch_to_project(&id);
+                }
+            }
+            Some(key) => {
+                if let Some(&item) = SidebarItem::all()
+                    .iter()
+                    .find(|&&item| CurrentView::from_sidebar_item(item).key() == key)
+                {
+                    self.switch_to_view(item);
+                }
+            }
+            None => {}
+        }
+
+        self.for_each_task_list(|view, list| {
+            if let Some(id) = session.selected_tasks.get(view.key()) {
+                list.select_first_present(std::slice::from_ref(id));
+            }
+        });
+    }
+
+    /// Remember the current view and each list's selected task for the next launch
+    pub fn save_session(&mut self) {
+        let view = self.current_view;
+        let project_id = self.project_view.project.as_ref().map(|p| p.id.clone());
+        let selected_tasks = TASK_LIST_VIEWS
+            .iter()
+            .filter_map(|&view| {
+                Some((view.key().to_string(), self.task_list(view)?.selected_task()?.id.clone()))
+            })
+            .collect();
+        let session = Session {
+            view: Some(view.key().to_string()),
+            project_id: if view == CurrentView::Project { project_id } else { None },
+            selected_tasks,
+            last_review: self.last_review,
+            collapsed_areas: self.sidebar.collapsed_areas.iter().cloned().collect(),
+        };
+        // Losing the session is harmless, so a failed write isn't reported
+        let _ = session.save();
+    }
+
     fn get_repo(&self) -> Result<Repository> {
         let conn = Connection::open(&self.db_path)?;
         Ok(Repository::new(conn))
     }
 
+    /// Remove completed tasks past the configured retention period
+    fn auto_archive(&mut self) {
+        if self.config.auto_archive_days <= 0 {
+            return;
+        }
+        // A window too long to represent means nothing is ever old enough to archive
+        let Some(cutoff) = days_ago(self.config.auto_archive_days) else {
+            return;
+        };
+        match self.get_repo().and_then(|repo| repo.archive_completed_before(cutoff)) {
+            Ok(0) => {}
+            Ok(count) => self.show_info(format!(
+                "Archived {} completed task(s) older than {} days",
+                count, self.config.auto_archive_days
+            )),
+            Err(e) => self.show_error(format!("Failed to archive completed tasks: {}", e)),
+        }
+    }
+
     pub fn load_data(&mut self) -> Result<()> {
         let repo = self.get_repo()?;
         self.tasks = repo.get_all_tasks()?;
+        self.started_sweep_day = None;
         self.projects = repo.get_all_projects()?;
         self.tags = repo.get_all_tags()?;
+        self.load_stats(&repo)?;
+        self.stats_stale = false;
 
         self.update_sidebar_counts();
         self.update_views();
@@ -159,46 +601,146 @@ impl App {
         Ok(())
     }
 
+    fn load_stats(&mut self, repo: &Repository) -> Result<()> {
+        self.stats_view.by_status = repo.count_tasks_grouped_by(GroupColumn::Status)?;
+        self.stats_view.by_kind = repo.count_tasks_grouped_by(GroupColumn::Kind)?;
+        self.stats_view.by_priority = repo.count_tasks_grouped_by(GroupColumn::Priority)?;
+        self.stats_view.completed_by_day =
+            repo.count_completed_by_day(self.stats_view.heatmap_start())?;
+        Ok(())
+    }
+
+    /// Swap in a task just saved by a single-task action: the sidebar counts are
+    /// adjusted for that task alone instead of recounting every task
+    fn replace_task(&mut self, task: Task) {
+        let Some(i) = self.tasks.iter().position(|t| t.id == task.id) else {
+            let _ = self.load_data();
+            return;
+        };
+        if task.is_completed() && !self.tasks[i].is_completed() {
+            // Triage moves straight on to the next task, so nothing lingers there
+            if self.config.completed_linger_seconds > 0 && !self.triage {
+                self.lingering
+                    .insert(task.id.clone(), (self.tasks[i].clone(), Instant::now()));
+            }
+        } else if !task.is_completed() {
+            self.lingering.remove(&task.id);
+        }
+        count_task(&mut self.sidebar.counts, &self.tasks[i], -1, &self.config);
+        self.tasks[i] = task;
+        self.started_sweep_day = None;
+        count_task(&mut self.sidebar.counts, &self.tasks[i], 1, &self.config);
+        self.sync_sidebar_with_counts();
+        self.stats_stale = true;
+        self.update_views();
+    }
+
     fn update_sidebar_counts(&mut self) {
-        use crate::services::*;
+        self.sidebar.counts = count_all(&self.tasks, &self.projects, &self.config);
+        self.sync_sidebar_with_counts();
+    }
+
+    /// Re-sort the sidebar projects and retitle the terminal after the counts change
+    fn sync_sidebar_with_counts(&mut self) {
+        let mut projects = self.projects.clone();
+        match self.sidebar.project_sort {
+            ProjectSort::Manual => {}
+            ProjectSort::Name => projects.sort_by_key(|p| p.name.to_lowercase()),
+            ProjectSort::OpenTasks => projects.sort_by_key(|p| {
+                std::cmp::Reverse(self.sidebar.counts.projects.get(&p.id).copied().unwrap_or(0))
+            }),
+        }
+        self.sidebar.projects = projects;
 
-        self.sidebar.counts = SidebarCounts {
-            inbox: filter_inbox(&self.tasks).len() as i64,
-            today: filter_today(&self.tasks).len() as i64,
-            upcoming: filter_upcoming(&self.tasks).len() as i64,
-            anytime: filter_anytime(&self.tasks).len() as i64,
-            completed: filter_completed(&self.tasks).len() as i64,
-            review: filter_review(&self.tasks).len() as i64,
+        let overdue = self.sidebar.counts.review;
+        let due_today = if self.config.today_includes_overdue {
+            self.sidebar.counts.today - overdue
+        } else {
+            self.sidebar.counts.today
+        };
+        self.terminal_title = if overdue > 0 {
+            format!("phitodo — {} today, {} overdue", due_today, overdue)
+        } else {
+            format!("phitodo — {} today", due_today)
         };
-        self.sidebar.projects = self.projects.clone();
+    }
+
+    /// Let lingering completed tasks leave their lists once their time is up
+    pub fn expire_lingering(&mut self) {
+        let linger = Duration::from_secs(self.config.completed_linger_seconds);
+        let before = self.lingering.len();
+        self.lingering.retain(|_, (_, completed_at)| completed_at.elapsed() < linger);
+        if self.lingering.len() != before {
+            self.update_views();
+        }
     }
 
     fn update_views(&mut self) {
-        self.inbox_view.update_tasks(&self.tasks);
-        self.today_view.update_tasks(&self.tasks);
-        self.upcoming_view.update_tasks(&self.tasks);
-        self.anytime_view.update_tasks(&self.tasks);
+        let project_labels: std::collections::HashMap<String, String> = self
+            .projects
+            .iter()
+            .map(|p| (p.id.clone(), format!("{} {}", Theme::project_icon(p), p.name)))
+            .collect();
+        let project_colors: std::rc::Rc<HashMap<String, ratatui::style::Color>> = std::rc::Rc::new(
+            self.projects
+                .iter()
+                .filter_map(|p| Some((p.id.clone(), Theme::parse_color(p.color.as_deref()?)?)))
+                .collect(),
+        );
+        let tag_colors: std::rc::Rc<HashMap<String, ratatui::style::Color>> = std::rc::Rc::new(
+            self.tags
+                .iter()
+                .filter_map(|t| Some((t.id.clone(), Theme::parse_color(t.color.as_deref()?)?)))
+                .collect(),
+        );
+        let struck: HashSet<String> = self.lingering.keys().cloned().collect();
+        self.for_each_task_list(|_, list| {
+            list.project_labels = project_labels.clone();
+            list.project_colors = std::rc::Rc::clone(&project_colors);
+            list.tag_colors = std::rc::Rc::clone(&tag_colors);
+            list.struck = struck.clone();
+        });
+
+        // Lingering tasks are filtered as they were before completion, so they stay
+        // where they were; the lists draw them as completed
+        let with_lingering: Vec<Task>;
+        let tasks = if self.lingering.is_empty() {
+            &self.tasks
+        } else {
+            with_lingering = self
+                .tasks
+                .iter()
+                .map(|t| match self.lingering.get(&t.id) {
+                    Some((before, _)) if t.is_completed() => before.clone(),
+                    _ => t.clone(),
+                })
+                .collect();
+            &with_lingering
+        };
+
+        self.inbox_view.update_tasks(tasks);
+        self.active_view.update_tasks(tasks);
+        self.scheduled_view.update_tasks(tasks);
+        self.today_view.update_tasks(tasks);
+        self.agenda_view.update_tasks(tasks);
+        self.week_view.update_tasks(tasks);
+        self.upcoming_view.update_tasks(tasks);
+        self.anytime_view.update_tasks(tasks);
+        self.flagged_view.update_tasks(tasks);
+        self.deferred_view.update_tasks(tasks);
         self.completed_view.update_tasks(&self.tasks);
-        self.project_view.update_tasks(&self.tasks);
-        self.tag_view.update_tasks(&self.tasks);
-        self.review_view.update_tasks(&self.tasks);
+        self.project_view.update_tasks(tasks);
+        self.tag_view.update_tasks(tasks);
+        self.review_view.update_tasks(tasks);
     }
 
     pub fn switch_to_view(&mut self, item: SidebarItem) {
+        self.forget_new_task_defaults();
         self.sidebar.selected_item = item;
         self.sidebar.selected_project = None;
+        self.sidebar.selected_area = None;
 
-        self.current_view = match item {
-            SidebarItem::Inbox => CurrentView::Inbox,
-            SidebarItem::Today => CurrentView::Today,
-            SidebarItem::Upcoming => CurrentView::Upcoming,
-            SidebarItem::Anytime => CurrentView::Anytime,
-            SidebarItem::Completed => CurrentView::Completed,
-            SidebarItem::Review => CurrentView::Review,
-            SidebarItem::GitHub => CurrentView::GitHub,
-            SidebarItem::Toggl => CurrentView::Toggl,
-            SidebarItem::Settings => CurrentView::Settings,
-        };
+        self.current_view = CurrentView::from_sidebar_item(item);
 
         if self.current_view == CurrentView::Settings {
             self.mode = AppMode::Settings;
@@ -206,24 +748,308 @@ impl App {
             self.mode = AppMode::Normal;
         }
 
-        // Trigger data loading for GitHub/Toggl
-        if self.current_view == CurrentView::GitHub {
+        // Trigger data loading for GitHub/Toggl, and catch up on stats skipped by
+        // single-task actions
+        if self.current_view == CurrentView::Stats && self.stats_stale {
+            if let Ok(repo) = self.get_repo() {
+                if self.load_stats(&repo).is_ok() {
+                    self.stats_stale = false;
+                }
+            }
+        } else if self.current_view == CurrentView::GitHub {
             self.fetch_github_data();
         } else if self.current_view == CurrentView::Toggl {
             self.fetch_toggl_data();
         }
+        self.bell_if_overdue();
+    }
+
+    /// Ring the bell (with `bell_on_overdue`) if the view just entered lists an
+    /// overdue task
+    fn bell_if_overdue(&mut self) {
+        if !self.config.bell_on_overdue {
+            return;
+        }
+        if let Some(list) = self.current_task_list_mut() {
+            if list.tasks.iter().any(|t| t.is_overdue()) {
+                self.ring_bell = true;
+            }
+        }
     }
 
     pub fn switch_to_project(&mut self, project_id: &str) {
         if let Some(project) = self.projects.iter().find(|p| p.id == project_id).cloned() {
+            self.forget_new_task_defaults();
             self.sidebar.selected_project = Some(project_id.to_string());
+            self.sidebar.selected_area = None;
             self.current_view = CurrentView::Project;
             self.project_view.set_project(Some(project));
             self.project_view.update_tasks(&self.tasks);
+            self.bell_if_overdue();
+        }
+    }
+
+    /// In the Project view, switch to the next (or previous) project in sidebar
+    /// order, wrapping around at the ends
+    pub fn switch_to_adjacent_project(&mut self, forward: bool) {
+        let projects = &self.sidebar.projects;
+        if self.current_view != CurrentView::Project || projects.is_empty() {
+            return;
+        }
+        let len = projects.len();
+        let current = self
+            .sidebar
+            .selected_project
+            .as_ref()
+            .and_then(|id| projects.iter().position(|p| &p.id == id));
+        let next = match current {
+            Some(i) if forward => (i + 1) % len,
+            Some(i) => (i + len - 1) % len,
+            None => 0,
+        };
+        let id = projects[next].id.clone();
+        self.switch_to_project(&id);
+        self.focus = FocusArea::List;
+    }
+
+    /// Start processing the inbox one task at a time
+    pub fn start_triage(&mut self) {
+        self.switch_to_view(SidebarItem::Inbox);
+        if self.inbox_view.task_list.tasks.is_empty() {
+            self.show_info("Inbox is empty".to_string());
+            return;
+        }
+        self.inbox_view.task_list.select_first();
+        self.triage = true;
+        self.mode = AppMode::Triage;
+    }
+
+    pub fn exit_triage(&mut self) {
+        self.triage = false;
+        self.mode = AppMode::Normal;
+    }
+
+    /// After a triage action: the inbox selection has already moved on to the next
+    /// task; leave triage once nothing is left
+    pub fn continue_triage(&mut self) {
+        if !self.triage {
+            return;
+        }
+        if self.inbox_view.task_list.tasks.is_empty() {
+            self.exit_triage();
+            self.show_info("Inbox zero!".to_string());
+        } else {
+            self.mode = AppMode::Triage;
+        }
+    }
+
+    pub fn start_triage_input(&mut self, prompt: &str) {
+        let placeholder = match prompt {
+            "Tag:" => "Tag name",
+            "Log time:" => "30m, 1h30m, 90, PT45M",
+            "Append note:" => "Line to add to the task's notes",
+            "Completed:" => "today, week, month, 2024-05-01..2024-05-31 (empty: all)",
+            _ => "YYYY-MM-DD, today, tomorrow, fri, +3d (empty clears)",
+        };
+        self.input = InputState::new(prompt).with_placeholder(placeholder);
+        self.mode = AppMode::Input;
+    }
+
+    /// Start the weekly review from the Review view, or move on to the next step
+    /// (the next project while reviewing projects) once one is running
+    pub fn advance_weekly_review(&mut self) {
+        let next = match self.weekly_review.take() {
+            None if self.current_view == CurrentView::Review => Some(WeeklyReview {
+                step: ReviewStep::EmptyInbox,
+                project_index: 0,
+                project_ids: self.sidebar.projects.iter().map(|p| p.id.clone()).collect(),
+            }),
+            None => return,
+            Some(review)
+                if review.step == ReviewStep::ReviewProjects
+                    && review.project_index + 1 < review.project_ids.len() =>
+            {
+                Some(WeeklyReview {
+                    project_index: review.project_index + 1,
+                    ..review
+                })
+            }
+            Some(review) => ReviewStep::ALL
+                .iter()
+                .skip_while(|&&s| s != review.step)
+                .skip(1)
+                .copied()
+                .find(|&s| s != ReviewStep::ReviewProjects || !review.project_ids.is_empty())
+                .map(|step| WeeklyReview {
+                    step,
+                    project_index: 0,
+                    ..review
+                }),
+        };
+
+        match next {
+            Some(review) => {
+                self.show_review_step(&review);
+                self.weekly_review = Some(review);
+            }
+            None => self.finish_weekly_review(),
+        }
+    }
+
+    fn show_review_step(&mut self, review: &WeeklyReview) {
+        match review.step {
+            ReviewStep::EmptyInbox => self.switch_to_view(SidebarItem::Inbox),
+            ReviewStep::ReviewProjects => {
+                if let Some(id) = review.project_ids.get(review.project_index) {
+                    self.switch_to_project(id);
+                }
+            }
+            ReviewStep::CheckOverdue => self.switch_to_view(SidebarItem::Review),
+            ReviewStep::PlanUpcoming => self.switch_to_view(SidebarItem::Upcoming),
+        }
+        self.focus = FocusArea::List;
+    }
+
+    fn finish_weekly_review(&mut self) {
+        self.weekly_review = None;
+        self.last_review = Some(Utc::now());
+        self.review_view.set_last_review(self.last_review);
+        self.save_session();
+        self.switch_to_view(SidebarItem::Review);
+        self.show_info("Weekly review complete".to_string());
+    }
+
+    pub fn stop_weekly_review(&mut self) {
+        if self.weekly_review.take().is_some() {
+            self.show_info("Weekly review stopped".to_string());
+        }
+    }
+
+    /// Step indicator text for the running weekly review
+    pub fn weekly_review_progress(&self) -> Option<ReviewProgress> {
+        let review = self.weekly_review.as_ref()?;
+        let step = ReviewStep::ALL.iter().position(|&s| s == review.step)? + 1;
+        let label = match review.step {
+            ReviewStep::EmptyInbox => format!(
+                "{} ({} left)",
+                review.step.label(),
+                self.sidebar.counts.inbox
+            ),
+            ReviewStep::ReviewProjects => format!(
+                "{}: {} ({} of {})",
+                review.step.label(),
+                review
+                    .project_ids
+                    .get(review.project_index)
+                    .and_then(|id| self.projects.iter().find(|p| &p.id == id))
+                    .map(|p| p.name.as_str())
+                    .unwrap_or(""),
+                review.project_index + 1,
+                review.project_ids.len()
+            ),
+            _ => review.step.label().to_string(),
+        };
+        Some(ReviewProgress {
+            step,
+            total: ReviewStep::ALL.len(),
+            label,
+        })
+    }
+
+    /// In the Completed view, switch between a flat list and project groups
+    pub fn toggle_completed_grouping(&mut self) {
+        if self.current_view == CurrentView::Completed {
+            self.completed_view.toggle_group_by_project(&self.tasks);
+        }
+    }
+
+    /// In the Completed view, ask for the period of completions to show
+    pub fn start_completed_range(&mut self) {
+        if self.current_view == CurrentView::Completed {
+            self.start_triage_input("Completed:");
+        }
+    }
+
+    fn set_completed_range(&mut self, input: &str) {
+        match CompletedRange::parse(input, Utc::now().date_naive()) {
+            Some(range) => {
+                self.completed_view.range = range;
+                self.completed_view.update_tasks(&self.tasks);
+            }
+            None => self.show_error(format!("Invalid range: {}", input)),
+        }
+    }
+
+    /// Narrow the current list to one priority level, stepping through the levels
+    pub fn cycle_priority_filter(&mut self) {
+        if let Some(list) = self.current_task_list_mut() {
+            list.cycle_priority_filter();
+        }
+    }
+
+    /// Cycle the sidebar project order: manual, by name, by open tasks
+    pub fn cycle_project_sort(&mut self) {
+        self.sidebar.project_sort = self.sidebar.project_sort.next();
+        self.update_sidebar_counts();
+        self.show_info(format!("Projects sorted by {}", self.sidebar.project_sort.label()));
+    }
+
+    pub fn toggle_focus_mode(&mut self) {
+        self.detail_expanded = false;
+        self.focus_mode = !self.focus_mode;
+        if self.focus_mode {
+            self.focus_before_focus_mode = self.focus;
+            self.focus = FocusArea::List;
+        } else {
+            self.focus = self.focus_before_focus_mode;
+        }
+    }
+
+    /// Show the selected task's details at full width, or go back to list + detail;
+    /// j/k keep moving through the list either way
+    pub fn toggle_detail_expanded(&mut self) {
+        if self.focus_mode {
+            self.toggle_focus_mode();
+        }
+        self.detail_expanded = !self.detail_expanded;
+    }
+
+    /// Hide or show the created/updated/URL block of the detail pane
+    pub fn toggle_detail_metadata(&mut self) {
+        self.detail_metadata_collapsed = !self.detail_metadata_collapsed;
+    }
+
+    /// How the detail panes are drawn this frame
+    pub fn detail_options(&self) -> DetailOptions {
+        DetailOptions {
+            metadata_collapsed: self.detail_metadata_collapsed,
+            markdown_notes: self.config.markdown_notes,
+        }
+    }
+
+    /// Show or hide the selected task's checklist in the list
+    pub fn toggle_task_expanded(&mut self) {
+        if self.focus == FocusArea::Sidebar {
+            return;
+        }
+        if let Some(list) = self.current_task_list_mut() {
+            list.toggle_expanded();
+        }
+    }
+
+    pub fn open_task_preview(&mut self) {
+        if self.selected_task().is_none() {
+            self.show_info("No task selected".to_string());
+            return;
         }
+        self.show_preview = true;
+        self.preview_scroll = 0;
     }
 
     pub fn cycle_focus(&mut self) {
+        if self.focus_mode {
+            return;
+        }
         self.focus = match self.focus {
             FocusArea::Sidebar => FocusArea::List,
             FocusArea::List => FocusArea::Detail,
@@ -232,6 +1058,9 @@ impl App {
     }
 
     pub fn cycle_focus_reverse(&mut self) {
+        if self.focus_mode {
+            return;
+        }
         self.focus = match self.focus {
             FocusArea::Sidebar => FocusArea::Detail,
             FocusArea::List => FocusArea::Sidebar,
@@ -244,17 +1073,10 @@ impl App {
             self.sidebar.select_next();
             return;
         }
-        match self.current_view {
-            CurrentView::Inbox => self.inbox_view.task_list.select_next(),
-            CurrentView::Today => self.today_view.task_list.select_next(),
-            CurrentView::Upcoming => self.upcoming_view.task_list.select_next(),
-            CurrentView::Anytime => self.anytime_view.task_list.select_next(),
-            CurrentView::Completed => self.completed_view.task_list.select_next(),
-            CurrentView::Project => self.project_view.task_list.select_next(),
-            CurrentView::Tag => self.tag_view.task_list.select_next(),
-            CurrentView::Review => self.review_view.task_list.select_next(),
-            CurrentView::GitHub => self.github_view.select_next(),
-            _ => {}
+        if self.current_view == CurrentView::GitHub {
+            self.github_view.select_next();
+        } else if let Some(list) = self.current_task_list_mut() {
+            list.select_next();
         }
     }
 
@@ -263,17 +1085,10 @@ impl App {
             self.sidebar.select_previous();
             return;
         }
-        match self.current_view {
-            CurrentView::Inbox => self.inbox_view.task_list.select_previous(),
-            CurrentView::Today => self.today_view.task_list.select_previous(),
-            CurrentView::Upcoming => self.upcoming_view.task_list.select_previous(),
-            CurrentView::Anytime => self.anytime_view.task_list.select_previous(),
-            CurrentView::Completed => self.completed_view.task_list.select_previous(),
-            CurrentView::Project => self.project_view.task_list.select_previous(),
-            CurrentView::Tag => self.tag_view.task_list.select_previous(),
-            CurrentView::Review => self.review_view.task_list.select_previous(),
-            CurrentView::GitHub => self.github_view.select_previous(),
-            _ => {}
+        if self.current_view == CurrentView::GitHub {
+            self.github_view.select_previous();
+        } else if let Some(list) = self.current_task_list_mut() {
+            list.select_previous();
         }
     }
 
@@ -282,16 +1097,8 @@ impl App {
             self.sidebar.select_first();
             return;
         }
-        match self.current_view {
-            CurrentView::Inbox => self.inbox_view.task_list.select_first(),
-            CurrentView::Today => self.today_view.task_list.select_first(),
-            CurrentView::Upcoming => self.upcoming_view.task_list.select_first(),
-            CurrentView::Anytime => self.anytime_view.task_list.select_first(),
-            CurrentView::Completed => self.completed_view.task_list.select_first(),
-            CurrentView::Project => self.project_view.task_list.select_first(),
-            CurrentView::Tag => self.tag_view.task_list.select_first(),
-            CurrentView::Review => self.review_view.task_list.select_first(),
-            _ => {}
+        if let Some(list) = self.current_task_list_mut() {
+            list.select_first();
         }
     }
 
@@ -300,23 +1107,26 @@ impl App {
             self.sidebar.select_last();
             return;
         }
-        match self.current_view {
-            CurrentView::Inbox => self.inbox_view.task_list.select_last(),
-            CurrentView::Today => self.today_view.task_list.select_last(),
-            CurrentView::Upcoming => self.upcoming_view.task_list.select_last(),
-            CurrentView::Anytime => self.anytime_view.task_list.select_last(),
-            CurrentView::Completed => self.completed_view.task_list.select_last(),
-            CurrentView::Project => self.project_view.task_list.select_last(),
-            CurrentView::Tag => self.tag_view.task_list.select_last(),
-            CurrentView::Review => self.review_view.task_list.select_last(),
-            _ => {}
+        if let Some(list) = self.current_task_list_mut() {
+            list.select_last();
+        }
+    }
+
+    pub fn select_overdue(&mut self, forward: bool) {
+        if self.focus == FocusArea::Sidebar {
+            return;
+        }
+        if let Some(list) = self.current_task_list_mut() {
+            list.select_overdue(forward);
         }
     }
 
     pub fn activate_selected(&mut self) {
         if self.focus == FocusArea::Sidebar {
-            // Check if a project is selected
-            if let Some(ref project_id) = self.sidebar.selected_project.clone() {
+            // An area header collapses/expands, a project opens
+            if self.sidebar.selected_area.is_some() {
+                self.sidebar.toggle_selected_area();
+            } else if let Some(ref project_id) = self.sidebar.selected_project.clone() {
                 self.switch_to_project(&project_id);
                 self.focus = FocusArea::List;
             } else {
@@ -328,37 +1138,130 @@ impl App {
     }
 
     // Task operations
-    fn selected_task(&self) -> Option<&Task> {
-        match self.current_view {
-            CurrentView::Inbox => self.inbox_view.selected_task(),
-            CurrentView::Today => self.today_view.selected_task(),
-            CurrentView::Upcoming => self.upcoming_view.selected_task(),
-            CurrentView::Anytime => self.anytime_view.selected_task(),
-            CurrentView::Completed => self.completed_view.selected_task(),
-            CurrentView::Project => self.project_view.selected_task(),
-            CurrentView::Tag => self.tag_view.selected_task(),
-            CurrentView::Review => self.review_view.selected_task(),
-            _ => None,
+    pub fn selected_task(&self) -> Option<&Task> {
+        let task = self.task_list(self.current_view)?.selected_task()?;
+        // Lists hold lingering tasks as they were before completion; act on the
+        // completed one
+        if self.lingering.contains_key(&task.id) {
+            return self.tasks.iter().find(|t| t.id == task.id).or(Some(task));
+        }
+        Some(task)
+    }
+
+    pub fn current_task_list(&self) -> Option<&TaskListState> {
+        self.task_list(self.current_view)
+    }
+
+    pub fn current_task_list_mut(&mut self) -> Option<&mut TaskListState> {
+        self.task_list_mut(self.current_view)
+    }
+
+    /// The task list of a view, for the views in `TASK_LIST_VIEWS`
+    pub fn task_list(&self, view: CurrentView) -> Option<&TaskListState> {
+        Some(match view {
+            CurrentView::Inbox => &self.inbox_view.task_list,
+            CurrentView::Active => &self.active_view.task_list,
+            CurrentView::Scheduled => &self.scheduled_view.task_list,
+            CurrentView::Today => &self.today_view.task_list,
+            CurrentView::Agenda => &self.agenda_view.task_list,
+            CurrentView::Week => &self.week_view.task_list,
+            CurrentView::Upcoming => &self.upcoming_view.task_list,
+            CurrentView::Anytime => &self.anytime_view.task_list,
+            CurrentView::Flagged => &self.flagged_view.task_list,
+            CurrentView::Deferred => &self.deferred_view.task_list,
+            CurrentView::Completed => &self.completed_view.task_list,
+            CurrentView::Project => &self.project_view.task_list,
+            CurrentView::Tag => &self.tag_view.task_list,
+            CurrentView::Review => &self.review_view.task_list,
+            _ => return None,
+        })
+    }
+
+    pub fn task_list_mut(&mut self, view: CurrentView) -> Option<&mut TaskListState> {
+        Some(match view {
+            CurrentView::Inbox => &mut self.inbox_view.task_list,
+            CurrentView::Active => &mut self.active_view.task_list,
+            CurrentView::Scheduled => &mut self.scheduled_view.task_list,
+            CurrentView::Today => &mut self.today_view.task_list,
+            CurrentView::Agenda => &mut self.agenda_view.task_list,
+            CurrentView::Week => &mut self.week_view.task_list,
+            CurrentView::Upcoming => &mut self.upcoming_view.task_list,
+            CurrentView::Anytime => &mut self.anytime_view.task_list,
+            CurrentView::Flagged => &mut self.flagged_view.task_list,
+            CurrentView::Deferred => &mut self.deferred_view.task_list,
+            CurrentView::Completed => &mut self.completed_view.task_list,
+            CurrentView::Project => &mut self.project_view.task_list,
+            CurrentView::Tag => &mut self.tag_view.task_list,
+            CurrentView::Review => &mut self.review_view.task_list,
+            _ => return None,
+        })
+    }
+
+    fn for_each_task_list(&mut self, mut f: impl FnMut(CurrentView, &mut TaskListState)) {
+        for view in TASK_LIST_VIEWS {
+            if let Some(list) = self.task_list_mut(view) {
+                f(view, list);
+            }
         }
     }
 
+    pub fn toggle_notes_preview(&mut self) {
+        let show_notes = !self.inbox_view.task_list.show_notes;
+        self.for_each_task_list(|_, list| list.show_notes = show_notes);
+    }
+
     pub fn toggle_task_completed(&mut self) {
+        // Remember neighbours so the selection stays put if the task leaves the list
+        let fallback_ids = self
+            .current_task_list_mut()
+            .map(|list| list.selection_fallback_ids())
+            .unwrap_or_default();
+
         if let Some(task) = self.selected_task().cloned() {
             if let Ok(repo) = self.get_repo() {
                 if let Some(mut t) = self.tasks.iter().find(|t| t.id == task.id).cloned() {
                     if t.status == TaskStatus::Completed {
-                        t.status = TaskStatus::Inbox;
-                        t.completed_at = None;
+                        t.reopen();
                     } else {
-                        t.status = TaskStatus::Completed;
-                        t.completed_at = Some(Utc::now());
+                        t.complete();
                     }
                     t.updated_at = Utc::now();
                     let _ = repo.update_task(&t);
-                    let _ = self.load_data();
+                    self.replace_task(t);
                 }
             }
         }
+
+        if let Some(list) = self.current_task_list_mut() {
+            list.select_first_present(&fallback_ids);
+        }
+    }
+
+    /// Complete the selected task (never reopens) and move on to the next task
+    pub fn complete_and_advance(&mut self) {
+        // Skip the task itself: it may stay listed (e.g. in Flagged) once completed
+        let next_ids: Vec<String> = self
+            .current_task_list_mut()
+            .map(|list| list.selection_fallback_ids().into_iter().skip(1).collect())
+            .unwrap_or_default();
+
+        if let Some(id) = self.selected_task().map(|t| t.id.clone()) {
+            if let Some(mut t) = self.tasks.iter().find(|t| t.id == id).cloned() {
+                if !t.is_completed() {
+                    t.complete();
+                    t.updated_at = Utc::now();
+                    if let Err(e) = self.get_repo().and_then(|repo| repo.update_task(&t)) {
+                        self.show_error(format!("Failed to update task: {}", e));
+                        return;
+                    }
+                    self.replace_task(t);
+                }
+            }
+        }
+
+        if let Some(list) = self.current_task_list_mut() {
+            list.select_first_present(&next_ids);
+        }
     }
 
     pub fn set_task_priority(&mut self, priority: TaskPriority) {
@@ -374,24 +1277,357 @@ impl App {
         }
     }
 
+    /// Assign the selected task to the next project, wrapping around to no project
+    pub fn cycle_task_project(&mut self) {
+        let Some(mut t) = self.selected_task().cloned() else {
+            return;
+        };
+        let current = t
+            .project_id
+            .as_ref()
+            .and_then(|id| self.projects.iter().position(|p| &p.id == id));
+        t.project_id = match current {
+            None => self.projects.first(),
+            Some(i) => self.projects.get(i + 1),
+        }
+        .map(|p| p.id.clone());
+        t.updated_at = Utc::now();
+        if let Ok(repo) = self.get_repo() {
+            let _ = repo.update_task(&t);
+            let _ = self.load_data();
+        }
+    }
+
+    /// Mark or unmark the selected task for a batch action
+    pub fn toggle_task_marked(&mut self) {
+        if self.focus == FocusArea::Sidebar {
+            return;
+        }
+        if let Some(list) = self.current_task_list_mut() {
+            list.toggle_marked();
+        }
+    }
+
+    pub fn clear_marked(&mut self) {
+        if let Some(list) = self.current_task_list_mut() {
+            list.marked.clear();
+        }
+    }
+
+    /// Ask for a tag to add to the marked tasks, or to the selected one
+    pub fn start_tag_tasks(&mut self) {
+        let has_marks = self
+            .current_task_list_mut()
+            .is_some_and(|list| !list.marked.is_empty());
+        if has_marks || self.selected_task().is_some() {
+            self.start_triage_input("Tag:");
+        }
+    }
+
+    /// Add a tag (by name, created if needed) to the marked tasks in one
+    /// transaction, or to the selected task when none are marked. Triage always
+    /// tags just the task on its card.
+    fn add_task_tag(&mut self, name: &str) {
+        let name = Tag::clean_name(name);
+        if name.is_empty() {
+            return;
+        }
+        let marked: Vec<Task> = if self.triage {
+            Vec::new()
+        } else {
+            self.current_task_list_mut()
+                .map(|list| list.marked_tasks().into_iter().cloned().collect())
+                .unwrap_or_default()
+        };
+        let batch = !marked.is_empty();
+        let targets = if batch {
+            marked
+        } else {
+            self.selected_task().cloned().into_iter().collect()
+        };
+        if targets.is_empty() {
+            return;
+        }
+        let Ok(repo) = self.get_repo() else {
+            return;
+        };
+
+        // Reuse an existing tag that differs only in case
+        let tag_id = match repo.find_or_insert_tag(name) {
+            Ok(id) => id,
+            Err(e) => {
+                self.show_error(format!("Failed to create tag: {}", e));
+                return;
+            }
+        };
+        // Tasks that already have the tag are left alone
+        let now = Utc::now();
+        let changed: Vec<Task> = targets
+            .into_iter()
+            .filter(|t| !t.tags.contains(&tag_id))
+            .map(|mut t| {
+                t.tags.push(tag_id.clone());
+                t.updated_at = now;
+                t
+            })
+            .collect();
+        if let Err(e) = repo.update_tasks(&changed) {
+            self.show_error(format!("Failed to tag tasks: {}", e));
+            return;
+        }
+        if batch {
+            self.clear_marked();
+            self.show_info(format!("Tagged {} tasks", changed.len()));
+        }
+        let _ = self.load_data();
+    }
+
+    /// Set the selected task's due date from user input; empty input clears it
+    fn set_task_due_date(&mut self, input: &str) {
+        let due_date = if input.trim().is_empty() {
+            None
+        } else {
+            match parse_due_date(input, Utc::now().date_naive()) {
+                Some(date) => Some(date),
+                None => {
+                    self.show_error(format!("Invalid date: {}", input));
+                    return;
+                }
+            }
+        };
+        let Some(mut t) = self.selected_task().cloned() else {
+            return;
+        };
+        t.due_date = due_date;
+        t.updated_at = Utc::now();
+        if let Ok(repo) = self.get_repo() {
+            let _ = repo.update_task(&t);
+            self.replace_task(t);
+        }
+    }
+
+    /// Focus the detail pane and ask for a line to add to the selected task's notes
+    pub fn start_append_note(&mut self) {
+        if self.selected_task().is_none() {
+            self.show_info("No task selected".to_string());
+            return;
+        }
+        if !self.focus_mode {
+            self.focus = FocusArea::Detail;
+        }
+        self.start_triage_input("Append note:");
+    }
+
+    fn append_task_note(&mut self, line: &str) {
+        let Some(mut t) = self.selected_task().cloned() else {
+            return;
+        };
+        t.notes = Some(match t.notes.as_deref().map(str::trim_end) {
+            Some(notes) if !notes.is_empty() => format!("{}\n{}", notes, line.trim_end()),
+            _ => line.trim_end().to_string(),
+        });
+        t.updated_at = Utc::now();
+        match self.get_repo().and_then(|repo| repo.update_task(&t)) {
+            Ok(()) => self.replace_task(t),
+            Err(e) => self.show_error(format!("Failed to update task: {}", e)),
+        }
+    }
+
+    /// Ask how long was spent on the selected task
+    pub fn start_log_time(&mut self) {
+        if self.selected_task().is_none() {
+            self.show_info("No task selected".to_string());
+            return;
+        }
+        self.start_triage_input("Log time:");
+    }
+
+    /// Add the time in `input` to the selected task's logged time
+    fn log_task_time(&mut self, input: &str) {
+        let Some(minutes) = parse_estimate(input) else {
+            self.show_error(format!("Invalid duration: {}", input));
+            return;
+        };
+        let Some(mut t) = self.selected_task().cloned() else {
+            return;
+        };
+        let Some(total) = t.time_spent_minutes.checked_add(minutes) else {
+            self.show_error("Logged time is too large".to_string());
+            return;
+        };
+        t.time_spent_minutes = total;
+        t.updated_at = Utc::now();
+        if let Ok(repo) = self.get_repo() {
+            if let Err(e) = repo.update_task(&t) {
+                self.show_error(format!("Failed to log time: {}", e));
+                return;
+            }
+            let _ = self.load_data();
+            self.show_info(format!(
+                "Logged {} (total {})",
+                format_estimate(minutes),
+                format_estimate(t.time_spent_minutes)
+            ));
+        }
+    }
+
+    /// Clear the selected task's due and start dates, which moves an open task to
+    /// Anytime
+    pub fn clear_task_dates(&mut self) {
+        let Some(mut t) = self.selected_task().cloned() else {
+            self.show_info("No task selected".to_string());
+            return;
+        };
+        if t.due_date.is_none() && t.start_date.is_none() {
+            self.show_info("Task has no dates to clear".to_string());
+            return;
+        }
+
+        t.due_date = None;
+        t.start_date = None;
+        t.updated_at = Utc::now();
+        match self.get_repo().and_then(|repo| repo.update_task(&t)) {
+            Ok(()) => {
+                let destination = if t.status.is_open() { " (now in Anytime)" } else { "" };
+                let message = format!("Unscheduled: {}{}", t.title, destination);
+                self.replace_task(t);
+                self.show_info(message);
+            }
+            Err(e) => self.show_error(format!("Failed to update task: {}", e)),
+        }
+    }
+
+    pub fn toggle_task_flagged(&mut self) {
+        if let Some(task) = self.selected_task().cloned() {
+            if let Ok(repo) = self.get_repo() {
+                if let Some(mut t) = self.tasks.iter().find(|t| t.id == task.id).cloned() {
+                    t.flagged = !t.flagged;
+                    t.updated_at = Utc::now();
+                    let _ = repo.update_task(&t);
+                    self.replace_task(t);
+                }
+            }
+        }
+    }
+
     pub fn set_task_status(&mut self, status: TaskStatus) {
         if let Some(task) = self.selected_task().cloned() {
             if let Ok(repo) = self.get_repo() {
                 if let Some(mut t) = self.tasks.iter().find(|t| t.id == task.id).cloned() {
-                    t.status = status;
+                    t.set_status(status);
                     t.updated_at = Utc::now();
-                    if status == TaskStatus::Completed {
-                        t.completed_at = Some(Utc::now());
-                    } else {
-                        t.completed_at = None;
-                    }
                     let _ = repo.update_task(&t);
-                    let _ = self.load_data();
+                    self.replace_task(t);
                 }
             }
         }
     }
 
+    /// Advance the selected task to the next workflow status
+    pub fn cycle_task_status(&mut self) {
+        let Some(id) = self.selected_task().map(|t| t.id.clone()) else {
+            return;
+        };
+        let Some(mut t) = self.tasks.iter().find(|t| t.id == id).cloned() else {
+            return;
+        };
+
+        let status = t.status.next();
+        t.set_status(status);
+        t.updated_at = Utc::now();
+
+        match self.get_repo().and_then(|repo| repo.update_task(&t)) {
+            Ok(()) => {
+                self.replace_task(t);
+                self.show_info(format!("Status: {}", status.as_str()));
+            }
+            Err(e) => self.show_error(format!("Failed to update task: {}", e)),
+        }
+    }
+
+    /// Unschedule the selected task and send it back to the Inbox for re-triage:
+    /// status Inbox, due and start dates cleared (a future start date would keep
+    /// it out of the Inbox)
+    pub fn bump_to_inbox(&mut self) {
+        let Some(id) = self.selected_task().map(|t| t.id.clone()) else {
+            self.show_info("No task selected".to_string());
+            return;
+        };
+        let Some(mut t) = self.tasks.iter().find(|t| t.id == id).cloned() else {
+            return;
+        };
+
+        t.set_status(TaskStatus::Inbox);
+        t.due_date = None;
+        t.start_date = None;
+        t.updated_at = Utc::now();
+
+        match self.get_repo().and_then(|repo| repo.update_task(&t)) {
+            Ok(()) => {
+                let _ = self.load_data();
+                self.show_info(format!("Back to Inbox: {}", t.title));
+            }
+            Err(e) => self.show_error(format!("Failed to update task: {}", e)),
+        }
+    }
+
+    /// Move the selected task above (or below) every other task in the current list
+    pub fn move_task_to_edge(&mut self, top: bool) {
+        if !self.current_view.is_manually_ordered() {
+            self.show_info(format!(
+                "The {} view sorts tasks itself; T/B reorder lists in manual order",
+                self.current_view.key()
+            ));
+            return;
+        }
+        let Some(list) = self.current_task_list_mut() else {
+            return;
+        };
+        let Some(id) = list.selected_task().map(|t| t.id.clone()) else {
+            return;
+        };
+        let others = list.tasks.iter().filter(|t| t.id != id).map(|t| t.order_index);
+        let Some(order_index) = (if top {
+            others.min().map(|min| min - 1)
+        } else {
+            others.max().map(|max| max + 1)
+        }) else {
+            return;
+        };
+        let Some(mut t) = self.tasks.iter().find(|t| t.id == id).cloned() else {
+            return;
+        };
+
+        t.order_index = order_index;
+        t.updated_at = Utc::now();
+        match self.get_repo().and_then(|repo| repo.update_task(&t)) {
+            Ok(()) => {
+                let _ = self.load_data();
+            }
+            Err(e) => self.show_error(format!("Failed to move task: {}", e)),
+        }
+    }
+
+    /// Copy the selected task's URL (or its id, for tasks without one)
+    pub fn copy_task_reference(&mut self) {
+        let Some(task) = self.selected_task() else {
+            self.show_info("No task selected".to_string());
+            return;
+        };
+        let (what, text) = match task.context_url {
+            Some(ref url) => ("URL", url.clone()),
+            None => ("id", task.id.clone()),
+        };
+        match copy_to_clipboard(&text) {
+            Ok(ClipboardCopy::Copied) => self.show_info(format!("Copied {}: {}", what, text)),
+            Ok(ClipboardCopy::SentToTerminal) => self.show_info(format!(
+                "No clipboard tool found; asked the terminal to copy the {}: {}",
+                what, text
+            )),
+            Err(e) => self.show_error(format!("Failed to copy {}: {}", what, e)),
+        }
+    }
+
     pub fn open_task_url(&mut self) {
         if let Some(task) = self.selected_task() {
             if let Some(ref url) = task.context_url {
@@ -404,14 +1640,42 @@ impl App {
         }
     }
 
-    pub fn start_new_task(&mut self) {
-        self.task_form = Some(TaskFormState::new_task(self.projects.clone()));
-        self.mode = AppMode::TaskForm;
+    pub fn start_new_task(&mut self) {
+        let mut form = TaskFormState::new_task(self.projects.clone());
+        form.preselect(self.last_new_project.as_deref(), self.last_new_kind.clone());
+        form.project_pinned = self.project_pinned;
+        form.enter = FormEnter::from_str(&self.config.form_enter);
+        self.task_form = Some(form);
+        self.mode = AppMode::TaskForm;
+    }
+
+    /// Pin the form's project so new tasks keep getting it, even after one is saved
+    /// to another project, until it's unpinned or the view changes
+    pub fn toggle_project_pin(&mut self) {
+        let Some(ref mut form) = self.task_form else {
+            return;
+        };
+        form.project_pinned = !form.project_pinned;
+        self.project_pinned = form.project_pinned;
+        self.last_new_project = if self.project_pinned {
+            form.task.project_id.clone()
+        } else {
+            None
+        };
+    }
+
+    /// Leaving a view drops the new-task defaults, pin included
+    fn forget_new_task_defaults(&mut self) {
+        self.project_pinned = false;
+        self.last_new_project = None;
+        self.last_new_kind = None;
     }
 
     pub fn start_edit_task(&mut self) {
         if let Some(task) = self.selected_task().cloned() {
-            self.task_form = Some(TaskFormState::edit_task(task, self.projects.clone()));
+            let mut form = TaskFormState::edit_task(task, self.projects.clone());
+            form.enter = FormEnter::from_str(&self.config.form_enter);
+            self.task_form = Some(form);
             self.mode = AppMode::TaskForm;
         }
     }
@@ -432,15 +1696,30 @@ impl App {
                 form.task.due_date,
                 form.task.priority,
                 form.task.status,
-                form.task.kind,
+                form.task.kind.clone(),
                 form.task.size,
+                form.task.estimate_minutes,
+                form.task.project_id.clone(),
                 form.task.id.clone(),
             ))
         } else {
             None
         };
 
-        if let Some((is_new, title, notes, due_date, priority, status, kind, size, id)) = form_data {
+        if let Some((
+            is_new,
+            title,
+            notes,
+            due_date,
+            priority,
+            status,
+            kind,
+            size,
+            estimate_minutes,
+            project_id,
+            id,
+        )) = form_data
+        {
             if let Ok(repo) = self.get_repo() {
                 if is_new {
                     let mut task = Task::new(title);
@@ -448,10 +1727,16 @@ impl App {
                     task.due_date = due_date;
                     task.priority = priority;
                     task.status = status;
-                    task.kind = kind;
+                    task.kind = kind.clone();
                     task.size = size;
+                    task.estimate_minutes = estimate_minutes;
+                    task.project_id = project_id.clone();
+                    if !self.project_pinned {
+                        self.last_new_project = project_id;
+                    }
+                    self.last_new_kind = kind;
 
-                    if let Ok(idx) = repo.get_next_order_index("tasks") {
+                    if let Ok(idx) = repo.get_next_order_index(OrderedTable::Tasks) {
                         task.order_index = idx;
                     }
 
@@ -467,6 +1752,8 @@ impl App {
                         task.status = status;
                         task.kind = kind;
                         task.size = size;
+                        task.estimate_minutes = estimate_minutes;
+                        task.project_id = project_id;
                         task.updated_at = Utc::now();
                         if let Err(e) = repo.update_task(&task) {
                             self.show_error(format!("Failed to update task: {}", e));
@@ -481,12 +1768,211 @@ impl App {
         self.mode = AppMode::Normal;
     }
 
+    pub fn start_paste_tasks(&mut self) {
+        self.input = InputState::new("Paste tasks:").with_placeholder("One task per line (#tag, !priority)");
+        self.mode = AppMode::Input;
+    }
+
+    /// Insert pasted text into the active input
+    pub fn handle_paste(&mut self, text: &str) {
+        if self.mode == AppMode::Input {
+            self.input.insert_str(&text.replace("\r\n", "\n").replace('\r', "\n"));
+        }
+    }
+
+    /// Create one task per non-empty line, using the current view's status/project
+    fn create_tasks_from_lines(&mut self, text: &str) {
+        let Ok(repo) = self.get_repo() else {
+            return;
+        };
+
+        let status = match self.current_view {
+            CurrentView::Active => TaskStatus::Active,
+            _ => TaskStatus::Inbox,
+        };
+        let project_id = match self.current_view {
+            CurrentView::Project => self.project_view.project.as_ref().map(|p| p.id.clone()),
+            _ => None,
+        };
+        let view_tag_id = match self.current_view {
+            CurrentView::Tag => self.tag_view.tag.as_ref().map(|t| t.id.clone()),
+            _ => None,
+        };
+
+        // Tag ids by normalized name, so "Work" and "work" share one tag
+        let mut tag_ids: std::collections::HashMap<String, String> = self
+            .tags
+            .iter()
+            .map(|t| (Tag::key(&t.name), t.id.clone()))
+            .collect();
+        let mut new_tags: Vec<Tag> = Vec::new();
+        let mut order_index = repo.get_next_order_index(OrderedTable::Tasks).unwrap_or(0);
+        let mut tasks: Vec<Task> = Vec::new();
+
+        for line in text.lines() {
+            let parsed = parse_quick_add(line);
+            if parsed.title.is_empty() {
+                continue;
+            }
+
+            let mut task = Task::new(parsed.title);
+            task.status = status;
+            task.priority = parsed.priority;
+            task.project_id = project_id.clone();
+            task.order_index = order_index;
+            order_index += 1;
+
+            task.tags.extend(view_tag_id.clone());
+            for name in parsed.tags {
+                let name = Tag::clean_name(&name).to_string();
+                if name.is_empty() {
+                    continue;
+                }
+                let id = tag_ids.entry(Tag::key(&name)).or_insert_with(|| {
+                    let tag = Tag::new(name);
+                    let id = tag.id.clone();
+                    new_tags.push(tag);
+                    id
+                });
+                if !task.tags.contains(id) {
+                    task.tags.push(id.clone());
+                }
+            }
+            tasks.push(task);
+        }
+
+        if tasks.is_empty() {
+            return;
+        }
+
+        match repo.insert_tasks(&new_tags, &tasks) {
+            Ok(()) => self.show_info(format!("Created {} tasks", tasks.len())),
+            Err(e) => self.show_error(format!("Failed to create tasks: {}", e)),
+        }
+        let _ = self.load_data();
+    }
+
     pub fn start_new_project(&mut self) {
         self.input = InputState::new("Project name:").with_placeholder("Enter project name");
         self.mode = AppMode::Input;
     }
 
+    /// The project selected in the sidebar or shown in the Project view
+    fn targeted_project(&self) -> Option<&Project> {
+        if self.focus == FocusArea::Sidebar {
+            self.sidebar.selected_project.as_ref()
+        } else if self.current_view == CurrentView::Project {
+            self.project_view.project.as_ref().map(|p| &p.id)
+        } else {
+            None
+        }
+        .and_then(|id| self.projects.iter().find(|p| &p.id == id))
+    }
+
+    /// Ask for the area of the project selected in the sidebar or shown in the
+    /// Project view
+    pub fn start_set_project_area(&mut self) {
+        let Some(project) = self.targeted_project() else {
+            return;
+        };
+        let (id, area) = (project.id.clone(), project.area.clone());
+        self.pending_area_project_id = Some(id);
+        self.input = InputState::new("Area:")
+            .with_placeholder("Area name (empty removes the project from its area)")
+            .with_value(area.unwrap_or_default());
+        self.mode = AppMode::Input;
+    }
+
+    /// Pin or unpin the project selected in the sidebar or shown in the Project
+    /// view; pinned projects head the sidebar's project list
+    pub fn toggle_project_pinned(&mut self) {
+        let Some(mut project) = self.targeted_project().cloned() else {
+            return;
+        };
+        project.pinned = !project.pinned;
+        project.updated_at = Utc::now();
+        match self.get_repo().and_then(|repo| repo.update_project(&project)) {
+            Ok(()) => {
+                let verb = if project.pinned { "Pinned" } else { "Unpinned" };
+                self.show_info(format!("{}: {}", verb, project.name));
+                let _ = self.load_data();
+            }
+            Err(e) => self.show_error(format!("Failed to update project: {}", e)),
+        }
+    }
+
+    fn set_project_area(&mut self, area: &str) {
+        let Some(id) = self.pending_area_project_id.take() else {
+            return;
+        };
+        let Some(mut project) = self.projects.iter().find(|p| p.id == id).cloned() else {
+            return;
+        };
+        let area = area.trim();
+        project.area = if area.is_empty() { None } else { Some(area.to_string()) };
+        project.updated_at = Utc::now();
+        match self.get_repo().and_then(|repo| repo.update_project(&project)) {
+            Ok(()) => {
+                let _ = self.load_data();
+            }
+            Err(e) => self.show_error(format!("Failed to update project: {}", e)),
+        }
+    }
+
+    pub fn start_edit_metadata(&mut self) {
+        if self.selected_task().is_some() {
+            self.input = InputState::new("Metadata:").with_placeholder("key=value (empty value removes)");
+            self.mode = AppMode::Input;
+        }
+    }
+
+    /// Apply a `key=value` metadata edit to the selected task; an empty value removes the key
+    fn apply_metadata_edit(&mut self, entry: &str) {
+        let Some((key, value)) = entry.split_once('=') else {
+            self.show_error("Expected key=value".to_string());
+            return;
+        };
+        let key = key.trim();
+        let value = value.trim();
+        if key.is_empty() {
+            self.show_error("Metadata key cannot be empty".to_string());
+            return;
+        }
+        if key.starts_with("github_") {
+            self.show_error(format!("\"{}\" is managed by GitHub sync", key));
+            return;
+        }
+        if is_internal_metadata_key(key) {
+            self.show_error(format!("\"{}\" is reserved for internal use", key));
+            return;
+        }
+
+        let Some(mut task) = self.selected_task().cloned() else {
+            return;
+        };
+        if value.is_empty() {
+            task.metadata.remove(key);
+        } else {
+            task.metadata.insert(key.to_string(), value.to_string());
+        }
+        task.updated_at = Utc::now();
+
+        if let Ok(repo) = self.get_repo() {
+            if let Err(e) = repo.update_task(&task) {
+                self.show_error(format!("Failed to update task: {}", e));
+            }
+            let _ = self.load_data();
+        }
+    }
+
     pub fn start_delete(&mut self) {
+        if self.focus == FocusArea::Sidebar {
+            if let Some(project_id) = self.sidebar.selected_project.clone() {
+                self.start_delete_project(project_id);
+            }
+            return;
+        }
+
         let task_info = self.selected_task().map(|t| (t.title.clone(), t.id.clone()));
         if let Some((title, id)) = task_info {
             self.confirm_modal = Some(ConfirmModal::delete(&title));
@@ -495,6 +1981,29 @@ impl App {
         }
     }
 
+    fn start_delete_project(&mut self, project_id: String) {
+        let Some(name) = self
+            .projects
+            .iter()
+            .find(|p| p.id == project_id)
+            .map(|p| p.name.clone())
+        else {
+            return;
+        };
+        let open_tasks = self
+            .get_repo()
+            .and_then(|repo| repo.count_tasks_for_project(&project_id))
+            .unwrap_or(0);
+
+        self.confirm_modal = Some(if open_tasks > 0 {
+            ConfirmModal::delete_project(&name, open_tasks)
+        } else {
+            ConfirmModal::delete(&name)
+        });
+        self.pending_delete_project_id = Some(project_id);
+        self.mode = AppMode::Confirm;
+    }
+
     pub fn execute_confirm(&mut self) {
         if let Some(id) = self.pending_delete_id.take() {
             if let Ok(repo) = self.get_repo() {
@@ -502,18 +2011,122 @@ impl App {
                 let _ = self.load_data();
             }
         }
+        if let Some(id) = self.pending_delete_project_id.take() {
+            let delete_tasks = self
+                .confirm_modal
+                .as_ref()
+                .is_some_and(|m| m.alternative_selected);
+            match self
+                .get_repo()
+                .and_then(|repo| repo.delete_project_and_tasks(&id, delete_tasks))
+            {
+                Ok(()) => {
+                    if self.current_view == CurrentView::Project
+                        && self.project_view.project.as_ref().is_some_and(|p| p.id == id)
+                    {
+                        self.switch_to_view(SidebarItem::Inbox);
+                    }
+                    self.sidebar.selected_project = None;
+                    let _ = self.load_data();
+                }
+                Err(e) => self.show_error(format!("Failed to delete project: {}", e)),
+            }
+        }
+        if std::mem::take(&mut self.pending_vacuum) {
+            self.vacuum_database();
+        }
+        if std::mem::take(&mut self.pending_quit) {
+            self.quit_requested = true;
+        }
+        self.close_confirm();
+    }
+
+    pub fn cancel_confirm(&mut self) {
+        self.pending_delete_id = None;
+        self.pending_delete_project_id = None;
+        self.pending_vacuum = false;
+        self.pending_quit = false;
+        self.close_confirm();
+    }
+
+    /// Ask before quitting (with `confirm_quit`), dropping any other open
+    /// confirmation; `q` again or Enter quits
+    pub fn start_quit(&mut self) {
+        if self.pending_quit || !self.config.confirm_quit {
+            self.quit_requested = true;
+            return;
+        }
+        self.cancel_confirm();
+        self.confirm_modal = Some(ConfirmModal::quit());
+        self.pending_quit = true;
+        self.mode = AppMode::Confirm;
+    }
+
+    /// Close the confirm modal, returning to the mode of the current view
+    fn close_confirm(&mut self) {
         self.confirm_modal = None;
-        self.mode = AppMode::Normal;
+        self.mode = if self.current_view == CurrentView::Settings {
+            AppMode::Settings
+        } else {
+            AppMode::Normal
+        };
+    }
+
+    pub fn start_vacuum(&mut self) {
+        let modal = ConfirmModal::vacuum(self.config.purge_deleted_after_days);
+        self.confirm_modal = Some(if self.config.type_to_confirm {
+            modal.require_word("yes")
+        } else {
+            modal
+        });
+        self.pending_vacuum = true;
+        self.mode = AppMode::Confirm;
+    }
+
+    fn vacuum_database(&mut self) {
+        // A retention too long to represent means nothing is ever old enough to purge
+        let Some(cutoff) = days_ago(self.config.purge_deleted_after_days.max(0)) else {
+            self.show_info("Vacuum complete: nothing is old enough to purge".to_string());
+            return;
+        };
+        match self.get_repo().and_then(|repo| repo.purge_deleted_before(cutoff)) {
+            Ok(count) => {
+                let _ = self.load_data();
+                self.show_info(format!("Vacuum complete: removed {} deleted rows", count));
+            }
+            Err(e) => self.show_error(format!("Failed to vacuum database: {}", e)),
+        }
     }
 
-    pub fn start_search(&mut self) {
-        self.input = InputState::new("/").with_placeholder("Search tasks...");
+    /// Open the filter prompt for the current list, which narrows it as you type
+    pub fn start_list_filter(&mut self) {
+        let Some(list) = self.current_task_list_mut() else {
+            return;
+        };
+        let current = list.filter.clone();
+        self.input = InputState::new("Filter:")
+            .with_placeholder("Type to filter this list...")
+            .with_value(current);
         self.mode = AppMode::Input;
     }
 
+    /// Apply the filter prompt's text to the current list
+    pub fn update_list_filter(&mut self) {
+        let query = self.input.value.clone();
+        if let Some(list) = self.current_task_list_mut() {
+            list.set_filter(query);
+        }
+    }
+
     pub fn cancel_input(&mut self) {
+        if self.input.prompt == "Filter:" {
+            if let Some(list) = self.current_task_list_mut() {
+                list.set_filter("");
+            }
+        }
         self.input.clear();
         self.mode = AppMode::Normal;
+        self.continue_triage();
     }
 
     pub fn submit_input(&mut self) {
@@ -526,18 +2139,109 @@ impl App {
         if prompt == "Project name:" && !value.is_empty() {
             if let Ok(repo) = self.get_repo() {
                 let mut project = Project::new(value);
-                if let Ok(idx) = repo.get_next_order_index("projects") {
+                if let Ok(idx) = repo.get_next_order_index(OrderedTable::Projects) {
                     project.order_index = idx;
                 }
                 let _ = repo.insert_project(&project);
                 let _ = self.load_data();
             }
+        } else if prompt == "Metadata:" && !value.is_empty() {
+            self.apply_metadata_edit(&value);
+        } else if prompt == "Paste tasks:" && !value.is_empty() {
+            self.create_tasks_from_lines(&value);
+        } else if prompt == "Tag:" && !value.trim().is_empty() {
+            self.add_task_tag(&value);
+        } else if prompt == "Append note:" && !value.trim().is_empty() {
+            self.append_task_note(&value);
+        } else if prompt == "Log time:" && !value.trim().is_empty() {
+            self.log_task_time(&value);
+        } else if prompt == "Completed:" {
+            self.set_completed_range(&value);
+        } else if prompt == "Due date:" {
+            self.set_task_due_date(&value);
+        } else if prompt == "Area:" {
+            self.set_project_area(&value);
+        }
+        self.continue_triage();
+    }
+
+    /// Maintenance action: renumber task and project ordering
+    pub fn normalize_order(&mut self) {
+        let result = self.get_repo().and_then(|repo| {
+            repo.normalize_order_indexes(OrderedTable::Tasks)?;
+            repo.normalize_order_indexes(OrderedTable::Projects)
+        });
+        match result {
+            Ok(()) => {
+                let _ = self.load_data();
+                self.show_info("Task and project order normalized".to_string());
+            }
+            Err(e) => self.show_error(format!("Failed to normalize order: {}", e)),
+        }
+    }
+
+    /// Maintenance action: fold tags whose names differ only in case or spacing
+    /// into one
+    pub fn merge_duplicate_tags(&mut self) {
+        match self.get_repo().and_then(|repo| repo.merge_duplicate_tags()) {
+            Ok(0) => self.show_info("No duplicate tags".to_string()),
+            Ok(count) => {
+                let _ = self.load_data();
+                self.show_info(format!("Merged {} duplicate tags", count));
+            }
+            Err(e) => self.show_error(format!("Failed to merge tags: {}", e)),
         }
     }
 
+    /// Re-read the config file (after editing it externally) and apply it
+    pub fn reload_config(&mut self) {
+        let config = match Config::load() {
+            Ok(config) => config,
+            Err(e) => {
+                self.show_error(format!("Config not reloaded: {}", e));
+                return;
+            }
+        };
+
+        Theme::apply_config(&config);
+        let show_notes = config.list_notes_preview;
+        let row_accent = RowAccent::from_str(&config.row_accent);
+        let title_overflow = TitleOverflow::from_str(&config.title_overflow);
+        let max_title_width = config.max_title_width;
+        self.for_each_task_list(|_, list| {
+            list.show_notes = show_notes;
+            list.row_accent = row_accent;
+            list.title_overflow = title_overflow;
+            list.max_title_width = max_title_width;
+        });
+        self.today_view.set_include_overdue(config.today_includes_overdue);
+        self.week_view.include_overdue = config.week_includes_overdue;
+        self.toggl_view.chart_state.week_start = config.first_weekday();
+        self.stats_view.week_start = config.first_weekday();
+        self.completed_view.week_start = config.first_weekday();
+        self.sidebar.project_sort = ProjectSort::from_str(&config.project_sort);
+        self.sidebar.layout = SidebarLayout::from_config(&config);
+        self.github_view.active_column = GitHubColumn::from_str(&config.github_start_column);
+        self.settings_view.config = config.clone();
+        self.config = config;
+        self.reset_fetch_cooldowns();
+        self.update_sidebar_counts();
+        self.update_views();
+        self.show_info("Config reloaded".to_string());
+    }
+
+    /// Allow the next GitHub/Toggl fetch to go through even if the last one failed
+    pub fn reset_fetch_cooldowns(&mut self) {
+        self.github_view.failed_at = None;
+        self.toggl_view.failed_at = None;
+    }
+
     pub fn refresh_data(&mut self) {
         let _ = self.load_data();
 
+        // An explicit refresh always retries
+        self.reset_fetch_cooldowns();
+
         if self.current_view == CurrentView::GitHub {
             self.fetch_github_data();
         } else if self.current_view == CurrentView::Toggl {
@@ -547,6 +2251,7 @@ impl App {
 
     pub fn show_error(&mut self, message: String) {
         self.notification = Some(NotificationModal::error(message));
+        self.ring_bell |= self.config.bell_on_error;
     }
 
     pub fn show_info(&mut self, message: String) {
@@ -569,12 +2274,18 @@ impl App {
             return;
         }
 
+        // Keep showing a recent failure instead of retrying a known-bad request
+        if in_error_cooldown(self.github_view.failed_at) {
+            return;
+        }
+
         self.github_view.set_loading(true);
         let token = token.clone();
+        let retries = self.config.http_retries;
         let tx = self.async_tx.clone();
 
         tokio::spawn(async move {
-            let service = GitHubService::new(token);
+            let service = GitHubService::new(token).with_retries(retries);
             let result = service.fetch_all().await;
             let _ = tx.send(AsyncMessage::GitHubDataReady(
                 result.map_err(|e| e.to_string()),
@@ -593,20 +2304,31 @@ impl App {
             return;
         }
 
+        // Keep showing a recent failure instead of retrying a known-bad request
+        if in_error_cooldown(self.toggl_view.failed_at) {
+            return;
+        }
+
         self.toggl_view.set_loading(true);
         let token = token.clone();
+        let retries = self.config.http_retries;
         let tx = self.async_tx.clone();
 
         tokio::spawn(async move {
-            let service = TogglService::new(token);
-            let result = service.fetch_all(7).await;
+            let service = TogglService::new(token).with_retries(retries);
+            // Two weeks back covers this week and last week for the comparison
+            let result = service.fetch_all(14).await;
             let _ = tx.send(AsyncMessage::TogglDataReady(
                 result.map_err(|e| e.to_string()),
             ));
         });
     }
 
-    /// Sync GitHub items to local tasks
+    /// Sync GitHub items to local tasks.
+    ///
+    /// Title and notes are only taken from GitHub when a task is created; after that
+    /// they belong to the user and are never overwritten. Existing tasks only get
+    /// their status (closed items complete) and missing project/tag updated.
     fn sync_github_to_tasks(&mut self, data: &GitHubData) {
         let repo = match self.get_repo() {
             Ok(r) => r,
@@ -625,6 +2347,10 @@ impl App {
             github_items.push((pr, "review"));
         }
 
+        // The same item can show up in several result sets; only sync it once
+        let mut collected_urls: std::collections::HashSet<&str> = std::collections::HashSet::new();
+        github_items.retain(|(item, _)| collected_urls.insert(item.html_url.as_str()));
+
         // Build a map of repo names to project IDs, creating projects as needed
         let mut repo_to_project: std::collections::HashMap<String, String> = std::collections::HashMap::new();
 
@@ -635,6 +2361,14 @@ impl App {
             repo_to_project.insert(project.name.clone(), project.id.clone());
         }
 
+        // In tag mode, repos map to tags instead of projects
+        let use_tags = self.config.github_sync_uses_tags();
+        let mut repo_to_tag: std::collections::HashMap<String, String> = self
+            .tags
+            .iter()
+            .map(|t| (t.name.clone(), t.id.clone()))
+            .collect();
+
         // Track which GitHub URLs we've seen (to mark closed items)
         let mut seen_urls: std::collections::HashSet<String> = std::collections::HashSet::new();
 
@@ -642,22 +2376,37 @@ impl App {
             seen_urls.insert(item.html_url.clone());
             let repo_name = item.repo_name();
 
-            // Get or create project for this repo
-            let project_id = if let Some(id) = repo_to_project.get(&repo_name) {
-                Some(id.clone())
+            // Get or create the project (or tag) for this repo
+            let (project_id, tag_id) = if use_tags {
+                let tag_id = if let Some(id) = repo_to_tag.get(&repo_name) {
+                    Some(id.clone())
+                } else {
+                    // Create a new tag for this repo
+                    let tag = Tag::new(repo_name.clone());
+                    let tag_id = tag.id.clone();
+                    if repo.insert_tag(&tag).is_ok() {
+                        repo_to_tag.insert(repo_name.clone(), tag_id.clone());
+                        Some(tag_id)
+                    } else {
+                        None
+                    }
+                };
+                (None, tag_id)
+            } else if let Some(id) = repo_to_project.get(&repo_name) {
+                (Some(id.clone()), None)
             } else {
                 // Create a new project for this repo
                 let mut project = Project::new(repo_name.clone());
                 project.icon = Some("".to_string()); // GitHub icon
-                if let Ok(idx) = repo.get_next_order_index("projects") {
+                if let Ok(idx) = repo.get_next_order_index(OrderedTable::Projects) {
                     project.order_index = idx;
                 }
                 let project_id = project.id.clone();
                 if repo.insert_project(&project).is_ok() {
                     repo_to_project.insert(repo_name.clone(), project_id.clone());
-                    Some(project_id)
+                    (Some(project_id), None)
                 } else {
-                    None
+                    (None, None)
                 }
             };
 
@@ -667,24 +2416,8 @@ impl App {
             });
 
             if let Some(task) = existing_task {
-                // Task exists - check if we need to update it
-                let mut needs_update = false;
-                let mut updated_task = task.clone();
-
-                if item.state == "closed" && task.status != TaskStatus::Completed {
-                    updated_task.status = TaskStatus::Completed;
-                    updated_task.completed_at = Some(Utc::now());
-                    needs_update = true;
-                }
-
-                // Update project assignment if not set
-                if task.project_id.is_none() && project_id.is_some() {
-                    updated_task.project_id = project_id.clone();
-                    needs_update = true;
-                }
-
-                if needs_update {
-                    updated_task.updated_at = Utc::now();
+                let merged = merge_github_item(task, item, project_id.as_deref(), tag_id.as_deref());
+                if let Some(updated_task) = merged {
                     let _ = repo.update_task(&updated_task);
                 }
             } else {
@@ -693,6 +2426,7 @@ impl App {
                 task.context_url = Some(item.html_url.clone());
                 task.status = TaskStatus::Inbox;
                 task.project_id = project_id;
+                task.tags = tag_id.into_iter().collect();
                 task.notes = item.body.clone();
                 task.metadata.insert("github_id".to_string(), item.id.to_string());
                 task.metadata.insert("github_type".to_string(), github_type.to_string());
@@ -706,34 +2440,42 @@ impl App {
                     _ => None,
                 };
 
-                if let Ok(idx) = repo.get_next_order_index("tasks") {
+                if let Ok(idx) = repo.get_next_order_index(OrderedTable::Tasks) {
                     task.order_index = idx;
                 }
                 let _ = repo.insert_task(&task);
             }
         }
 
-        // Check for tasks that were synced from GitHub but the item is now closed
-        for task in &self.tasks {
-            if let Some(ref url) = task.context_url {
-                if url.contains("github.com") && !seen_urls.contains(url) {
-                    // This GitHub item is no longer in our open lists - it's closed
-                    if task.status != TaskStatus::Completed {
-                        let mut updated_task = task.clone();
-                        updated_task.status = TaskStatus::Completed;
-                        updated_task.completed_at = Some(Utc::now());
-                        updated_task.updated_at = Utc::now();
-                        let _ = repo.update_task(&updated_task);
+        // Check for tasks that were synced from GitHub but the item is now closed.
+        // Skipped after a partial fetch, where missing items may just be unfetched.
+        if data.errors.is_empty() {
+            for task in &self.tasks {
+                if let Some(ref url) = task.context_url {
+                    if url.contains("github.com") && !seen_urls.contains(url) {
+                        // This GitHub item is no longer in our open lists - it's closed.
+                        // Tasks cancelled locally stay cancelled.
+                        if task.status.is_open() {
+                            let mut updated_task = task.clone();
+                            updated_task.complete();
+                            updated_task.updated_at = Utc::now();
+                            let _ = repo.update_task(&updated_task);
+                        }
                     }
                 }
             }
         }
 
+        // Sync may have appended many items; keep ordering dense and deterministic
+        let _ = repo.normalize_order_indexes(OrderedTable::Tasks);
+        let _ = repo.normalize_order_indexes(OrderedTable::Projects);
+
         // Reload tasks to reflect changes
         let _ = self.load_data();
     }
 
     pub fn poll_async_messages(&mut self) {
+        self.tick = self.tick.wrapping_add(1);
         while let Ok(msg) = self.async_rx.try_recv() {
             match msg {
                 AsyncMessage::GitHubDataReady(result) => {
@@ -755,3 +2497,323 @@ impl App {
         }
     }
 }
+
+#[cfg(test)]
+pub(crate) struct TestApp(App);
+
+#[cfg(test)]
+impl std::ops::Deref for TestApp {
+    type Target = App;
+
+    fn deref(&self) -> &App {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl std::ops::DerefMut for TestApp {
+    fn deref_mut(&mut self) -> &mut App {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0.db_path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration as Days;
+
+    use super::*;
+
+    fn fixtures() -> (Vec<Task>, Vec<Project>) {
+        let projects = vec![Project::new("Home".to_string()), Project::new("Work".to_string())];
+        let today = Utc::now().date_naive();
+        let mut tasks = Vec::new();
+        let statuses = [TaskStatus::Inbox, TaskStatus::Active, TaskStatus::Scheduled];
+        for (i, status) in statuses.into_iter().enumerate() {
+            for (j, due) in [None, Some(-2), Some(0), Some(3), Some(20)].into_iter().enumerate() {
+                let mut task = Task::new(format!("task {} {}", i, j));
+                task.status = status;
+                task.due_date = due.map(|d| today + Days::days(d));
+                task.flagged = j % 2 == 0;
+                task.project_id = projects.get(j % 3).map(|p| p.id.clone());
+                tasks.push(task);
+            }
+        }
+        (tasks, projects)
+    }
+
+    /// Apply `change` to each task in turn, adjusting the counts incrementally,
+    /// and check them against a full recount after every step
+    fn assert_incremental_matches(change: impl Fn(&mut Task, &[Project])) {
+        let config = Config::default();
+        let (mut tasks, projects) = fixtures();
+        let mut counts = count_all(&tasks, &projects, &config);
+        for i in 0..tasks.len() {
+            let mut updated = tasks[i].clone();
+            change(&mut updated, &projects);
+            count_task(&mut counts, &tasks[i], -1, &config);
+            tasks[i] = updated;
+            count_task(&mut counts, &tasks[i], 1, &config);
+            assert_eq!(counts, count_all(&tasks, &projects, &config), "after task {}", i);
+        }
+    }
+
+    #[test]
+    fn incremental_counts_follow_status_changes() {
+        assert_incremental_matches(|task, _| task.status = task.status.next());
+    }
+
+    #[test]
+    fn incremental_counts_follow_due_date_changes() {
+        let today = Utc::now().date_naive();
+        assert_incremental_matches(|task, _| {
+            task.due_date = match task.due_date {
+                None => Some(today),
+                Some(due) if due < today => None,
+                Some(due) => Some(due - Days::days(5)),
+            }
+        });
+    }
+
+    #[test]
+    fn incremental_counts_follow_project_changes() {
+        assert_incremental_matches(|task, projects| {
+            task.project_id = match task.project_id.as_deref() {
+                Some(id) if id == projects[0].id => Some(projects[1].id.clone()),
+                Some(_) => None,
+                None => Some(projects[0].id.clone()),
+            }
+        });
+    }
+
+    #[test]
+    fn incremental_counts_follow_completion() {
+        assert_incremental_matches(|task, _| task.complete());
+        assert_incremental_matches(|task, _| {
+            task.complete();
+            task.reopen();
+            task.flagged = !task.flagged;
+        });
+    }
+
+    #[test]
+    fn closed_tasks_do_not_count_as_open_project_tasks() {
+        let project = Project::new("Home".to_string());
+        let mut tasks = Vec::new();
+        for status in [TaskStatus::Active, TaskStatus::Completed, TaskStatus::Cancelled] {
+            let mut task = Task::new(status.as_str().to_string());
+            task.status = status;
+            task.project_id = Some(project.id.clone());
+            tasks.push(task);
+        }
+
+        let counts = count_all(&tasks, std::slice::from_ref(&project), &Config::default());
+        assert_eq!(counts.projects[&project.id], 1);
+    }
+
+    fn inbox(titles: &[&str]) -> Vec<Task> {
+        titles
+            .iter()
+            .enumerate()
+            .map(|(i, title)| {
+                let mut task = Task::new(title.to_string());
+                task.order_index = i as i64;
+                task
+            })
+            .collect()
+    }
+
+    fn lingering_config() -> Config {
+        Config {
+            completed_linger_seconds: 60,
+            ..Config::default()
+        }
+    }
+
+    fn inbox_titles(app: &App) -> Vec<&str> {
+        app.inbox_view.task_list.tasks.iter().map(|t| t.title.as_str()).collect()
+    }
+
+    #[test]
+    fn completed_tasks_linger_in_their_list() {
+        let mut app = App::for_test(lingering_config(), &inbox(&["first", "second"]));
+
+        app.set_task_status(TaskStatus::Completed);
+
+        assert_eq!(inbox_titles(&app), vec!["first", "second"]);
+        assert!(app.selected_task().is_some_and(|t| t.is_completed()));
+    }
+
+    #[test]
+    fn triage_completion_moves_on_without_lingering() {
+        let mut app = App::for_test(lingering_config(), &inbox(&["first", "second"]));
+        app.start_triage();
+
+        app.set_task_status(TaskStatus::Completed);
+        app.continue_triage();
+
+        assert_eq!(inbox_titles(&app), vec!["second"]);
+        assert_eq!(app.selected_task().map(|t| t.title.as_str()), Some("second"));
+        assert_eq!(app.mode, AppMode::Triage);
+    }
+
+    /// Open the new-task form in `app` with `project_id` chosen, and pin it
+    fn pin_new_task_project(app: &mut App, project_id: &str) {
+        app.start_new_task();
+        let form = app.task_form.as_mut().unwrap();
+        form.task.project_id = Some(project_id.to_string());
+        app.toggle_project_pin();
+        app.task_form = None;
+        app.mode = AppMode::Normal;
+    }
+
+    #[test]
+    fn switching_views_or_projects_drops_the_pinned_project() {
+        let mut app = App::for_test(Config::default(), &[]);
+        let project = Project::new("Home".to_string());
+        app.get_repo().unwrap().insert_project(&project).unwrap();
+        app.load_data().unwrap();
+
+        pin_new_task_project(&mut app, &project.id);
+        assert!(app.project_pinned);
+        app.start_new_task();
+        assert_eq!(app.task_form.as_ref().unwrap().task.project_id, Some(project.id.clone()));
+
+        app.switch_to_view(SidebarItem::Today);
+        assert!(!app.project_pinned);
+        app.start_new_task();
+        assert_eq!(app.task_form.as_ref().unwrap().task.project_id, None);
+
+        pin_new_task_project(&mut app, &project.id);
+        app.switch_to_project(&project.id);
+        assert!(!app.project_pinned);
+        assert_eq!(app.last_new_project, None);
+    }
+
+    #[test]
+    fn days_ago_rejects_out_of_range_retention() {
+        assert!(days_ago(30).is_some_and(|t| t < Utc::now()));
+        assert_eq!(days_ago(i64::MAX), None);
+        assert_eq!(days_ago(365 * 1_000_000), None);
+    }
+
+    #[test]
+    fn vacuum_with_huge_retention_purges_nothing() {
+        let config = Config {
+            purge_deleted_after_days: i64::MAX,
+            ..Config::default()
+        };
+        let task = Task::new("gone".to_string());
+        let mut app = App::for_test(config, std::slice::from_ref(&task));
+        app.get_repo().unwrap().delete_task(&task.id).unwrap();
+
+        app.vacuum_database();
+
+        let count: i64 = Connection::open(&app.db_path)
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM tasks", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn huge_auto_archive_window_archives_nothing() {
+        let config = Config {
+            auto_archive_days: i64::MAX,
+            ..Config::default()
+        };
+        let mut task = Task::new("done".to_string());
+        task.complete();
+        task.completed_at = Some(Utc::now() - Days::days(3650));
+        let app = App::for_test(config, &[task]);
+        assert_eq!(app.tasks[0].status, TaskStatus::Completed);
+        assert!(app.notification.is_none());
+    }
+
+    fn scheduled(title: &str, start: NaiveDate) -> Task {
+        let mut task = Task::new(title.to_string());
+        task.status = TaskStatus::Scheduled;
+        task.start_date = Some(start);
+        task
+    }
+
+    #[test]
+    fn started_tasks_become_active_at_startup() {
+        let today = Utc::now().date_naive();
+        let tasks = [scheduled("started", today), scheduled("later", today + Days::days(1))];
+        let app = App::for_test(Config::default(), &tasks);
+        let status = |id: &str| app.tasks.iter().find(|t| t.id == id).unwrap().status;
+        assert_eq!(status(&tasks[0].id), TaskStatus::Active);
+        assert_eq!(status(&tasks[1].id), TaskStatus::Scheduled);
+    }
+
+    #[test]
+    fn tasks_moved_to_start_today_are_activated_the_same_day() {
+        let today = Utc::now().date_naive();
+        let task = scheduled("later", today + Days::days(1));
+        let mut app = App::for_test(Config::default(), std::slice::from_ref(&task));
+        app.activate_started_tasks();
+        assert_eq!(app.tasks[0].status, TaskStatus::Scheduled);
+
+        let mut moved = app.tasks[0].clone();
+        moved.start_date = Some(today);
+        app.get_repo().unwrap().update_task(&moved).unwrap();
+        app.replace_task(moved);
+        app.activate_started_tasks();
+
+        assert_eq!(app.tasks[0].status, TaskStatus::Active);
+    }
+
+    #[test]
+    fn weekly_review_visits_projects_in_their_starting_order() {
+        let mut app = App::for_test(Config::default(), &[]);
+        let projects: Vec<Project> =
+            ["A", "B", "C"].iter().map(|name| Project::new(name.to_string())).collect();
+        for project in &projects {
+            app.get_repo().unwrap().insert_project(project).unwrap();
+        }
+        app.load_data().unwrap();
+        app.switch_to_view(SidebarItem::Review);
+        let order: Vec<String> = app.sidebar.projects.iter().map(|p| p.id.clone()).collect();
+
+        app.advance_weekly_review();
+        let mut visited = Vec::new();
+        for _ in 0..order.len() {
+            app.advance_weekly_review();
+            assert_eq!(app.weekly_review.as_ref().unwrap().step, ReviewStep::ReviewProjects);
+            visited.push(app.sidebar.selected_project.clone().unwrap());
+            // The sidebar re-sorts under the review
+            app.sidebar.projects.reverse();
+        }
+        assert_eq!(visited, order);
+        app.advance_weekly_review();
+        assert_eq!(app.weekly_review.as_ref().unwrap().step, ReviewStep::CheckOverdue);
+    }
+
+    #[test]
+    fn task_list_views_are_the_views_with_a_list() {
+        let mut app = App::for_test(Config::default(), &[]);
+        for view in TASK_LIST_VIEWS {
+            assert!(app.task_list(view).is_some(), "{:?}", view);
+            assert!(app.task_list_mut(view).is_some(), "{:?}", view);
+        }
+        for view in [CurrentView::GitHub, CurrentView::Toggl, CurrentView::Stats, CurrentView::Settings] {
+            assert!(app.task_list(view).is_none(), "{:?}", view);
+        }
+    }
+
+    #[test]
+    fn views_map_back_to_their_sidebar_item() {
+        for &item in SidebarItem::all() {
+            assert_eq!(CurrentView::from_sidebar_item(item).sidebar_item(), Some(item));
+        }
+        assert_eq!(CurrentView::Project.sidebar_item(), None);
+        assert_eq!(CurrentView::Tag.sidebar_item(), None);
+    }
+}
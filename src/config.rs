@@ -1,26 +1,139 @@
 use crate::error::{AppError, Result};
+use chrono::Weekday;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+/// Retries after a transient failure when the config doesn't say otherwise
+pub const DEFAULT_HTTP_RETRIES: u32 = 2;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Config {
     pub shortcut_modifier: String,
+    /// Icon set: "nerdfont" (requires a Nerd Font) or "ascii"
+    pub icon_style: String,
+    /// Show the first line of a task's notes under its title in lists
+    pub list_notes_preview: bool,
+    /// Render task notes as Markdown in the detail pane
+    pub markdown_notes: bool,
+    /// Tint task rows with their project (or first tag) color: "off", "bar" or "title"
+    pub row_accent: String,
+    /// Long task titles in lists: "truncate" (with an ellipsis) or "wrap" (onto a second line)
+    pub title_overflow: String,
+    /// Widest a task title may get in lists, in characters (0 = as wide as the list allows)
+    pub max_title_width: usize,
+    /// Draw without colors, relying on bold/reverse/underline (also enabled by NO_COLOR)
+    pub monochrome: bool,
+    /// "auto" (from COLORTERM and TERM), "truecolor", "256" or "16"
+    pub color_mode: String,
+    /// Sidebar project order: "manual" (order_index), "name" or "count" (open tasks)
+    pub project_sort: String,
+    /// Sidebar sections from top to bottom: "main", "projects", "footer"
+    pub sidebar_sections: Vec<String>,
+    /// Views listed in the main and footer sections of the sidebar, by name
+    /// ("inbox", "github", ...); views in neither are reachable by key only
+    pub sidebar_main: Vec<String>,
+    pub sidebar_footer: Vec<String>,
+    /// List overdue tasks in Today as well as in Review
+    pub today_includes_overdue: bool,
+    /// Lead This Week with overdue tasks
+    pub week_includes_overdue: bool,
+    /// First day of the week for week-based summaries (the Toggl chart, the Stats
+    /// heatmap): "mon" or "sun"
+    pub week_start: String,
+    /// Capture the mouse (turn off to use the terminal's own text selection)
+    pub enable_mouse: bool,
+    /// Draw on the alternate screen, restoring the terminal contents on exit
+    pub alternate_screen: bool,
+    /// Enter in the task form: "save" (from any field) or "next" (move to the next
+    /// field; Ctrl+S saves)
+    pub form_enter: String,
+    /// Require typing "yes" to confirm actions that cannot be undone (vacuum)
+    pub type_to_confirm: bool,
+    /// Ask before quitting with `q`
+    pub confirm_quit: bool,
+    /// Seconds a task completed in a list stays there, struck out, before it leaves
+    /// (0 = leave right away)
+    pub completed_linger_seconds: u64,
+    /// Remove completed tasks older than this many days at startup (0 = keep forever)
+    pub auto_archive_days: i64,
+    /// Deleted items older than this many days are purged by the vacuum action
+    pub purge_deleted_after_days: i64,
+    /// Ring the terminal bell when an error notification appears
+    pub bell_on_error: bool,
+    /// Ring the terminal bell when switching to a view that lists overdue tasks
+    pub bell_on_overdue: bool,
+    /// Extra attempts for GitHub/Toggl requests that fail with 502/503/504 or time out
+    pub http_retries: u32,
     pub github_token: Option<String>,
     pub github_repos: Vec<String>,
+    /// How synced GitHub items are organized: "project" (one project per repo) or "tag"
+    pub github_sync_mode: String,
+    /// Column focused when the GitHub view first opens: "review", "mine" or "assigned"
+    pub github_start_column: String,
     pub toggl_token: Option<String>,
     pub toggl_hidden_projects: Vec<String>,
+    /// Extra task kinds offered in the task form (a built-in name restyles that kind)
+    pub custom_kinds: Vec<CustomKind>,
+}
+
+/// A task kind defined in the config, e.g. `{ name = "spike", symbol = "[SPK]", color = "purple" }`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomKind {
+    pub name: String,
+    /// Shown before the title in lists (defaults to "[*]")
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// Same formats as project colors: "#rrggbb", a color name or an ANSI index
+    #[serde(default)]
+    pub color: Option<String>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             shortcut_modifier: "alt".to_string(),
+            icon_style: "nerdfont".to_string(),
+            list_notes_preview: false,
+            markdown_notes: true,
+            row_accent: "off".to_string(),
+            title_overflow: "truncate".to_string(),
+            max_title_width: 0,
+            monochrome: false,
+            color_mode: "auto".to_string(),
+            project_sort: "manual".to_string(),
+            sidebar_sections: ["main", "projects", "footer"].map(String::from).to_vec(),
+            sidebar_main: [
+                "inbox", "active", "scheduled", "today", "agenda", "week", "upcoming", "anytime",
+                "flagged", "deferred", "completed",
+            ]
+            .map(String::from)
+            .to_vec(),
+            sidebar_footer: ["review", "github", "toggl", "stats", "settings"]
+                .map(String::from)
+                .to_vec(),
+            today_includes_overdue: true,
+            week_includes_overdue: true,
+            week_start: "mon".to_string(),
+            enable_mouse: true,
+            alternate_screen: true,
+            form_enter: "save".to_string(),
+            type_to_confirm: false,
+            confirm_quit: false,
+            completed_linger_seconds: 0,
+            auto_archive_days: 0,
+            purge_deleted_after_days: 30,
+            bell_on_error: false,
+            bell_on_overdue: false,
+            http_retries: DEFAULT_HTTP_RETRIES,
             github_token: None,
             github_repos: Vec::new(),
+            github_sync_mode: "project".to_string(),
+            github_start_column: "review".to_string(),
             toggl_token: None,
             toggl_hidden_projects: Vec::new(),
+            custom_kinds: Vec::new(),
         }
     }
 }
@@ -91,6 +204,19 @@ impl Config {
         self.github_token.as_ref().is_some_and(|t| !t.is_empty())
     }
 
+    /// Check if synced GitHub items should be tagged by repo instead of assigned to a project
+    pub fn github_sync_uses_tags(&self) -> bool {
+        self.github_sync_mode == "tag"
+    }
+
+    /// First day of the week; anything but "sun"/"sunday" means Monday
+    pub fn first_weekday(&self) -> Weekday {
+        match self.week_start.trim().to_lowercase().as_str() {
+            "sun" | "sunday" => Weekday::Sun,
+            _ => Weekday::Mon,
+        }
+    }
+
     /// Check if Toggl is configured
     pub fn has_toggl(&self) -> bool {
         self.toggl_token.as_ref().is_some_and(|t| !t.is_empty())
@@ -1,13 +1,96 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, NaiveDate, Utc};
 use rusqlite::{params, Connection, OptionalExtension};
 
 use crate::error::Result;
 use crate::models::{Project, Tag, Task, TaskKind, TaskPriority, TaskSize, TaskStatus};
 
+/// SQL condition matching open tasks, the counterpart of `TaskStatus::is_open`
+const OPEN_STATUS_SQL: &str = "status NOT IN ('completed', 'cancelled')";
+
 pub struct Repository {
     conn: Connection,
 }
 
+/// Task columns the Stats view counts open tasks by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupColumn {
+    Status,
+    Kind,
+    Priority,
+}
+
+impl GroupColumn {
+    pub fn as_sql(self) -> &'static str {
+        match self {
+            GroupColumn::Status => "status",
+            GroupColumn::Kind => "kind",
+            GroupColumn::Priority => "priority",
+        }
+    }
+}
+
+/// Tables with an order_index column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderedTable {
+    Tasks,
+    Projects,
+}
+
+impl OrderedTable {
+    fn as_sql(self) -> &'static str {
+        match self {
+            OrderedTable::Tasks => "tasks",
+            OrderedTable::Projects => "projects",
+        }
+    }
+}
+
+/// Tasks whose project is missing or deleted
+const DANGLING_PROJECT_SQL: &str = "deleted = 0 AND project_id IS NOT NULL
+     AND project_id NOT IN (SELECT id FROM projects WHERE deleted = 0)";
+
+/// Tag links whose task no longer exists or whose tag is missing or deleted
+const ORPHANED_TASK_TAG_SQL: &str = "task_id NOT IN (SELECT id FROM tasks)
+     OR tag_id NOT IN (SELECT id FROM tags WHERE deleted = 0)";
+
+/// Tasks with a status `TaskStatus::from_str` doesn't know (read as inbox)
+const INVALID_STATUS_SQL: &str = "deleted = 0
+     AND status NOT IN ('inbox', 'active', 'scheduled', 'completed', 'cancelled')";
+
+/// Data problems found by `Repository::check_integrity`
+#[derive(Debug, Default)]
+pub struct IntegrityReport {
+    pub dangling_projects: i64,
+    pub orphaned_task_tags: i64,
+    /// Tasks (or projects) sharing their order_index with another one
+    pub duplicate_task_orders: i64,
+    pub duplicate_project_orders: i64,
+    pub invalid_statuses: i64,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.lines().is_empty()
+    }
+
+    /// One line per kind of problem found
+    pub fn lines(&self) -> Vec<String> {
+        [
+            (self.dangling_projects, "tasks referencing a missing or deleted project"),
+            (self.orphaned_task_tags, "tag links to missing tasks or tags"),
+            (self.duplicate_task_orders, "tasks with a duplicate order index"),
+            (self.duplicate_project_orders, "projects with a duplicate order index"),
+            (self.invalid_statuses, "tasks with an unknown status"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, what)| format!("{} {}", count, what))
+        .collect()
+    }
+}
+
 impl Repository {
     pub fn new(conn: Connection) -> Self {
         Self { conn }
@@ -19,7 +102,8 @@ impl Repository {
         let mut stmt = self.conn.prepare(
             "SELECT id, title, notes, created_at, updated_at, due_date, start_date,
                     completed_at, project_id, priority, status, order_index, deleted,
-                    kind, size, assignee, context_url, metadata
+                    kind, size, assignee, context_url, metadata, flagged, estimate_minutes,
+                    time_spent_minutes
              FROM tasks WHERE deleted = 0 ORDER BY order_index ASC, created_at DESC",
         )?;
 
@@ -41,7 +125,8 @@ impl Repository {
         let mut stmt = self.conn.prepare(
             "SELECT id, title, notes, created_at, updated_at, due_date, start_date,
                     completed_at, project_id, priority, status, order_index, deleted,
-                    kind, size, assignee, context_url, metadata
+                    kind, size, assignee, context_url, metadata, flagged, estimate_minutes,
+                    time_spent_minutes
              FROM tasks WHERE id = ?1 AND deleted = 0",
         )?;
 
@@ -63,8 +148,10 @@ impl Repository {
         self.conn.execute(
             "INSERT INTO tasks (id, title, notes, created_at, updated_at, due_date, start_date,
                                completed_at, project_id, priority, status, order_index, deleted,
-                               kind, size, assignee, context_url, metadata)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+                               kind, size, assignee, context_url, metadata, flagged,
+                               estimate_minutes, time_spent_minutes)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19,
+                     ?20, ?21)",
             params![
                 task.id,
                 task.title,
@@ -79,7 +166,7 @@ impl Repository {
                 task.status.as_str(),
                 task.order_index,
                 task.deleted,
-                task.kind.map(|k| k.as_str()),
+                task.kind.as_ref().map(|k| k.as_str()),
                 task.size.map(|s| s.as_str()),
                 task.assignee,
                 task.context_url,
@@ -88,6 +175,9 @@ impl Repository {
                 } else {
                     Some(serde_json::to_string(&task.metadata).unwrap_or_default())
                 },
+                task.flagged,
+                task.estimate_minutes,
+                task.time_spent_minutes,
             ],
         )?;
 
@@ -102,13 +192,58 @@ impl Repository {
         Ok(())
     }
 
+    /// Insert new tags and the tasks using them in a single transaction
+    pub fn insert_tasks(&self, tags: &[Tag], tasks: &[Task]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for tag in tags {
+            self.insert_tag(tag)?;
+        }
+        for task in tasks {
+            self.insert_task(task)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Insert imported projects, tags and tasks in one transaction, appending them
+    /// after the existing order indexes
+    pub fn insert_import(&self, projects: &mut [Project], tags: &[Tag], tasks: &mut [Task]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        let first_project_index = self.get_next_order_index(OrderedTable::Projects)?;
+        for (project, index) in projects.iter_mut().zip(first_project_index..) {
+            project.order_index = index;
+            self.insert_project(project)?;
+        }
+        for tag in tags {
+            self.insert_tag(tag)?;
+        }
+        let first_task_index = self.get_next_order_index(OrderedTable::Tasks)?;
+        for (task, index) in tasks.iter_mut().zip(first_task_index..) {
+            task.order_index = index;
+            self.insert_task(task)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Update several tasks in a single transaction
+    pub fn update_tasks(&self, tasks: &[Task]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for task in tasks {
+            self.update_task(task)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
     pub fn update_task(&self, task: &Task) -> Result<()> {
         self.conn.execute(
             "UPDATE tasks SET title = ?2, notes = ?3, updated_at = ?4, due_date = ?5,
                              start_date = ?6, completed_at = ?7, project_id = ?8,
                              priority = ?9, status = ?10, order_index = ?11, deleted = ?12,
                              kind = ?13, size = ?14, assignee = ?15, context_url = ?16,
-                             metadata = ?17
+                             metadata = ?17, flagged = ?18, estimate_minutes = ?19,
+                             time_spent_minutes = ?20
              WHERE id = ?1",
             params![
                 task.id,
@@ -123,7 +258,7 @@ impl Repository {
                 task.status.as_str(),
                 task.order_index,
                 task.deleted,
-                task.kind.map(|k| k.as_str()),
+                task.kind.as_ref().map(|k| k.as_str()),
                 task.size.map(|s| s.as_str()),
                 task.assignee,
                 task.context_url,
@@ -132,6 +267,9 @@ impl Repository {
                 } else {
                     Some(serde_json::to_string(&task.metadata).unwrap_or_default())
                 },
+                task.flagged,
+                task.estimate_minutes,
+                task.time_spent_minutes,
             ],
         )?;
 
@@ -158,6 +296,52 @@ impl Repository {
         Ok(())
     }
 
+    /// Soft delete completed tasks completed before the cutoff, returning how many were removed
+    pub fn archive_completed_before(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let count = self.conn.execute(
+            "UPDATE tasks SET deleted = 1, updated_at = ?2
+             WHERE status = 'completed' AND deleted = 0 AND completed_at < ?1",
+            params![cutoff.to_rfc3339(), Utc::now().to_rfc3339()],
+        )?;
+        Ok(count)
+    }
+
+    /// Permanently remove soft-deleted tasks, projects and tags last updated before
+    /// the cutoff, then VACUUM the database. Returns the number of rows removed.
+    pub fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let cutoff = cutoff.to_rfc3339();
+        let tx = self.conn.unchecked_transaction()?;
+        self.conn.execute(
+            "DELETE FROM task_tags
+             WHERE task_id IN (SELECT id FROM tasks WHERE deleted = 1 AND updated_at < ?1)
+                OR tag_id IN (SELECT id FROM tags WHERE deleted = 1 AND updated_at < ?1)",
+            params![cutoff],
+        )?;
+        let mut count = self.conn.execute(
+            "DELETE FROM tasks WHERE deleted = 1 AND updated_at < ?1",
+            params![cutoff],
+        )?;
+        // Remaining tasks must not point at a purged project
+        self.conn.execute(
+            "UPDATE tasks SET project_id = NULL
+             WHERE project_id IN (SELECT id FROM projects WHERE deleted = 1 AND updated_at < ?1)",
+            params![cutoff],
+        )?;
+        count += self.conn.execute(
+            "DELETE FROM projects WHERE deleted = 1 AND updated_at < ?1",
+            params![cutoff],
+        )?;
+        count += self.conn.execute(
+            "DELETE FROM tags WHERE deleted = 1 AND updated_at < ?1",
+            params![cutoff],
+        )?;
+        tx.commit()?;
+
+        // VACUUM can't run inside a transaction
+        self.conn.execute_batch("VACUUM")?;
+        Ok(count)
+    }
+
     fn get_task_tags(&self, task_id: &str) -> Result<Vec<String>> {
         let mut stmt = self.conn.prepare(
             "SELECT tag_id FROM task_tags WHERE task_id = ?1",
@@ -211,6 +395,9 @@ impl Repository {
             metadata: metadata
                 .and_then(|m| serde_json::from_str(&m).ok())
                 .unwrap_or_default(),
+            flagged: row.get(18)?,
+            estimate_minutes: row.get(19)?,
+            time_spent_minutes: row.get(20)?,
         })
     }
 
@@ -218,14 +405,14 @@ impl Repository {
 
     pub fn get_all_projects(&self) -> Result<Vec<Project>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, description, color, icon, order_index, is_inbox, created_at, updated_at, deleted
-             FROM projects WHERE deleted = 0 ORDER BY order_index ASC",
+            "SELECT id, name, description, color, icon, order_index, created_at, updated_at, deleted, area, pinned
+             FROM projects WHERE deleted = 0 ORDER BY order_index ASC, created_at DESC",
         )?;
 
         let projects = stmt
             .query_map([], |row| {
-                let created_at: String = row.get(7)?;
-                let updated_at: String = row.get(8)?;
+                let created_at: String = row.get(6)?;
+                let updated_at: String = row.get(7)?;
 
                 Ok(Project {
                     id: row.get(0)?,
@@ -233,15 +420,16 @@ impl Repository {
                     description: row.get(2)?,
                     color: row.get(3)?,
                     icon: row.get(4)?,
+                    area: row.get(9)?,
+                    pinned: row.get(10)?,
                     order_index: row.get(5)?,
-                    is_inbox: row.get(6)?,
                     created_at: DateTime::parse_from_rfc3339(&created_at)
                         .map(|d| d.with_timezone(&Utc))
                         .unwrap_or_else(|_| Utc::now()),
                     updated_at: DateTime::parse_from_rfc3339(&updated_at)
                         .map(|d| d.with_timezone(&Utc))
                         .unwrap_or_else(|_| Utc::now()),
-                    deleted: row.get(9)?,
+                    deleted: row.get(8)?,
                 })
             })?
             .filter_map(|r| r.ok())
@@ -252,14 +440,14 @@ impl Repository {
 
     pub fn get_project(&self, id: &str) -> Result<Option<Project>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, description, color, icon, order_index, is_inbox, created_at, updated_at, deleted
+            "SELECT id, name, description, color, icon, order_index, created_at, updated_at, deleted, area, pinned
              FROM projects WHERE id = ?1 AND deleted = 0",
         )?;
 
         let project = stmt
             .query_row([id], |row| {
-                let created_at: String = row.get(7)?;
-                let updated_at: String = row.get(8)?;
+                let created_at: String = row.get(6)?;
+                let updated_at: String = row.get(7)?;
 
                 Ok(Project {
                     id: row.get(0)?,
@@ -267,15 +455,16 @@ impl Repository {
                     description: row.get(2)?,
                     color: row.get(3)?,
                     icon: row.get(4)?,
+                    area: row.get(9)?,
+                    pinned: row.get(10)?,
                     order_index: row.get(5)?,
-                    is_inbox: row.get(6)?,
                     created_at: DateTime::parse_from_rfc3339(&created_at)
                         .map(|d| d.with_timezone(&Utc))
                         .unwrap_or_else(|_| Utc::now()),
                     updated_at: DateTime::parse_from_rfc3339(&updated_at)
                         .map(|d| d.with_timezone(&Utc))
                         .unwrap_or_else(|_| Utc::now()),
-                    deleted: row.get(9)?,
+                    deleted: row.get(8)?,
                 })
             })
             .optional()?;
@@ -285,8 +474,8 @@ impl Repository {
 
     pub fn insert_project(&self, project: &Project) -> Result<()> {
         self.conn.execute(
-            "INSERT INTO projects (id, name, description, color, icon, order_index, is_inbox, created_at, updated_at, deleted)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            "INSERT INTO projects (id, name, description, color, icon, order_index, created_at, updated_at, deleted, area, pinned)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
             params![
                 project.id,
                 project.name,
@@ -294,10 +483,11 @@ impl Repository {
                 project.color,
                 project.icon,
                 project.order_index,
-                project.is_inbox,
                 project.created_at.to_rfc3339(),
                 project.updated_at.to_rfc3339(),
                 project.deleted,
+                project.area,
+                project.pinned,
             ],
         )?;
         Ok(())
@@ -306,7 +496,7 @@ impl Repository {
     pub fn update_project(&self, project: &Project) -> Result<()> {
         self.conn.execute(
             "UPDATE projects SET name = ?2, description = ?3, color = ?4, icon = ?5,
-                                order_index = ?6, is_inbox = ?7, updated_at = ?8, deleted = ?9
+                                order_index = ?6, updated_at = ?7, deleted = ?8, area = ?9, pinned = ?10
              WHERE id = ?1",
             params![
                 project.id,
@@ -315,9 +505,10 @@ impl Repository {
                 project.color,
                 project.icon,
                 project.order_index,
-                project.is_inbox,
                 project.updated_at.to_rfc3339(),
                 project.deleted,
+                project.area,
+                project.pinned,
             ],
         )?;
         Ok(())
@@ -331,6 +522,30 @@ impl Repository {
         Ok(())
     }
 
+    /// Soft delete a project together with its tasks, or move its tasks to the Inbox
+    /// (open tasks get status=inbox, all lose the project). Runs in one transaction.
+    pub fn delete_project_and_tasks(&self, id: &str, delete_tasks: bool) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let tx = self.conn.unchecked_transaction()?;
+        if delete_tasks {
+            self.conn.execute(
+                "UPDATE tasks SET deleted = 1, updated_at = ?2 WHERE project_id = ?1 AND deleted = 0",
+                params![id, now],
+            )?;
+        } else {
+            self.conn.execute(
+                "UPDATE tasks SET project_id = NULL, updated_at = ?2,
+                                  status = CASE WHEN status IN ('completed', 'cancelled')
+                                                THEN status ELSE 'inbox' END
+                 WHERE project_id = ?1",
+                params![id, now],
+            )?;
+        }
+        self.delete_project(id)?;
+        tx.commit()?;
+        Ok(())
+    }
+
     // ==================== Tags ====================
 
     pub fn get_all_tags(&self) -> Result<Vec<Tag>> {
@@ -408,6 +623,18 @@ impl Repository {
         Ok(())
     }
 
+    /// Id of the tag named `name`, ignoring case and a leading '#' (see `Tag::key`),
+    /// inserting a new tag under the cleaned name when there is none
+    pub fn find_or_insert_tag(&self, name: &str) -> Result<String> {
+        let key = Tag::key(name);
+        if let Some(tag) = self.get_all_tags()?.into_iter().find(|t| Tag::key(&t.name) == key) {
+            return Ok(tag.id);
+        }
+        let tag = Tag::new(Tag::clean_name(name).to_string());
+        self.insert_tag(&tag)?;
+        Ok(tag.id)
+    }
+
     pub fn update_tag(&self, tag: &Tag) -> Result<()> {
         self.conn.execute(
             "UPDATE tags SET name = ?2, color = ?3, updated_at = ?4, deleted = ?5 WHERE id = ?1",
@@ -441,10 +668,46 @@ impl Repository {
         Ok(count)
     }
 
+    /// Open task counts per value of `column`; tasks without a value count under
+    /// "none"
+    pub fn count_tasks_grouped_by(&self, column: GroupColumn) -> Result<HashMap<String, i64>> {
+        let column = column.as_sql();
+        let query = format!(
+            "SELECT COALESCE({column}, 'none'), COUNT(*) FROM tasks
+             WHERE deleted = 0 AND {OPEN_STATUS_SQL} GROUP BY 1"
+        );
+        let mut stmt = self.conn.prepare(&query)?;
+        let counts = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<HashMap<String, i64>>>()?;
+        Ok(counts)
+    }
+
+    /// Completed tasks per day of `completed_at`, from `since` on
+    pub fn count_completed_by_day(&self, since: NaiveDate) -> Result<HashMap<NaiveDate, i64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT date(completed_at), COUNT(*) FROM tasks
+             WHERE deleted = 0 AND status = 'completed' AND date(completed_at) >= ?1
+             GROUP BY 1",
+        )?;
+        let counts = stmt
+            .query_map([since.to_string()], |row| {
+                let day: String = row.get(0)?;
+                Ok((day, row.get::<_, i64>(1)?))
+            })?
+            .filter_map(|r| r.ok())
+            .filter_map(|(day, count)| Some((NaiveDate::parse_from_str(&day, "%Y-%m-%d").ok()?, count)))
+            .collect();
+        Ok(counts)
+    }
+
     pub fn count_tasks_due_today(&self) -> Result<i64> {
         let today = Utc::now().date_naive().to_string();
         let count: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM tasks WHERE due_date = ?1 AND status != 'completed' AND deleted = 0",
+            &format!(
+                "SELECT COUNT(*) FROM tasks WHERE due_date = ?1 AND {} AND deleted = 0",
+                OPEN_STATUS_SQL
+            ),
             params![today],
             |row| row.get(0),
         )?;
@@ -454,7 +717,10 @@ impl Repository {
     pub fn count_overdue_tasks(&self) -> Result<i64> {
         let today = Utc::now().date_naive().to_string();
         let count: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM tasks WHERE due_date < ?1 AND status != 'completed' AND deleted = 0",
+            &format!(
+                "SELECT COUNT(*) FROM tasks WHERE due_date < ?1 AND {} AND deleted = 0",
+                OPEN_STATUS_SQL
+            ),
             params![today],
             |row| row.get(0),
         )?;
@@ -463,19 +729,296 @@ impl Repository {
 
     pub fn count_tasks_for_project(&self, project_id: &str) -> Result<i64> {
         let count: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM tasks WHERE project_id = ?1 AND status != 'completed' AND deleted = 0",
+            &format!(
+                "SELECT COUNT(*) FROM tasks WHERE project_id = ?1 AND {} AND deleted = 0",
+                OPEN_STATUS_SQL
+            ),
             params![project_id],
             |row| row.get(0),
         )?;
         Ok(count)
     }
 
-    pub fn get_next_order_index(&self, table: &str) -> Result<i64> {
+    pub fn get_next_order_index(&self, table: OrderedTable) -> Result<i64> {
         let query = format!(
             "SELECT COALESCE(MAX(order_index), 0) + 1 FROM {} WHERE deleted = 0",
-            table
+            table.as_sql()
         );
         let index: i64 = self.conn.query_row(&query, [], |row| row.get(0))?;
         Ok(index)
     }
+
+    /// Merge tags whose names normalize to the same key (see `Tag::key`): the
+    /// oldest tag of each group is kept under its cleaned name, the others' task
+    /// links move to it and they are soft deleted. Returns how many tags were
+    /// merged away.
+    pub fn merge_duplicate_tags(&self) -> Result<usize> {
+        let mut tags = self.get_all_tags()?;
+        tags.sort_by_key(|t| t.created_at);
+        let mut keep: HashMap<String, String> = HashMap::new();
+        let now = Utc::now().to_rfc3339();
+        let mut merged = 0;
+
+        let tx = self.conn.unchecked_transaction()?;
+        for tag in &tags {
+            let Some(keep_id) = keep.get(&Tag::key(&tag.name)) else {
+                keep.insert(Tag::key(&tag.name), tag.id.clone());
+                let clean = Tag::clean_name(&tag.name);
+                if clean != tag.name {
+                    tx.execute(
+                        "UPDATE tags SET name = ?2, updated_at = ?3 WHERE id = ?1",
+                        params![tag.id, clean, now],
+                    )?;
+                }
+                continue;
+            };
+            tx.execute(
+                "INSERT OR IGNORE INTO task_tags (task_id, tag_id)
+                 SELECT task_id, ?2 FROM task_tags WHERE tag_id = ?1",
+                params![tag.id, keep_id],
+            )?;
+            tx.execute("DELETE FROM task_tags WHERE tag_id = ?1", params![tag.id])?;
+            tx.execute(
+                "UPDATE tags SET deleted = 1, updated_at = ?2 WHERE id = ?1",
+                params![tag.id, now],
+            )?;
+            merged += 1;
+        }
+        tx.commit()?;
+
+        Ok(merged)
+    }
+
+    // ==================== Integrity ====================
+
+    /// Look for data problems without changing anything
+    pub fn check_integrity(&self) -> Result<IntegrityReport> {
+        let count = |sql: &str| -> Result<i64> {
+            Ok(self.conn.query_row(sql, [], |row| row.get(0))?)
+        };
+        let duplicate_orders = |table: &str| {
+            count(&format!(
+                "SELECT COUNT(*) FROM {table} WHERE deleted = 0 AND order_index IN
+                 (SELECT order_index FROM {table} WHERE deleted = 0
+                  GROUP BY order_index HAVING COUNT(*) > 1)"
+            ))
+        };
+
+        Ok(IntegrityReport {
+            dangling_projects: count(&format!(
+                "SELECT COUNT(*) FROM tasks WHERE {}",
+                DANGLING_PROJECT_SQL
+            ))?,
+            orphaned_task_tags: count(&format!(
+                "SELECT COUNT(*) FROM task_tags WHERE {}",
+                ORPHANED_TASK_TAG_SQL
+            ))?,
+            duplicate_task_orders: duplicate_orders("tasks")?,
+            duplicate_project_orders: duplicate_orders("projects")?,
+            invalid_statuses: count(&format!(
+                "SELECT COUNT(*) FROM tasks WHERE {}",
+                INVALID_STATUS_SQL
+            ))?,
+        })
+    }
+
+    /// Repair what `check_integrity` reports: dangling projects are cleared, orphaned
+    /// tag links removed, order indexes renumbered and unknown statuses set to inbox
+    pub fn repair_integrity(&self, report: &IntegrityReport) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            &format!(
+                "UPDATE tasks SET project_id = NULL, updated_at = ?1 WHERE {}",
+                DANGLING_PROJECT_SQL
+            ),
+            params![now],
+        )?;
+        tx.execute(&format!("DELETE FROM task_tags WHERE {}", ORPHANED_TASK_TAG_SQL), [])?;
+        tx.execute(
+            &format!(
+                "UPDATE tasks SET status = 'inbox', updated_at = ?1 WHERE {}",
+                INVALID_STATUS_SQL
+            ),
+            params![now],
+        )?;
+        tx.commit()?;
+
+        // Renumbering runs its own transaction
+        if report.duplicate_task_orders > 0 {
+            self.normalize_order_indexes(OrderedTable::Tasks)?;
+        }
+        if report.duplicate_project_orders > 0 {
+            self.normalize_order_indexes(OrderedTable::Projects)?;
+        }
+        Ok(())
+    }
+
+    /// Rewrite order_index to a dense 1..N sequence, keeping the current display order
+    pub fn normalize_order_indexes(&self, table: OrderedTable) -> Result<()> {
+        let table = table.as_sql();
+        let query = format!(
+            "SELECT id FROM {} WHERE deleted = 0 ORDER BY order_index ASC, created_at DESC",
+            table
+        );
+        let ids: Vec<String> = {
+            let mut stmt = self.conn.prepare(&query)?;
+            let ids = stmt
+                .query_map([], |row| row.get(0))?
+                .filter_map(|r| r.ok())
+                .collect();
+            ids
+        };
+
+        let update = format!("UPDATE {} SET order_index = ?2 WHERE id = ?1", table);
+        let tx = self.conn.unchecked_transaction()?;
+        for (i, id) in ids.iter().enumerate() {
+            tx.execute(&update, params![id, i as i64 + 1])?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::db::init_database;
+
+    fn repo() -> Repository {
+        let conn = Connection::open_in_memory().unwrap();
+        init_database(&conn).unwrap();
+        Repository::new(conn)
+    }
+
+    fn task(title: &str, order_index: i64, age_minutes: i64) -> Task {
+        let mut task = Task::new(title.to_string());
+        task.order_index = order_index;
+        task.created_at = Utc::now() - Duration::minutes(age_minutes);
+        task
+    }
+
+    #[test]
+    fn normalize_order_indexes_separates_colliding_indexes() {
+        let repo = repo();
+        // "newer" and "older" collide; the newer one is shown first
+        repo.insert_task(&task("last", 7, 0)).unwrap();
+        repo.insert_task(&task("older", 3, 10)).unwrap();
+        repo.insert_task(&task("newer", 3, 5)).unwrap();
+
+        repo.normalize_order_indexes(OrderedTable::Tasks).unwrap();
+
+        let order: Vec<(String, i64)> = repo
+            .get_all_tasks()
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.title, t.order_index))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("newer".to_string(), 1),
+                ("older".to_string(), 2),
+                ("last".to_string(), 3),
+            ]
+        );
+        assert_eq!(repo.get_next_order_index(OrderedTable::Tasks).unwrap(), 4);
+    }
+
+    #[test]
+    fn count_tasks_grouped_by_counts_open_tasks_per_value() {
+        let repo = repo();
+        let mut bug = task("bug", 1, 0);
+        bug.kind = Some(TaskKind::Bug);
+        repo.insert_task(&bug).unwrap();
+        repo.insert_task(&task("plain", 2, 0)).unwrap();
+        let mut done = task("done", 3, 0);
+        done.kind = Some(TaskKind::Bug);
+        done.complete();
+        repo.insert_task(&done).unwrap();
+
+        let by_kind = repo.count_tasks_grouped_by(GroupColumn::Kind).unwrap();
+        assert_eq!(by_kind.get("bug"), Some(&1));
+        assert_eq!(by_kind.get("none"), Some(&1));
+        let by_status = repo.count_tasks_grouped_by(GroupColumn::Status).unwrap();
+        assert_eq!(by_status.get("inbox"), Some(&2));
+        assert_eq!(by_status.get("completed"), None);
+    }
+
+    fn tag(name: &str, age_minutes: i64) -> Tag {
+        let mut tag = Tag::new(name.to_string());
+        tag.created_at = Utc::now() - Duration::minutes(age_minutes);
+        tag
+    }
+
+    #[test]
+    fn merge_duplicate_tags_keeps_the_oldest_and_moves_links() {
+        let repo = repo();
+        let oldest = tag(" #Work", 30);
+        let lower = tag("work", 20);
+        let hashed = tag("#WORK", 10);
+        let other = tag("Home", 5);
+        for t in [&oldest, &lower, &hashed, &other] {
+            repo.insert_tag(t).unwrap();
+        }
+        let mut both = task("both", 1, 0);
+        both.tags = vec![oldest.id.clone(), lower.id.clone()];
+        repo.insert_task(&both).unwrap();
+        let mut hashed_only = task("hashed only", 2, 0);
+        hashed_only.tags = vec![hashed.id.clone(), other.id.clone()];
+        repo.insert_task(&hashed_only).unwrap();
+
+        assert_eq!(repo.merge_duplicate_tags().unwrap(), 2);
+
+        let mut names: Vec<String> =
+            repo.get_all_tags().unwrap().into_iter().map(|t| t.name).collect();
+        names.sort();
+        assert_eq!(names, vec!["Home".to_string(), "Work".to_string()]);
+        assert_eq!(repo.get_task(&both.id).unwrap().unwrap().tags, vec![oldest.id.clone()]);
+        let mut tags = repo.get_task(&hashed_only.id).unwrap().unwrap().tags;
+        tags.sort();
+        let mut expected = vec![oldest.id.clone(), other.id.clone()];
+        expected.sort();
+        assert_eq!(tags, expected);
+        assert_eq!(repo.merge_duplicate_tags().unwrap(), 0);
+    }
+
+    #[test]
+    fn find_or_insert_tag_reuses_names_differing_in_case() {
+        let repo = repo();
+        let work = tag("Work", 0);
+        repo.insert_tag(&work).unwrap();
+
+        assert_eq!(repo.find_or_insert_tag(" #work ").unwrap(), work.id);
+
+        let id = repo.find_or_insert_tag(" #Errands ").unwrap();
+        assert_ne!(id, work.id);
+        assert_eq!(repo.get_tag(&id).unwrap().unwrap().name, "Errands");
+        assert_eq!(repo.find_or_insert_tag("errands").unwrap(), id);
+        assert_eq!(repo.get_all_tags().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn cancelled_tasks_are_not_counted_as_open() {
+        let repo = repo();
+        let project = Project::new("Home".to_string());
+        repo.insert_project(&project).unwrap();
+        let today = Utc::now().date_naive();
+        for (i, status) in [TaskStatus::Active, TaskStatus::Cancelled, TaskStatus::Completed]
+            .into_iter()
+            .enumerate()
+        {
+            let mut t = task(status.as_str(), i as i64, 0);
+            t.status = status;
+            t.project_id = Some(project.id.clone());
+            t.due_date = Some(today);
+            repo.insert_task(&t).unwrap();
+        }
+
+        assert_eq!(repo.count_tasks_for_project(&project.id).unwrap(), 1);
+        assert_eq!(repo.count_tasks_due_today().unwrap(), 1);
+    }
 }
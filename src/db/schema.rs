@@ -1,7 +1,7 @@
 use rusqlite::Connection;
 use crate::error::Result;
 
-pub const SCHEMA_VERSION: i32 = 1;
+pub const SCHEMA_VERSION: i32 = 6;
 
 pub fn init_database(conn: &Connection) -> Result<()> {
     // Create version table
@@ -23,6 +23,9 @@ pub fn init_database(conn: &Connection) -> Result<()> {
 
     if current_version < SCHEMA_VERSION {
         create_tables(conn)?;
+        if current_version > 0 {
+            migrate(conn, current_version)?;
+        }
         conn.execute(
             "INSERT OR REPLACE INTO schema_version (version) VALUES (?1)",
             [SCHEMA_VERSION],
@@ -32,6 +35,36 @@ pub fn init_database(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Upgrade an existing database from `from_version` to SCHEMA_VERSION
+fn migrate(conn: &Connection, from_version: i32) -> Result<()> {
+    if from_version < 2 {
+        conn.execute(
+            "ALTER TABLE tasks ADD COLUMN flagged INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+    if from_version < 3 {
+        conn.execute("ALTER TABLE projects ADD COLUMN area TEXT", [])?;
+    }
+    if from_version < 4 {
+        conn.execute("ALTER TABLE tasks ADD COLUMN estimate_minutes INTEGER", [])?;
+    }
+    if from_version < 5 {
+        conn.execute(
+            "ALTER TABLE projects ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+    if from_version < 6 {
+        conn.execute(
+            "ALTER TABLE tasks ADD COLUMN time_spent_minutes INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+
+    Ok(())
+}
+
 fn create_tables(conn: &Connection) -> Result<()> {
     // Projects table
     conn.execute(
@@ -41,8 +74,10 @@ fn create_tables(conn: &Connection) -> Result<()> {
             description TEXT,
             color TEXT,
             icon TEXT,
+            area TEXT,
+            pinned INTEGER NOT NULL DEFAULT 0,
             order_index INTEGER NOT NULL DEFAULT 0,
-            is_inbox INTEGER NOT NULL DEFAULT 0,
+            is_inbox INTEGER NOT NULL DEFAULT 0, -- unused: the inbox is a task status, not a project
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL,
             deleted INTEGER NOT NULL DEFAULT 0
@@ -83,7 +118,10 @@ fn create_tables(conn: &Connection) -> Result<()> {
             size TEXT,
             assignee TEXT,
             context_url TEXT,
-            metadata TEXT
+            metadata TEXT,
+            flagged INTEGER NOT NULL DEFAULT 0,
+            estimate_minutes INTEGER,
+            time_spent_minutes INTEGER NOT NULL DEFAULT 0
         )",
         [],
     )?;
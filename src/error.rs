@@ -34,3 +34,37 @@ pub enum AppError {
 }
 
 pub type Result<T> = std::result::Result<T, AppError>;
+
+/// Describe a failed HTTP response from its status and body, e.g.
+/// "422: Validation Failed — q is invalid". JSON bodies contribute their `message`
+/// (and the first nested `errors[].message`); other bodies are included as text.
+pub fn http_error_message(status: reqwest::StatusCode, body: &str) -> String {
+    const MAX_DETAIL: usize = 200;
+
+    let text = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    let detail = match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(json) => {
+            let message = json["message"].as_str().map(str::to_string);
+            let first_error = json["errors"][0]["message"].as_str().map(str::to_string);
+            match (message, first_error) {
+                (Some(m), Some(e)) => format!("{} — {}", m, e),
+                (Some(m), None) | (None, Some(m)) => m,
+                (None, None) => text,
+            }
+        }
+        Err(_) => text,
+    };
+
+    let detail = if detail.chars().count() > MAX_DETAIL {
+        let cut: String = detail.chars().take(MAX_DETAIL).collect();
+        format!("{}…", cut)
+    } else {
+        detail
+    };
+
+    if detail.is_empty() {
+        status.to_string()
+    } else {
+        format!("{}: {}", status.as_u16(), detail)
+    }
+}
@@ -6,20 +6,53 @@ use crate::ui::theme::SidebarItem;
 /// Handle a key event and return whether to continue running
 pub fn handle_key_event(app: &mut App, key: KeyEvent) -> bool {
     // Global shortcuts that work in any mode
-    // Allow quit with 'q' unless in input mode, task form, or actively editing in settings
+    // Allow quit with 'q' unless in input mode, task form, actively editing in settings, or
+    // typing a confirmation word
     let in_settings_editing = app.mode == AppMode::Settings && app.settings_view.editing;
-    if key.code == KeyCode::Char('q') && !matches!(app.mode, AppMode::Input | AppMode::TaskForm) && !in_settings_editing {
-        return false;
+    let typing_confirmation = app.confirm_modal.as_ref().is_some_and(|m| m.confirm_word.is_some());
+    if key.code == KeyCode::Char('q')
+        && !matches!(app.mode, AppMode::Input | AppMode::TaskForm)
+        && !in_settings_editing
+        && !typing_confirmation
+    {
+        app.start_quit();
+        return !app.quit_requested;
     }
 
     if key.code == KeyCode::Char('?') && app.mode == AppMode::Normal {
         app.show_help = !app.show_help;
+        app.help_scroll = 0;
         return true;
     }
 
     if app.show_help {
-        // Any key closes help
-        app.show_help = false;
+        // Navigation keys scroll the help, Esc closes it (the overlay clamps the offset)
+        match key.code {
+            KeyCode::Esc => app.show_help = false,
+            KeyCode::Char('j') | KeyCode::Down => app.help_scroll = app.help_scroll.saturating_add(1),
+            KeyCode::Char('k') | KeyCode::Up => app.help_scroll = app.help_scroll.saturating_sub(1),
+            KeyCode::PageDown => app.help_scroll = app.help_scroll.saturating_add(10),
+            KeyCode::PageUp => app.help_scroll = app.help_scroll.saturating_sub(10),
+            KeyCode::Char('g') | KeyCode::Home => app.help_scroll = 0,
+            _ => {}
+        }
+        return true;
+    }
+
+    if app.show_preview {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('V') => app.show_preview = false,
+            KeyCode::Char('j') | KeyCode::Down => {
+                app.preview_scroll = app.preview_scroll.saturating_add(1)
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                app.preview_scroll = app.preview_scroll.saturating_sub(1)
+            }
+            KeyCode::PageDown => app.preview_scroll = app.preview_scroll.saturating_add(10),
+            KeyCode::PageUp => app.preview_scroll = app.preview_scroll.saturating_sub(10),
+            KeyCode::Char('g') | KeyCode::Home => app.preview_scroll = 0,
+            _ => {}
+        }
         return true;
     }
 
@@ -30,9 +63,10 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> bool {
         AppMode::TaskForm => handle_task_form_mode(app, key),
         AppMode::Confirm => handle_confirm_mode(app, key),
         AppMode::Settings => handle_settings_mode(app, key),
+        AppMode::Triage => handle_triage_mode(app, key),
     }
 
-    true
+    !app.quit_requested
 }
 
 fn handle_normal_mode(app: &mut App, key: KeyEvent) {
@@ -50,6 +84,16 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
         return;
     }
 
+    // Ctrl+N/Ctrl+P step through projects while viewing one
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        match key.code {
+            KeyCode::Char('n') => app.switch_to_adjacent_project(true),
+            KeyCode::Char('p') => app.switch_to_adjacent_project(false),
+            _ => {}
+        }
+        return;
+    }
+
     match key.code {
         // View switching with number keys
         KeyCode::Char('1') => app.switch_to_view(SidebarItem::Inbox),
@@ -61,6 +105,13 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
         KeyCode::Char('7') => app.switch_to_view(SidebarItem::GitHub),
         KeyCode::Char('8') => app.switch_to_view(SidebarItem::Toggl),
         KeyCode::Char('9') => app.switch_to_view(SidebarItem::Settings),
+        KeyCode::Char('0') => app.switch_to_view(SidebarItem::Active),
+        KeyCode::Char('A') => app.switch_to_view(SidebarItem::Agenda),
+        KeyCode::Char('K') => app.switch_to_view(SidebarItem::Week),
+        KeyCode::Char('F') => app.switch_to_view(SidebarItem::Flagged),
+        KeyCode::Char('D') => app.switch_to_view(SidebarItem::Deferred),
+        KeyCode::Char('#') => app.switch_to_view(SidebarItem::Stats),
+        KeyCode::Char('@') => app.switch_to_view(SidebarItem::Scheduled),
 
         // Navigation
         KeyCode::Tab => app.cycle_focus(),
@@ -70,14 +121,16 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
         KeyCode::Char('k') | KeyCode::Up => app.select_previous(),
         KeyCode::Char('g') => app.select_first(),
         KeyCode::Char('G') => app.select_last(),
-        KeyCode::Char('h') | KeyCode::Left => {
+        KeyCode::Char(']') => app.select_overdue(true),
+        KeyCode::Char('[') => app.select_overdue(false),
+        KeyCode::Char('h') | KeyCode::Left if !app.focus_mode => {
             if app.focus == FocusArea::Detail {
                 app.focus = FocusArea::List;
             } else if app.focus == FocusArea::List {
                 app.focus = FocusArea::Sidebar;
             }
         }
-        KeyCode::Char('l') | KeyCode::Right => {
+        KeyCode::Char('l') | KeyCode::Right if !app.focus_mode => {
             if app.focus == FocusArea::Sidebar {
                 app.focus = FocusArea::List;
             } else if app.focus == FocusArea::List {
@@ -87,19 +140,49 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
 
         // Task actions
         KeyCode::Char(' ') => app.toggle_task_completed(),
+        KeyCode::Char('x') => app.complete_and_advance(),
         KeyCode::Char('n') => app.start_new_task(),
         KeyCode::Char('N') => app.start_new_project(),
+        KeyCode::Char('P') => app.start_paste_tasks(),
         KeyCode::Char('e') => app.start_edit_task(),
         KeyCode::Char('d') => app.start_delete(),
         KeyCode::Char('o') => app.open_task_url(),
+        KeyCode::Char('y') => app.copy_task_reference(),
+        KeyCode::Char('f') => app.toggle_task_flagged(),
+        KeyCode::Char('T') => app.move_task_to_edge(true),
+        KeyCode::Char('B') => app.move_task_to_edge(false),
+        KeyCode::Char('p') => app.toggle_notes_preview(),
+        KeyCode::Char('m') => app.start_edit_metadata(),
+        KeyCode::Char('z') => app.toggle_focus_mode(),
+        KeyCode::Char('Z') => app.toggle_detail_expanded(),
+        KeyCode::Char('H') => app.toggle_detail_metadata(),
+        KeyCode::Char('X') => app.toggle_task_marked(),
+        KeyCode::Char('C') => app.toggle_task_expanded(),
+        KeyCode::Char('J') => app.start_log_time(),
+        KeyCode::Char('O') => app.start_append_note(),
+        KeyCode::Char('L') => app.start_tag_tasks(),
+        KeyCode::Esc => app.clear_marked(),
+        KeyCode::Char('V') => app.open_task_preview(),
+        KeyCode::Char('I') => app.start_triage(),
+        KeyCode::Char('v') => app.toggle_completed_grouping(),
+        KeyCode::Char('R') => app.start_completed_range(),
+        KeyCode::Char('S') => app.cycle_project_sort(),
+        KeyCode::Char('M') => app.start_set_project_area(),
+        KeyCode::Char('t') => app.toggle_project_pinned(),
+        KeyCode::Char('w') => app.advance_weekly_review(),
+        KeyCode::Char('W') => app.stop_weekly_review(),
 
         // Status shortcuts
         KeyCode::Char('i') => app.set_task_status(TaskStatus::Inbox),
+        KeyCode::Char('b') => app.bump_to_inbox(),
+        KeyCode::Char('u') => app.clear_task_dates(),
         KeyCode::Char('a') => app.set_task_status(TaskStatus::Active),
         KeyCode::Char('s') => app.set_task_status(TaskStatus::Scheduled),
+        KeyCode::Char('c') => app.cycle_task_status(),
 
         // Search
-        KeyCode::Char('/') => app.start_search(),
+        KeyCode::Char('/') => app.start_list_filter(),
+        KeyCode::Char('!') => app.cycle_priority_filter(),
 
         // Refresh
         KeyCode::Char('r') => app.refresh_data(),
@@ -111,6 +194,39 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
     }
 }
 
+fn handle_triage_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.exit_triage(),
+
+        // Move the task out of the inbox; the next one comes up
+        KeyCode::Char('a') => app.set_task_status(TaskStatus::Active),
+        KeyCode::Char('s') => app.set_task_status(TaskStatus::Scheduled),
+        KeyCode::Char('d') => app.set_task_status(TaskStatus::Completed),
+        KeyCode::Char('x') => app.set_task_status(TaskStatus::Cancelled),
+
+        // Edit the current task
+        KeyCode::Char('p') => app.cycle_task_project(),
+        KeyCode::Char('t') => app.start_triage_input("Tag:"),
+        KeyCode::Char('u') => app.start_triage_input("Due date:"),
+        KeyCode::Char('f') => app.toggle_task_flagged(),
+        KeyCode::Char('1') => app.set_task_priority(crate::models::TaskPriority::None),
+        KeyCode::Char('2') => app.set_task_priority(crate::models::TaskPriority::Low),
+        KeyCode::Char('3') => app.set_task_priority(crate::models::TaskPriority::Medium),
+        KeyCode::Char('4') => app.set_task_priority(crate::models::TaskPriority::High),
+
+        // Skip ahead or go back without changing anything
+        KeyCode::Char('j') | KeyCode::Char('n') | KeyCode::Down => {
+            app.inbox_view.task_list.select_next()
+        }
+        KeyCode::Char('k') | KeyCode::Up => app.inbox_view.task_list.select_previous(),
+        _ => {}
+    }
+    // A prompt ('t', 'u') returns to triage once it's submitted or cancelled
+    if app.mode != AppMode::Input {
+        app.continue_triage();
+    }
+}
+
 fn handle_input_mode(app: &mut App, key: KeyEvent) {
     match key.code {
         KeyCode::Esc => app.cancel_input(),
@@ -124,6 +240,11 @@ fn handle_input_mode(app: &mut App, key: KeyEvent) {
         KeyCode::Char(c) => app.input.insert(c),
         _ => {}
     }
+
+    // The list filter narrows as you type
+    if app.mode == AppMode::Input && app.input.prompt == "Filter:" {
+        app.update_list_filter();
+    }
 }
 
 fn handle_task_form_mode(app: &mut App, key: KeyEvent) {
@@ -132,23 +253,42 @@ fn handle_task_form_mode(app: &mut App, key: KeyEvent) {
         return;
     };
 
+    if key.code == KeyCode::Char('p') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        app.toggle_project_pin();
+        return;
+    }
+    if key.code == KeyCode::Char('s') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        form.apply_inputs();
+        app.save_task_form();
+        return;
+    }
+
+    // The project picker takes all keys while open
+    if form.project_picker.is_some() {
+        match key.code {
+            KeyCode::Esc => form.project_picker = None,
+            KeyCode::Enter => form.picker_confirm(),
+            KeyCode::Down | KeyCode::Tab => form.picker_move(true),
+            KeyCode::Up | KeyCode::BackTab => form.picker_move(false),
+            KeyCode::Char(c) => form.picker_input(Some(c)),
+            KeyCode::Backspace => form.picker_input(None),
+            _ => {}
+        }
+        return;
+    }
+
     match key.code {
         KeyCode::Esc => {
             app.task_form = None;
             app.mode = AppMode::Normal;
         }
         KeyCode::Enter => {
-            // If on a text field, could be submitting. Otherwise save the form.
-            use crate::ui::components::TaskFormField;
-            match form.current_field {
-                TaskFormField::Title | TaskFormField::Notes | TaskFormField::DueDate => {
-                    // Check if shift is held for submit
-                    if key.modifiers.contains(KeyModifiers::SHIFT) {
-                        form.apply_inputs();
-                        app.save_task_form();
-                    }
-                }
-                _ => {
+            // Same on every field except Project, which opens the picker
+            use crate::ui::components::{FormEnter, TaskFormField};
+            match (form.current_field, form.enter) {
+                (TaskFormField::Project, _) => form.open_project_picker(),
+                (_, FormEnter::NextField) => form.next_field(),
+                (_, FormEnter::Save) => {
                     form.apply_inputs();
                     app.save_task_form();
                 }
@@ -157,15 +297,16 @@ fn handle_task_form_mode(app: &mut App, key: KeyEvent) {
         KeyCode::Tab => form.next_field(),
         KeyCode::BackTab => form.prev_field(),
 
-        // Handle cycling for select fields
+        // Handle cycling for select fields: Right steps forward, Left back
         KeyCode::Left | KeyCode::Right => {
             use crate::ui::components::TaskFormField;
+            let forward = key.code == KeyCode::Right;
             match form.current_field {
-                TaskFormField::Project => form.cycle_project(),
-                TaskFormField::Priority => form.cycle_priority(),
-                TaskFormField::Status => form.cycle_status(),
-                TaskFormField::Kind => form.cycle_kind(),
-                TaskFormField::Size => form.cycle_size(),
+                TaskFormField::Project => form.cycle_project(forward),
+                TaskFormField::Priority => form.cycle_priority(forward),
+                TaskFormField::Status => form.cycle_status(forward),
+                TaskFormField::Kind => form.cycle_kind(forward),
+                TaskFormField::Size => form.cycle_size(forward),
                 _ => {}
             }
         }
@@ -177,6 +318,7 @@ fn handle_task_form_mode(app: &mut App, key: KeyEvent) {
                 TaskFormField::Title => form.title_input.push(c),
                 TaskFormField::Notes => form.notes_input.push(c),
                 TaskFormField::DueDate => form.due_date_input.push(c),
+                TaskFormField::Estimate => form.estimate_input.push(c),
                 _ => {}
             }
         }
@@ -186,6 +328,7 @@ fn handle_task_form_mode(app: &mut App, key: KeyEvent) {
                 TaskFormField::Title => { form.title_input.pop(); }
                 TaskFormField::Notes => { form.notes_input.pop(); }
                 TaskFormField::DueDate => { form.due_date_input.pop(); }
+                TaskFormField::Estimate => { form.estimate_input.pop(); }
                 _ => {}
             }
         }
@@ -200,17 +343,27 @@ fn handle_confirm_mode(app: &mut App, key: KeyEvent) {
         return;
     };
 
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('n') => {
-            app.confirm_modal = None;
-            app.mode = AppMode::Normal;
+    // Typed confirmation: keys go to the text, Enter only confirms once it matches
+    if modal.confirm_word.is_some() {
+        match key.code {
+            KeyCode::Esc => app.cancel_confirm(),
+            KeyCode::Enter if modal.word_typed() => app.execute_confirm(),
+            KeyCode::Char(c) => modal.typed.push(c),
+            KeyCode::Backspace => {
+                modal.typed.pop();
+            }
+            _ => {}
         }
+        return;
+    }
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('n') => app.cancel_confirm(),
         KeyCode::Enter | KeyCode::Char('y') => {
-            if modal.selected {
+            if modal.selected || modal.alternative_selected {
                 app.execute_confirm();
             } else {
-                app.confirm_modal = None;
-                app.mode = AppMode::Normal;
+                app.cancel_confirm();
             }
         }
         KeyCode::Tab | KeyCode::Left | KeyCode::Right | KeyCode::Char('h') | KeyCode::Char('l') => {
@@ -233,6 +386,7 @@ fn handle_settings_mode(app: &mut App, key: KeyEvent) {
                     app.show_error(format!("Failed to save config: {}", e));
                 } else {
                     app.config = app.settings_view.config.clone();
+                    app.reset_fetch_cooldowns();
                     app.settings_view.saved_message = Some("Saved!".to_string());
                 }
             }
@@ -256,6 +410,13 @@ fn handle_settings_mode(app: &mut App, key: KeyEvent) {
             KeyCode::Char('6') => app.switch_to_view(SidebarItem::Review),
             KeyCode::Char('7') => app.switch_to_view(SidebarItem::GitHub),
             KeyCode::Char('8') => app.switch_to_view(SidebarItem::Toggl),
+            KeyCode::Char('0') => app.switch_to_view(SidebarItem::Active),
+            KeyCode::Char('A') => app.switch_to_view(SidebarItem::Agenda),
+            KeyCode::Char('K') => app.switch_to_view(SidebarItem::Week),
+            KeyCode::Char('F') => app.switch_to_view(SidebarItem::Flagged),
+            KeyCode::Char('D') => app.switch_to_view(SidebarItem::Deferred),
+            KeyCode::Char('#') => app.switch_to_view(SidebarItem::Stats),
+            KeyCode::Char('@') => app.switch_to_view(SidebarItem::Scheduled),
             // 9 is current view (Settings), no need to switch
 
             KeyCode::Char('j') | KeyCode::Down => app.settings_view.next_field(),
@@ -266,9 +427,14 @@ fn handle_settings_mode(app: &mut App, key: KeyEvent) {
                     app.show_error(format!("Failed to save config: {}", e));
                 } else {
                     app.config = app.settings_view.config.clone();
+                    app.reset_fetch_cooldowns();
                     app.settings_view.saved_message = Some("Config saved!".to_string());
                 }
             }
+            KeyCode::Char('o') => app.normalize_order(),
+            KeyCode::Char('v') => app.start_vacuum(),
+            KeyCode::Char('m') => app.merge_duplicate_tags(),
+            KeyCode::Char('E') => app.edit_config_requested = true,
             // Navigation - allow leaving settings
             KeyCode::Tab => {
                 app.cycle_focus();
@@ -286,3 +452,86 @@ fn handle_settings_mode(app: &mut App, key: KeyEvent) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyEvent;
+
+    use super::*;
+    use crate::config::Config;
+    use crate::models::Task;
+
+    fn press(app: &mut App, code: KeyCode) {
+        handle_key_event(app, KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn inbox(titles: &[&str]) -> Vec<Task> {
+        titles
+            .iter()
+            .enumerate()
+            .map(|(i, title)| {
+                let mut task = Task::new(title.to_string());
+                task.order_index = i as i64;
+                task
+            })
+            .collect()
+    }
+
+    #[test]
+    fn triage_prompts_open_and_return_to_triage() {
+        let mut app = App::for_test(Config::default(), &inbox(&["first", "second"]));
+        app.start_triage();
+        assert_eq!(app.mode, AppMode::Triage);
+
+        for key in ['t', 'u'] {
+            press(&mut app, KeyCode::Char(key));
+            assert_eq!(app.mode, AppMode::Input, "after '{}'", key);
+            press(&mut app, KeyCode::Esc);
+            assert_eq!(app.mode, AppMode::Triage);
+        }
+    }
+
+    #[test]
+    fn triage_tag_prompt_tags_the_current_task() {
+        let mut app = App::for_test(Config::default(), &inbox(&["first", "second"]));
+        let marked = app.inbox_view.task_list.tasks[1].id.clone();
+        app.inbox_view.task_list.marked.insert(marked.clone());
+        app.start_triage();
+        let current = app.inbox_view.task_list.selected_task().unwrap().id.clone();
+        assert_ne!(current, marked);
+
+        press(&mut app, KeyCode::Char('t'));
+        for c in "work".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.mode, AppMode::Triage);
+        let task = app.tasks.iter().find(|t| t.id == current).unwrap();
+        assert_eq!(task.tags.len(), 1);
+        let other = app.tasks.iter().find(|t| t.id == marked).unwrap();
+        assert!(other.tags.is_empty());
+    }
+
+    #[test]
+    fn move_to_edge_reorders_manual_lists_only() {
+        let mut tasks = inbox(&["first", "second"]);
+        for task in &mut tasks {
+            task.due_date = Some(chrono::Utc::now().date_naive());
+        }
+        let mut app = App::for_test(Config::default(), &tasks);
+        app.switch_to_view(SidebarItem::Inbox);
+        app.select_first();
+        press(&mut app, KeyCode::Char('B'));
+        let titles: Vec<&str> =
+            app.inbox_view.task_list.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["second", "first"]);
+
+        app.switch_to_view(SidebarItem::Today);
+        let before: Vec<i64> = app.tasks.iter().map(|t| t.order_index).collect();
+        press(&mut app, KeyCode::Char('T'));
+        assert_eq!(app.tasks.iter().map(|t| t.order_index).collect::<Vec<_>>(), before);
+        let notification = app.notification.as_ref().unwrap();
+        assert!(notification.message.contains("manual order"), "{}", notification.message);
+    }
+}
@@ -5,30 +5,42 @@ mod error;
 mod events;
 mod models;
 mod services;
+mod session;
 mod ui;
 
-use std::io;
+use std::io::{self, Write};
 use std::time::Duration;
 
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event,
+    },
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    style::Print,
+    terminal::{
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
+    },
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Layout},
+    layout::{Constraint, Layout, Rect},
+    widgets::Clear,
     Terminal,
 };
 
 use app::{App, AppMode, CurrentView, FocusArea};
 use config::Config;
+use db::{init_database, Repository};
 use events::handle_key_event;
+use services::{export_tasks, parse_todoist, search_tasks, ExportFormat, ImportSource};
 use ui::components::{
-    render_confirm_modal, render_help_overlay, render_input_modal, render_notification,
-    render_sidebar, render_task_form, render_status_bar, StatusBarContext,
+    render_confirm_modal, render_help_overlay, render_input, render_input_modal, render_notification,
+    render_review_progress, render_sidebar, render_task_detail, render_task_form, render_task_list,
+    render_status_bar, render_task_preview, render_triage, StatusBarContext, TaskPreviewContext,
+    TriageContext,
 };
-use ui::theme::Theme;
+use ui::theme::{Palette, Theme};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -38,10 +50,37 @@ async fn main() -> anyhow::Result<()> {
     // Load configuration
     let config = Config::load()?;
 
+    // `phitodo-tui export [json|csv]` prints all tasks to stdout and exits
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("export") {
+        return run_export(args.get(1).map(String::as_str).unwrap_or("json"));
+    }
+    // `phitodo-tui import --from todoist <file.json>`
+    if args.first().map(String::as_str) == Some("import") {
+        return match &args[1..] {
+            [flag, source, path] if flag == "--from" => run_import(source, path),
+            _ => Err(anyhow::anyhow!("Usage: phitodo-tui import --from todoist <file.json>")),
+        };
+    }
+    // `phitodo-tui doctor [--fix]` checks the database and optionally repairs it
+    if args.first().map(String::as_str) == Some("doctor") {
+        return run_doctor(args.get(1).map(String::as_str) == Some("--fix"));
+    }
+    // `phitodo-tui tasks [QUERY]` lists every task, whatever view would hide it
+    if args.first().map(String::as_str) == Some("tasks") {
+        return run_tasks(&args[1..].join(" "));
+    }
+    Theme::apply_config(&config);
+
     // Setup terminal
-    enable_raw_mode()?;
+    let setup = TerminalSetup {
+        alternate_screen: config.alternate_screen,
+        mouse: config.enable_mouse,
+    };
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    setup.enter(&mut stdout)?;
+    write!(stdout, "{}", PUSH_TITLE)?;
+    setup.restore_on_panic();
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
@@ -49,15 +88,13 @@ async fn main() -> anyhow::Result<()> {
     let mut app = App::new(config)?;
 
     // Main loop
-    let result = run_app(&mut terminal, &mut app).await;
+    let result = run_app(&mut terminal, &mut app, setup).await;
+    app.save_session();
 
     // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    setup.leave(terminal.backend_mut())?;
+    write!(terminal.backend_mut(), "{}", POP_TITLE)?;
+    terminal.backend_mut().flush()?;
     terminal.show_cursor()?;
 
     if let Err(e) = result {
@@ -67,13 +104,191 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+fn run_export(format: &str) -> anyhow::Result<()> {
+    let format = ExportFormat::from_str(format)
+        .ok_or_else(|| anyhow::anyhow!("Unknown export format '{}' (use json or csv)", format))?;
+
+    let conn = rusqlite::Connection::open(Config::database_path()?)?;
+    init_database(&conn)?;
+    let repo = Repository::new(conn);
+    let output = export_tasks(&repo.get_all_tasks()?, &repo.get_all_projects()?, format)?;
+
+    let mut stdout = io::stdout();
+    stdout.write_all(output.as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn run_import(source: &str, path: &str) -> anyhow::Result<()> {
+    let source = ImportSource::from_str(source)
+        .ok_or_else(|| anyhow::anyhow!("Unknown import source '{}' (use todoist)", source))?;
+    let contents = std::fs::read_to_string(path)?;
+
+    let conn = rusqlite::Connection::open(Config::database_path()?)?;
+    init_database(&conn)?;
+    let repo = Repository::new(conn);
+    let mut result = match source {
+        ImportSource::Todoist => {
+            parse_todoist(&contents, &repo.get_all_projects()?, &repo.get_all_tags()?)?
+        }
+    };
+    repo.insert_import(&mut result.projects, &result.tags, &mut result.tasks)?;
+
+    println!(
+        "Imported {} tasks, {} new projects, {} new tags ({} skipped)",
+        result.tasks.len(),
+        result.projects.len(),
+        result.tags.len(),
+        result.skipped
+    );
+    Ok(())
+}
+
+fn run_doctor(fix: bool) -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open(Config::database_path()?)?;
+    init_database(&conn)?;
+    let repo = Repository::new(conn);
+
+    let report = repo.check_integrity()?;
+    if report.is_clean() {
+        println!("No problems found");
+        return Ok(());
+    }
+    for line in report.lines() {
+        println!("Found {}", line);
+    }
+
+    if fix {
+        repo.repair_integrity(&report)?;
+        println!("Fixed");
+    } else {
+        println!("Run `phitodo-tui doctor --fix` to repair");
+    }
+    Ok(())
+}
+
+/// Print a table of all non-deleted tasks (any status, deferred or not) whose
+/// title, notes or id match `query`
+fn run_tasks(query: &str) -> anyhow::Result<()> {
+    let conn = rusqlite::Connection::open(Config::database_path()?)?;
+    init_database(&conn)?;
+    let repo = Repository::new(conn);
+    let tasks = repo.get_all_tasks()?;
+    let projects = repo.get_all_projects()?;
+
+    let mut matches = search_tasks(&tasks, query);
+    if !query.is_empty() {
+        let id_matches = tasks
+            .iter()
+            .filter(|t| t.id.starts_with(query) && !matches.iter().any(|m| m.id == t.id));
+        matches.extend(id_matches.collect::<Vec<_>>());
+    }
+    let date = |d: Option<chrono::NaiveDate>| d.map_or("-".to_string(), |d| d.to_string());
+
+    println!(
+        "{:<8}  {:<9}  {:<10}  {:<10}  {:<16}  TITLE",
+        "ID", "STATUS", "DUE", "START", "PROJECT"
+    );
+    for task in &matches {
+        let project = task
+            .project_id
+            .as_ref()
+            .map(|id| {
+                projects
+                    .iter()
+                    .find(|p| &p.id == id)
+                    .map_or("(missing)", |p| p.name.as_str())
+            })
+            .unwrap_or("-");
+        println!(
+            "{:<8}  {:<9}  {:<10}  {:<10}  {:<16}  {}",
+            task.id.chars().take(8).collect::<String>(),
+            task.status.as_str(),
+            date(task.due_date),
+            date(task.start_date),
+            project.chars().take(16).collect::<String>(),
+            task.title
+        );
+    }
+    println!("{} task(s)", matches.len());
+    Ok(())
+}
+
+/// Save the current window title on the terminal's title stack (xterm, tmux,
+/// kitty...) at startup, and restore it on exit
+const PUSH_TITLE: &str = "\x1b[22;0t";
+const POP_TITLE: &str = "\x1b[23;0t";
+
+/// Terminal features turned on at startup, so suspending and teardown undo
+/// exactly those (config changes take effect on the next launch)
+#[derive(Debug, Clone, Copy)]
+struct TerminalSetup {
+    alternate_screen: bool,
+    mouse: bool,
+}
+
+impl TerminalSetup {
+    fn enter(self, out: &mut impl Write) -> io::Result<()> {
+        enable_raw_mode()?;
+        if self.alternate_screen {
+            execute!(out, EnterAlternateScreen)?;
+        }
+        if self.mouse {
+            execute!(out, EnableMouseCapture)?;
+        }
+        execute!(out, EnableBracketedPaste)
+    }
+
+    /// Leave the terminal usable if the app panics: undo the setup and restore the
+    /// window title before the default hook prints the panic message, which would
+    /// otherwise land on the alternate screen in raw mode. Panics in spawned fetch
+    /// tasks are caught by tokio and the UI keeps running, so only main-thread
+    /// panics restore.
+    fn restore_on_panic(self) {
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if std::thread::current().name() == Some("main") {
+                let mut stdout = io::stdout();
+                let _ = self.leave(&mut stdout);
+                let _ = write!(stdout, "{}", POP_TITLE);
+                let _ = execute!(stdout, crossterm::cursor::Show);
+            }
+            default_hook(info);
+        }));
+    }
+
+    fn leave(self, out: &mut impl Write) -> io::Result<()> {
+        disable_raw_mode()?;
+        if self.alternate_screen {
+            execute!(out, LeaveAlternateScreen)?;
+        }
+        if self.mouse {
+            execute!(out, DisableMouseCapture)?;
+        }
+        execute!(out, DisableBracketedPaste)
+    }
+}
+
 async fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
+    setup: TerminalSetup,
 ) -> anyhow::Result<()> {
+    let mut shown_title = String::new();
     loop {
         // Poll async messages
         app.poll_async_messages();
+        app.activate_started_tasks();
+        app.expire_lingering();
+
+        if app.terminal_title != shown_title {
+            shown_title = app.terminal_title.clone();
+            execute!(terminal.backend_mut(), SetTitle(&shown_title))?;
+        }
+        if app.ring_bell {
+            app.ring_bell = false;
+            execute!(terminal.backend_mut(), Print('\x07'))?;
+        }
 
         // Draw UI
         terminal.draw(|frame| {
@@ -82,18 +297,55 @@ async fn run_app(
 
         // Handle events with timeout for async polling
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                // Clear notification on any key
-                app.clear_notification();
-
-                if !handle_key_event(app, key) {
-                    return Ok(());
+            match event::read()? {
+                Event::Key(key) => {
+                    // Clear notification on any key
+                    app.clear_notification();
+
+                    if !handle_key_event(app, key) {
+                        return Ok(());
+                    }
+
+                    if std::mem::take(&mut app.edit_config_requested) {
+                        edit_config_in_editor(terminal, app, setup)?;
+                    }
                 }
+                Event::Paste(text) => app.handle_paste(&text),
+                _ => {}
             }
         }
     }
 }
 
+/// Suspend the TUI, open the config file in $VISUAL/$EDITOR, then reload it
+fn edit_config_in_editor(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    setup: TerminalSetup,
+) -> anyhow::Result<()> {
+    let path = Config::config_path()?;
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // Allow editors with arguments, e.g. EDITOR="code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+
+    setup.leave(terminal.backend_mut())?;
+
+    let status = std::process::Command::new(program).args(parts).arg(&path).status();
+
+    setup.enter(terminal.backend_mut())?;
+    terminal.clear()?;
+
+    match status {
+        Ok(s) if s.success() => app.reload_config(),
+        Ok(s) => app.show_error(format!("Editor exited with {}", s)),
+        Err(e) => app.show_error(format!("Failed to launch {}: {}", program, e)),
+    }
+    Ok(())
+}
+
 fn draw_ui(frame: &mut ratatui::Frame, app: &mut App) {
     let area = frame.size();
 
@@ -103,9 +355,10 @@ fn draw_ui(frame: &mut ratatui::Frame, app: &mut App) {
         area,
     );
 
-    // Main layout: sidebar | content
+    // Main layout: sidebar | content (sidebar collapsed in focus mode)
+    let sidebar_width = if app.focus_mode { 0 } else { 25 };
     let chunks = Layout::horizontal([
-        Constraint::Length(25),
+        Constraint::Length(sidebar_width),
         Constraint::Min(40),
     ])
     .split(area);
@@ -116,30 +369,29 @@ fn draw_ui(frame: &mut ratatui::Frame, app: &mut App) {
     // Only sync sidebar selection with current view when sidebar is not focused
     // This allows j/k navigation in the sidebar to work independently
     if app.focus != FocusArea::Sidebar {
-        app.sidebar.selected_item = match app.current_view {
-            CurrentView::Inbox => ui::theme::SidebarItem::Inbox,
-            CurrentView::Today => ui::theme::SidebarItem::Today,
-            CurrentView::Upcoming => ui::theme::SidebarItem::Upcoming,
-            CurrentView::Anytime => ui::theme::SidebarItem::Anytime,
-            CurrentView::Completed => ui::theme::SidebarItem::Completed,
-            CurrentView::Review => ui::theme::SidebarItem::Review,
-            CurrentView::GitHub => ui::theme::SidebarItem::GitHub,
-            CurrentView::Toggl => ui::theme::SidebarItem::Toggl,
-            CurrentView::Settings => ui::theme::SidebarItem::Settings,
-            _ => app.sidebar.selected_item,
-        };
+        if let Some(item) = app.current_view.sidebar_item() {
+            app.sidebar.selected_item = item;
+        }
     }
 
     // Render sidebar
-    render_sidebar(frame, chunks[0], &app.sidebar);
+    if !app.focus_mode {
+        render_sidebar(frame, chunks[0], &mut app.sidebar);
+    }
 
     // Determine if we should show status bar (for task views)
-    let show_status_bar = matches!(
+    let show_status_bar = !app.focus_mode && matches!(
         app.current_view,
         CurrentView::Inbox
+            | CurrentView::Active
+            | CurrentView::Scheduled
             | CurrentView::Today
+            | CurrentView::Agenda
+            | CurrentView::Week
             | CurrentView::Upcoming
             | CurrentView::Anytime
+            | CurrentView::Flagged
+            | CurrentView::Deferred
             | CurrentView::Completed
             | CurrentView::Project
             | CurrentView::Tag
@@ -155,75 +407,113 @@ fn draw_ui(frame: &mut ratatui::Frame, app: &mut App) {
             .split(chunks[1])
     };
 
-    let content_area = content_chunks[0];
+    let mut content_area = content_chunks[0];
     let status_area = content_chunks[1];
 
+    // Weekly review step indicator above the view it drives
+    if let Some(progress) = app.weekly_review_progress() {
+        let parts = Layout::vertical([Constraint::Length(1), Constraint::Min(4)]).split(content_area);
+        render_review_progress(frame, parts[0], &progress);
+        content_area = parts[1];
+    }
+
     // Update list focus state
     let list_focused = app.focus == FocusArea::List;
     let detail_focused = app.focus == FocusArea::Detail;
+    let detail = app.detail_options();
 
     // Get selected task info for status bar
-    let selected_task_info = match app.current_view {
-        CurrentView::Inbox => app.inbox_view.selected_task(),
-        CurrentView::Today => app.today_view.selected_task(),
-        CurrentView::Upcoming => app.upcoming_view.selected_task(),
-        CurrentView::Anytime => app.anytime_view.selected_task(),
-        CurrentView::Completed => app.completed_view.selected_task(),
-        CurrentView::Project => app.project_view.selected_task(),
-        CurrentView::Tag => app.tag_view.selected_task(),
-        CurrentView::Review => app.review_view.selected_task(),
-        _ => None,
-    };
+    let selected_task_info = app.current_task_list().and_then(|list| list.selected_task());
 
     let has_selection = selected_task_info.is_some();
     let is_completed = selected_task_info.map(|t| t.is_completed()).unwrap_or(false);
 
+    if let Some(list) = app.current_task_list_mut() {
+        list.focused = list_focused;
+    }
+
+    // Focus mode: just the current task list, full width; expanded detail: just
+    // the selected task's details
+    let mut single_pane = false;
+    if app.focus_mode {
+        if let Some(list) = app.current_task_list_mut() {
+            list.focused = true;
+            render_task_list(frame, content_area, list);
+            single_pane = true;
+        }
+    } else if app.detail_expanded {
+        if let Some(list) = app.current_task_list_mut() {
+            render_task_detail(frame, content_area, list.selected_task(), true, detail);
+            single_pane = true;
+        }
+    }
+
     match app.current_view {
+        _ if single_pane => {}
         CurrentView::Inbox => {
-            app.inbox_view.task_list.focused = list_focused;
             app.inbox_view.detail_focused = detail_focused;
-            app.inbox_view.render(frame, content_area);
+            app.inbox_view.render(frame, content_area, detail);
+        }
+        CurrentView::Active => {
+            app.active_view.detail_focused = detail_focused;
+            app.active_view.render(frame, content_area, detail);
+        }
+        CurrentView::Scheduled => {
+            app.scheduled_view.detail_focused = detail_focused;
+            app.scheduled_view.render(frame, content_area, detail);
         }
         CurrentView::Today => {
-            app.today_view.task_list.focused = list_focused;
             app.today_view.detail_focused = detail_focused;
-            app.today_view.render(frame, content_area);
+            app.today_view.render(frame, content_area, detail);
+        }
+        CurrentView::Agenda => {
+            app.agenda_view.detail_focused = detail_focused;
+            app.agenda_view.render(frame, content_area, detail);
+        }
+        CurrentView::Week => {
+            app.week_view.detail_focused = detail_focused;
+            app.week_view.render(frame, content_area, detail);
         }
         CurrentView::Upcoming => {
-            app.upcoming_view.task_list.focused = list_focused;
             app.upcoming_view.detail_focused = detail_focused;
-            app.upcoming_view.render(frame, content_area);
+            app.upcoming_view.render(frame, content_area, detail);
         }
         CurrentView::Anytime => {
-            app.anytime_view.task_list.focused = list_focused;
             app.anytime_view.detail_focused = detail_focused;
-            app.anytime_view.render(frame, content_area);
+            app.anytime_view.render(frame, content_area, detail);
+        }
+        CurrentView::Flagged => {
+            app.flagged_view.detail_focused = detail_focused;
+            app.flagged_view.render(frame, content_area, detail);
+        }
+        CurrentView::Deferred => {
+            app.deferred_view.detail_focused = detail_focused;
+            app.deferred_view.render(frame, content_area, detail);
         }
         CurrentView::Completed => {
-            app.completed_view.task_list.focused = list_focused;
             app.completed_view.detail_focused = detail_focused;
-            app.completed_view.render(frame, content_area);
+            app.completed_view.render(frame, content_area, detail);
         }
         CurrentView::Project => {
-            app.project_view.task_list.focused = list_focused;
             app.project_view.detail_focused = detail_focused;
-            app.project_view.render(frame, content_area);
+            app.project_view.render(frame, content_area, detail);
         }
         CurrentView::Tag => {
-            app.tag_view.task_list.focused = list_focused;
             app.tag_view.detail_focused = detail_focused;
-            app.tag_view.render(frame, content_area);
+            app.tag_view.render(frame, content_area, detail);
         }
         CurrentView::Review => {
-            app.review_view.task_list.focused = list_focused;
             app.review_view.detail_focused = detail_focused;
-            app.review_view.render(frame, content_area);
+            app.review_view.render(frame, content_area, detail);
         }
         CurrentView::GitHub => {
-            app.github_view.render(frame, chunks[1]);
+            app.github_view.render(frame, chunks[1], app.tick);
         }
         CurrentView::Toggl => {
-            app.toggl_view.render(frame, chunks[1]);
+            app.toggl_view.render(frame, chunks[1], app.tick);
+        }
+        CurrentView::Stats => {
+            app.stats_view.render(frame, chunks[1]);
         }
         CurrentView::Settings => {
             app.settings_view.render(frame, chunks[1]);
@@ -245,6 +535,54 @@ fn draw_ui(frame: &mut ratatui::Frame, app: &mut App) {
         render_status_bar(frame, status_area, &ctx);
     }
 
+    // Inbox triage covers the whole screen; prompts still render on top
+    if app.triage {
+        if let Some(task) = app.inbox_view.task_list.selected_task() {
+            let ctx = TriageContext {
+                task,
+                position: app.inbox_view.task_list.list_state.selected().unwrap_or(0) + 1,
+                total: app.inbox_view.task_list.tasks.len(),
+                project_name: task
+                    .project_id
+                    .as_ref()
+                    .and_then(|id| app.projects.iter().find(|p| &p.id == id))
+                    .map(|p| p.name.as_str()),
+                tag_names: task
+                    .tags
+                    .iter()
+                    .filter_map(|id| app.tags.iter().find(|t| &t.id == id))
+                    .map(|t| t.name.as_str())
+                    .collect(),
+                markdown_notes: app.config.markdown_notes,
+            };
+            render_triage(frame, area, &ctx);
+        }
+    }
+
+    // Task preview also covers the whole screen
+    if app.show_preview {
+        let mut scroll = app.preview_scroll;
+        if let Some(task) = app.selected_task() {
+            let ctx = TaskPreviewContext {
+                task,
+                project_name: task
+                    .project_id
+                    .as_ref()
+                    .and_then(|id| app.projects.iter().find(|p| &p.id == id))
+                    .map(|p| p.name.as_str()),
+                tag_names: task
+                    .tags
+                    .iter()
+                    .filter_map(|id| app.tags.iter().find(|t| &t.id == id))
+                    .map(|t| t.name.as_str())
+                    .collect(),
+                markdown_notes: app.config.markdown_notes,
+            };
+            render_task_preview(frame, area, &ctx, &mut scroll);
+        }
+        app.preview_scroll = scroll;
+    }
+
     // Render overlays
     if let Some(ref form) = app.task_form {
         render_task_form(frame, area, form);
@@ -254,7 +592,12 @@ fn draw_ui(frame: &mut ratatui::Frame, app: &mut App) {
         render_confirm_modal(frame, area, modal);
     }
 
-    if app.mode == AppMode::Input {
+    if app.mode == AppMode::Input && app.input.prompt == "Filter:" {
+        // Filter prompt sits on the bottom line so the list stays visible
+        let bar = Rect::new(area.x, area.bottom().saturating_sub(1), area.width, 1);
+        frame.render_widget(Clear, bar);
+        render_input(frame, bar, &app.input);
+    } else if app.mode == AppMode::Input {
         render_input_modal(frame, area, &app.input, "Input");
     }
 
@@ -263,6 +606,12 @@ fn draw_ui(frame: &mut ratatui::Frame, app: &mut App) {
     }
 
     if app.show_help {
-        render_help_overlay(frame, area);
+        render_help_overlay(frame, area, &mut app.help_scroll);
+    }
+
+    if Theme::palette() != Palette::TrueColor {
+        for cell in frame.buffer_mut().content.iter_mut() {
+            Theme::apply_palette(cell);
+        }
     }
 }
@@ -8,8 +8,13 @@ pub struct Project {
     pub description: Option<String>,
     pub color: Option<String>,
     pub icon: Option<String>,
+    /// Area of focus the project belongs to; the sidebar groups projects by area
+    #[serde(default)]
+    pub area: Option<String>,
+    /// Pinned projects are listed first in the sidebar, outside their area
+    #[serde(default)]
+    pub pinned: bool,
     pub order_index: i64,
-    pub is_inbox: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted: bool,
@@ -24,8 +29,9 @@ impl Project {
             description: None,
             color: None,
             icon: None,
+            area: None,
+            pinned: false,
             order_index: 0,
-            is_inbox: false,
             created_at: now,
             updated_at: now,
             deleted: false,
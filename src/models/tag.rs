@@ -24,6 +24,17 @@ impl Tag {
         }
     }
 
+    /// Trimmed tag name without a leading '#', the form new tags are stored in
+    pub fn clean_name(name: &str) -> &str {
+        name.trim().trim_start_matches('#').trim()
+    }
+
+    /// Key under which tag names are considered the same: "Work", "work" and
+    /// " #work " all map to "work"
+    pub fn key(name: &str) -> String {
+        Self::clean_name(name).to_lowercase()
+    }
+
     /// Returns a display symbol for the tag
     pub fn display_symbol(&self) -> &str {
         "#"
@@ -24,6 +24,23 @@ impl TaskStatus {
         }
     }
 
+    /// Next status in the workflow: Inbox → Active → Scheduled → Completed → Cancelled → Inbox
+    pub fn next(&self) -> Self {
+        match self {
+            TaskStatus::Inbox => TaskStatus::Active,
+            TaskStatus::Active => TaskStatus::Scheduled,
+            TaskStatus::Scheduled => TaskStatus::Completed,
+            TaskStatus::Completed => TaskStatus::Cancelled,
+            TaskStatus::Cancelled => TaskStatus::Inbox,
+        }
+    }
+
+    /// Whether a task with this status still needs doing (not completed or cancelled).
+    /// Keep in sync with OPEN_STATUS_SQL in the repository.
+    pub fn is_open(&self) -> bool {
+        !matches!(self, TaskStatus::Completed | TaskStatus::Cancelled)
+    }
+
     pub fn from_str(s: &str) -> Self {
         match s {
             "inbox" => TaskStatus::Inbox,
@@ -76,23 +93,34 @@ impl TaskPriority {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+/// Serialized (and stored) as its name, e.g. "bug" or "gh:issue"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum TaskKind {
     Task,
     Bug,
     Feature,
     Chore,
-    #[serde(rename = "gh:issue")]
     GhIssue,
-    #[serde(rename = "gh:pr")]
     GhPr,
-    #[serde(rename = "gh:review")]
     GhReview,
+    /// A kind defined in the config's `custom_kinds`, by name
+    Custom(String),
 }
 
 impl TaskKind {
-    pub fn as_str(&self) -> &'static str {
+    /// Built-in kinds, in the order the task form cycles through them
+    pub const BUILT_IN: [TaskKind; 7] = [
+        TaskKind::Task,
+        TaskKind::Bug,
+        TaskKind::Feature,
+        TaskKind::Chore,
+        TaskKind::GhIssue,
+        TaskKind::GhPr,
+        TaskKind::GhReview,
+    ];
+
+    pub fn as_str(&self) -> &str {
         match self {
             TaskKind::Task => "task",
             TaskKind::Bug => "bug",
@@ -101,9 +129,11 @@ impl TaskKind {
             TaskKind::GhIssue => "gh:issue",
             TaskKind::GhPr => "gh:pr",
             TaskKind::GhReview => "gh:review",
+            TaskKind::Custom(name) => name,
         }
     }
 
+    /// Parse a kind name; names other than the built-in ones are custom kinds
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             "task" => Some(TaskKind::Task),
@@ -113,10 +143,13 @@ impl TaskKind {
             "gh:issue" => Some(TaskKind::GhIssue),
             "gh:pr" => Some(TaskKind::GhPr),
             "gh:review" => Some(TaskKind::GhReview),
-            _ => None,
+            "" => None,
+            name => Some(TaskKind::Custom(name.to_string())),
         }
     }
 
+    /// Default list symbol; custom kinds get theirs from the config
+    /// (see `Theme::kind_symbol`)
     pub fn symbol(&self) -> &'static str {
         match self {
             TaskKind::Task => "[T]",
@@ -126,10 +159,23 @@ impl TaskKind {
             TaskKind::GhIssue => "[ISS]",
             TaskKind::GhPr => "[PR]",
             TaskKind::GhReview => "[REV]",
+            TaskKind::Custom(_) => "[*]",
         }
     }
 }
 
+impl From<String> for TaskKind {
+    fn from(s: String) -> Self {
+        TaskKind::from_str(&s).unwrap_or(TaskKind::Custom(s))
+    }
+}
+
+impl From<TaskKind> for String {
+    fn from(kind: TaskKind) -> Self {
+        kind.as_str().to_string()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TaskSize {
@@ -167,6 +213,24 @@ impl TaskSize {
             TaskSize::L => "L",
         }
     }
+
+    /// Estimate assumed for tasks with a size but no explicit estimate
+    pub fn default_minutes(&self) -> i64 {
+        match self {
+            TaskSize::Xs => 15,
+            TaskSize::S => 30,
+            TaskSize::M => 60,
+            TaskSize::L => 180,
+        }
+    }
+}
+
+/// Metadata key holding the status a task had before it was completed
+pub const STATUS_BEFORE_COMPLETION_KEY: &str = "_status_before_completion";
+
+/// Keys starting with `_` are bookkeeping: hidden from the detail pane, `m` and exports
+pub fn is_internal_metadata_key(key: &str) -> bool {
+    key.starts_with('_')
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -191,6 +255,14 @@ pub struct Task {
     pub context_url: Option<String>,
     #[serde(default)]
     pub metadata: HashMap<String, String>,
+    #[serde(default)]
+    pub flagged: bool,
+    /// Expected effort in minutes
+    #[serde(default)]
+    pub estimate_minutes: Option<i64>,
+    /// Minutes logged on the task locally, independent of Toggl
+    #[serde(default)]
+    pub time_spent_minutes: i64,
 }
 
 impl Task {
@@ -216,22 +288,71 @@ impl Task {
             assignee: None,
             context_url: None,
             metadata: HashMap::new(),
+            flagged: false,
+            estimate_minutes: None,
+            time_spent_minutes: 0,
         }
     }
 
+    /// The explicit estimate, or the size's default when there is none
+    pub fn effective_estimate(&self) -> Option<i64> {
+        self.estimate_minutes.or(self.size.map(|s| s.default_minutes()))
+    }
+
     pub fn is_completed(&self) -> bool {
         self.status == TaskStatus::Completed
     }
 
+    /// Mark the task completed, remembering its current status for reopening
+    pub fn complete(&mut self) {
+        if self.status != TaskStatus::Completed {
+            self.metadata.insert(
+                STATUS_BEFORE_COMPLETION_KEY.to_string(),
+                self.status.as_str().to_string(),
+            );
+        }
+        self.status = TaskStatus::Completed;
+        self.completed_at = Some(Utc::now());
+    }
+
+    /// Reopen a completed task, restoring its pre-completion status (Inbox if unknown)
+    pub fn reopen(&mut self) {
+        self.status = self
+            .metadata
+            .remove(STATUS_BEFORE_COMPLETION_KEY)
+            .map(|s| TaskStatus::from_str(&s))
+            .filter(|s| *s != TaskStatus::Completed)
+            .unwrap_or(TaskStatus::Inbox);
+        self.completed_at = None;
+    }
+
+    /// Move the task to `status`. Completing goes through `complete`; any other
+    /// status drops the completion time and the remembered pre-completion status.
+    pub fn set_status(&mut self, status: TaskStatus) {
+        if status == TaskStatus::Completed {
+            self.complete();
+            return;
+        }
+        self.status = status;
+        self.completed_at = None;
+        self.metadata.remove(STATUS_BEFORE_COMPLETION_KEY);
+    }
+
     pub fn is_overdue(&self) -> bool {
         if let Some(due) = self.due_date {
             let today = Utc::now().date_naive();
-            due < today && !self.is_completed()
+            due < today && self.status.is_open()
         } else {
             false
         }
     }
 
+    /// Whether the task's start date is still in the future (GTD "defer")
+    pub fn is_deferred(&self) -> bool {
+        let today = Utc::now().date_naive();
+        self.start_date.is_some_and(|start| start > today)
+    }
+
     pub fn is_due_today(&self) -> bool {
         if let Some(due) = self.due_date {
             let today = Utc::now().date_naive();
@@ -247,3 +368,57 @@ impl Default for Task {
         Self::new(String::new())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reopen_restores_the_status_before_completion() {
+        let mut task = Task::new("Write report".to_string());
+        task.status = TaskStatus::Active;
+
+        task.complete();
+        assert_eq!(task.status, TaskStatus::Completed);
+        assert!(task.completed_at.is_some());
+
+        task.reopen();
+        assert_eq!(task.status, TaskStatus::Active);
+        assert!(task.completed_at.is_none());
+        assert!(task.metadata.is_empty());
+    }
+
+    #[test]
+    fn completion_bookkeeping_is_internal_metadata() {
+        let mut task = Task::new("Write report".to_string());
+        task.complete();
+        assert!(task.metadata.keys().all(|key| is_internal_metadata_key(key)));
+    }
+
+    #[test]
+    fn cycling_through_every_status_leaves_no_completion_behind() {
+        let mut task = Task::new("Write report".to_string());
+        let mut seen = vec![task.status];
+        loop {
+            task.set_status(task.status.next());
+            if task.status == TaskStatus::Cancelled {
+                assert!(task.completed_at.is_none());
+                assert!(task.metadata.is_empty());
+            }
+            if task.status == TaskStatus::Inbox {
+                break;
+            }
+            seen.push(task.status);
+        }
+        assert_eq!(
+            seen,
+            vec![
+                TaskStatus::Inbox,
+                TaskStatus::Active,
+                TaskStatus::Scheduled,
+                TaskStatus::Completed,
+                TaskStatus::Cancelled,
+            ]
+        );
+    }
+}
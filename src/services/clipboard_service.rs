@@ -0,0 +1,57 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use base64::Engine;
+
+/// Clipboard commands to try, in order, with their arguments
+const CLIPBOARD_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip.exe", &[]),
+];
+
+/// How `copy_to_clipboard` delivered the text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardCopy {
+    /// A clipboard tool took the text
+    Copied,
+    /// No clipboard tool worked, so the terminal was asked to copy it; whether it
+    /// did can't be told
+    SentToTerminal,
+}
+
+/// Copy text to the system clipboard using the first available clipboard tool.
+/// Without one, falls back to the OSC 52 escape sequence, which most terminals
+/// (including over SSH and in tmux) turn into a clipboard write.
+pub fn copy_to_clipboard(text: &str) -> std::io::Result<ClipboardCopy> {
+    for (program, args) in CLIPBOARD_COMMANDS {
+        if pipe_to(program, args, text) {
+            return Ok(ClipboardCopy::Copied);
+        }
+    }
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+    stdout.flush()?;
+    Ok(ClipboardCopy::SentToTerminal)
+}
+
+fn pipe_to(program: &str, args: &[&str], text: &str) -> bool {
+    let Ok(mut child) = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return false;
+    };
+    let written = child
+        .stdin
+        .take()
+        .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+    child.wait().is_ok_and(|status| status.success()) && written
+}
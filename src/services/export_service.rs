@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use chrono::SecondsFormat;
+
+use crate::error::Result;
+use crate::models::{is_internal_metadata_key, Project, Task};
+
+/// Output format for `phitodo-tui export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "json" => Some(ExportFormat::Json),
+            "csv" => Some(ExportFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Serialize non-deleted tasks in the given format
+pub fn export_tasks(tasks: &[Task], projects: &[Project], format: ExportFormat) -> Result<String> {
+    let tasks: Vec<&Task> = tasks.iter().filter(|t| !t.deleted).collect();
+    match format {
+        ExportFormat::Json => {
+            let tasks: Vec<Task> = tasks
+                .into_iter()
+                .cloned()
+                .map(|mut task| {
+                    task.metadata.retain(|key, _| !is_internal_metadata_key(key));
+                    task
+                })
+                .collect();
+            Ok(serde_json::to_string_pretty(&tasks)?)
+        }
+        ExportFormat::Csv => Ok(export_csv(&tasks, projects)),
+    }
+}
+
+fn export_csv(tasks: &[&Task], projects: &[Project]) -> String {
+    let project_names: HashMap<&str, &str> = projects
+        .iter()
+        .map(|p| (p.id.as_str(), p.name.as_str()))
+        .collect();
+
+    let mut out = String::from(
+        "id,title,status,priority,due_date,project,created_at,updated_at,completed_at\n",
+    );
+    for task in tasks {
+        let project = task
+            .project_id
+            .as_deref()
+            .and_then(|id| project_names.get(id).copied())
+            .unwrap_or("");
+        let fields = [
+            task.id.clone(),
+            task.title.clone(),
+            task.status.as_str().to_string(),
+            task.priority.as_str().to_string(),
+            task.due_date.map(|d| d.to_string()).unwrap_or_default(),
+            project.to_string(),
+            task.created_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            task.updated_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            task.completed_at
+                .map(|d| d.to_rfc3339_opts(SecondsFormat::Secs, true))
+                .unwrap_or_default(),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a CSV field when it contains separators, quotes or newlines
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -1,95 +1,170 @@
 use chrono::{NaiveDate, Utc};
 use crate::models::{Task, TaskStatus};
 
-/// Filter tasks for the Inbox view (status = inbox)
-pub fn filter_inbox(tasks: &[Task]) -> Vec<&Task> {
+// Task predicates, combined per view by `select`
+
+/// Not soft-deleted
+pub fn not_deleted(task: &Task) -> bool {
+    !task.deleted
+}
+
+/// Neither completed nor cancelled
+pub fn not_completed(task: &Task) -> bool {
+    task.status.is_open()
+}
+
+/// Start date in the future
+pub fn deferred(task: &Task) -> bool {
+    task.is_deferred()
+}
+
+pub fn not_deferred(task: &Task) -> bool {
+    !task.is_deferred()
+}
+
+pub fn flagged(task: &Task) -> bool {
+    task.flagged
+}
+
+pub fn no_due_date(task: &Task) -> bool {
+    task.due_date.is_none()
+}
+
+pub fn has_status(status: TaskStatus) -> impl Fn(&Task) -> bool {
+    move |task| task.status == status
+}
+
+pub fn has_project(project_id: &str) -> impl Fn(&Task) -> bool + '_ {
+    move |task| task.project_id.as_deref() == Some(project_id)
+}
+
+pub fn has_tag(tag_id: &str) -> impl Fn(&Task) -> bool + '_ {
+    move |task| task.tags.iter().any(|id| id == tag_id)
+}
+
+/// Start date on or before `date`
+pub fn started_by(date: NaiveDate) -> impl Fn(&Task) -> bool {
+    move |task| task.start_date.is_some_and(|start| start <= date)
+}
+
+/// Due strictly before `date`
+pub fn due_before(date: NaiveDate) -> impl Fn(&Task) -> bool {
+    move |task| task.due_date.is_some_and(|due| due < date)
+}
+
+/// Due strictly after `date`
+pub fn due_after(date: NaiveDate) -> impl Fn(&Task) -> bool {
+    move |task| task.due_date.is_some_and(|due| due > date)
+}
+
+/// Due within `from..=to`
+pub fn due_between(from: NaiveDate, to: NaiveDate) -> impl Fn(&Task) -> bool {
+    move |task| task.due_date.is_some_and(|due| from <= due && due <= to)
+}
+
+/// Completed within `from..=to`, by the date of `completed_at`
+pub fn completed_between(from: NaiveDate, to: NaiveDate) -> impl Fn(&Task) -> bool {
+    move |task| {
+        task.completed_at
+            .is_some_and(|at| from <= at.date_naive() && at.date_naive() <= to)
+    }
+}
+
+/// Tasks matching every predicate, in their original order
+pub fn select<'a>(tasks: &'a [Task], predicates: &[&dyn Fn(&Task) -> bool]) -> Vec<&'a Task> {
     tasks
         .iter()
-        .filter(|t| t.status == TaskStatus::Inbox && !t.deleted)
+        .filter(|t| predicates.iter().all(|p| p(t)))
         .collect()
 }
 
-/// Filter tasks for Today view (due today or overdue, not completed)
-pub fn filter_today(tasks: &[Task]) -> Vec<&Task> {
+/// Filter tasks for the Inbox view (status = inbox, not deferred)
+pub fn filter_inbox(tasks: &[Task]) -> Vec<&Task> {
+    select(tasks, &[&not_deleted, &has_status(TaskStatus::Inbox), &not_deferred])
+}
+
+/// Filter tasks for Active view (status = active, regardless of due date)
+pub fn filter_active(tasks: &[Task]) -> Vec<&Task> {
+    select(tasks, &[&not_deleted, &has_status(TaskStatus::Active)])
+}
+
+/// Filter tasks for the Scheduled view (status = scheduled, whatever the dates;
+/// those with a due date are also listed by the due-date views)
+pub fn filter_scheduled(tasks: &[Task]) -> Vec<&Task> {
+    select(tasks, &[&not_deleted, &has_status(TaskStatus::Scheduled)])
+}
+
+/// Filter tasks for Today view (due today, plus overdue if `include_overdue`, still open)
+pub fn filter_today(tasks: &[Task], include_overdue: bool) -> Vec<&Task> {
     let today = Utc::now().date_naive();
-    tasks
-        .iter()
-        .filter(|t| {
-            !t.deleted
-                && t.status != TaskStatus::Completed
-                && t.due_date.is_some_and(|due| due <= today)
-        })
-        .collect()
+    let from = if include_overdue { NaiveDate::MIN } else { today };
+    select(tasks, &[&not_deleted, &not_completed, &due_between(from, today)])
+}
+
+/// Filter tasks for the This Week view (due within the next 7 days, plus overdue
+/// if `include_overdue`, still open)
+pub fn filter_this_week(tasks: &[Task], include_overdue: bool) -> Vec<&Task> {
+    let today = Utc::now().date_naive();
+    let from = if include_overdue { NaiveDate::MIN } else { today };
+    let week_end = today + chrono::Duration::days(6);
+    select(tasks, &[&not_deleted, &not_completed, &due_between(from, week_end)])
 }
 
-/// Filter tasks for Upcoming view (future due dates, not completed)
+/// Filter tasks for Upcoming view (future due dates, still open)
 pub fn filter_upcoming(tasks: &[Task]) -> Vec<&Task> {
     let today = Utc::now().date_naive();
-    tasks
-        .iter()
-        .filter(|t| {
-            !t.deleted
-                && t.status != TaskStatus::Completed
-                && t.due_date.is_some_and(|due| due > today)
-        })
-        .collect()
+    select(tasks, &[&not_deleted, &not_completed, &due_after(today)])
 }
 
-/// Filter tasks for Anytime view (no due date, not completed)
+/// Filter tasks for Anytime view (no due date, still open, not deferred)
 pub fn filter_anytime(tasks: &[Task]) -> Vec<&Task> {
-    tasks
-        .iter()
-        .filter(|t| {
-            !t.deleted
-                && t.status != TaskStatus::Completed
-                && t.due_date.is_none()
-        })
-        .collect()
+    select(tasks, &[&not_deleted, &not_completed, &no_due_date, &not_deferred])
+}
+
+/// Filter tasks for Deferred view (start date in the future), earliest start first
+pub fn filter_deferred(tasks: &[Task]) -> Vec<&Task> {
+    let mut result = select(tasks, &[&not_deleted, &not_completed, &deferred]);
+    result.sort_by_key(|t| t.start_date);
+    result
+}
+
+/// Scheduled tasks whose start date has arrived by `today`, due to become active
+pub fn filter_started_scheduled(tasks: &[Task], today: NaiveDate) -> Vec<&Task> {
+    select(tasks, &[&not_deleted, &has_status(TaskStatus::Scheduled), &started_by(today)])
+}
+
+/// Filter tasks for Flagged view (flagged tasks of any status)
+pub fn filter_flagged(tasks: &[Task]) -> Vec<&Task> {
+    select(tasks, &[&not_deleted, &flagged])
 }
 
 /// Filter tasks for Completed view
 pub fn filter_completed(tasks: &[Task]) -> Vec<&Task> {
-    tasks
-        .iter()
-        .filter(|t| !t.deleted && t.status == TaskStatus::Completed)
-        .collect()
+    select(tasks, &[&not_deleted, &has_status(TaskStatus::Completed)])
+}
+
+/// Filter tasks for the Completed view, limited to completions within `from..=to`
+pub fn filter_completed_between(tasks: &[Task], from: NaiveDate, to: NaiveDate) -> Vec<&Task> {
+    select(
+        tasks,
+        &[&not_deleted, &has_status(TaskStatus::Completed), &completed_between(from, to)],
+    )
 }
 
 /// Filter tasks by project ID
 pub fn filter_by_project<'a>(tasks: &'a [Task], project_id: &str) -> Vec<&'a Task> {
-    tasks
-        .iter()
-        .filter(|t| {
-            !t.deleted
-                && t.status != TaskStatus::Completed
-                && t.project_id.as_deref() == Some(project_id)
-        })
-        .collect()
+    select(tasks, &[&not_deleted, &not_completed, &has_project(project_id)])
 }
 
 /// Filter tasks by tag ID
 pub fn filter_by_tag<'a>(tasks: &'a [Task], tag_id: &str) -> Vec<&'a Task> {
-    tasks
-        .iter()
-        .filter(|t| {
-            !t.deleted
-                && t.status != TaskStatus::Completed
-                && t.tags.iter().any(|tid| tid == tag_id)
-        })
-        .collect()
+    select(tasks, &[&not_deleted, &not_completed, &has_tag(tag_id)])
 }
 
 /// Filter tasks for Review view (overdue tasks)
 pub fn filter_review(tasks: &[Task]) -> Vec<&Task> {
     let today = Utc::now().date_naive();
-    tasks
-        .iter()
-        .filter(|t| {
-            !t.deleted
-                && t.status != TaskStatus::Completed
-                && t.due_date.is_some_and(|due| due < today)
-        })
-        .collect()
+    select(tasks, &[&not_deleted, &not_completed, &due_before(today)])
 }
 
 /// Search tasks by title or notes
@@ -107,6 +182,17 @@ pub fn search_tasks<'a>(tasks: &'a [Task], query: &str) -> Vec<&'a Task> {
         .collect()
 }
 
+/// Whether all characters of `query` appear in `text` in order (case-insensitive),
+/// so "rvw pr" matches "Review PR #12"
+pub fn fuzzy_matches(query: &str, text: &str) -> bool {
+    let mut text_chars = text.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .all(|q| text_chars.any(|c| c == q))
+}
+
 /// Sort tasks by due date (ascending, nulls last)
 pub fn sort_by_due_date(tasks: &mut [&Task]) {
     tasks.sort_by(|a, b| {
@@ -128,6 +214,16 @@ pub fn sort_by_priority(tasks: &mut [&Task]) {
     });
 }
 
+/// Sort tasks for the Today view: overdue first, then priority (descending), then due date
+pub fn sort_for_today(tasks: &mut [&Task]) {
+    tasks.sort_by(|a, b| {
+        b.is_overdue()
+            .cmp(&a.is_overdue())
+            .then_with(|| priority_value(&b.priority).cmp(&priority_value(&a.priority)))
+            .then_with(|| a.due_date.cmp(&b.due_date))
+    });
+}
+
 fn priority_value(priority: &crate::models::TaskPriority) -> u8 {
     use crate::models::TaskPriority;
     match priority {
@@ -138,6 +234,37 @@ fn priority_value(priority: &crate::models::TaskPriority) -> u8 {
     }
 }
 
+/// Sections of the Agenda view: overdue, due today, then active tasks not already
+/// listed. Empty sections are omitted.
+pub fn agenda_sections(tasks: &[Task]) -> Vec<(&'static str, Vec<&Task>)> {
+    let today = Utc::now().date_naive();
+    let mut due = filter_today(tasks, true);
+    sort_for_today(&mut due);
+    let (overdue, due_today): (Vec<&Task>, Vec<&Task>) =
+        due.into_iter().partition(|t| t.is_overdue());
+    let next_actions: Vec<&Task> = filter_active(tasks)
+        .into_iter()
+        .filter(|t| !t.due_date.is_some_and(|d| d <= today))
+        .collect();
+
+    [
+        ("Overdue", overdue),
+        ("Due Today", due_today),
+        ("Next Actions", next_actions),
+    ]
+    .into_iter()
+    .filter(|(_, tasks)| !tasks.is_empty())
+    .collect()
+}
+
+/// Filter tasks for Agenda view (all agenda sections, in order)
+pub fn filter_agenda(tasks: &[Task]) -> Vec<&Task> {
+    agenda_sections(tasks)
+        .into_iter()
+        .flat_map(|(_, tasks)| tasks)
+        .collect()
+}
+
 /// Group tasks by due date
 pub fn group_by_date(tasks: Vec<&Task>) -> Vec<(Option<NaiveDate>, Vec<&Task>)> {
     use std::collections::BTreeMap;
@@ -150,3 +277,179 @@ pub fn group_by_date(tasks: Vec<&Task>) -> Vec<(Option<NaiveDate>, Vec<&Task>)>
 
     groups.into_iter().collect()
 }
+
+/// Group tasks by project (None = no project), groups in order of first appearance.
+/// Tasks whose project is not `known` (e.g. deleted) join the no-project group.
+pub fn group_by_project(
+    tasks: Vec<&Task>,
+    known: impl Fn(&str) -> bool,
+) -> Vec<(Option<String>, Vec<&Task>)> {
+    let mut groups: Vec<(Option<String>, Vec<&Task>)> = Vec::new();
+
+    for task in tasks {
+        let project_id = task.project_id.as_deref().filter(|id| known(id));
+        match groups.iter_mut().find(|(id, _)| id.as_deref() == project_id) {
+            Some((_, group)) => group.push(task),
+            None => groups.push((project_id.map(str::to_string), vec![task])),
+        }
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn task(title: &str, status: TaskStatus) -> Task {
+        let mut task = Task::new(title.to_string());
+        task.status = status;
+        task
+    }
+
+    fn titles(tasks: Vec<&Task>) -> Vec<&str> {
+        tasks.into_iter().map(|t| t.title.as_str()).collect()
+    }
+
+    #[test]
+    fn started_scheduled_includes_start_dates_up_to_today() {
+        let today = date(2024, 3, 10);
+        let mut tasks = Vec::new();
+        for (title, start) in [
+            ("yesterday", today.pred_opt()),
+            ("today", Some(today)),
+            ("tomorrow", today.succ_opt()),
+            ("no start", None),
+        ] {
+            let mut t = task(title, TaskStatus::Scheduled);
+            t.start_date = start;
+            tasks.push(t);
+        }
+
+        assert_eq!(titles(filter_started_scheduled(&tasks, today)), vec!["yesterday", "today"]);
+    }
+
+    #[test]
+    fn started_scheduled_skips_other_statuses_and_deleted_tasks() {
+        let today = date(2024, 3, 10);
+        let mut active = task("active", TaskStatus::Active);
+        active.start_date = Some(today);
+        let mut deleted = task("deleted", TaskStatus::Scheduled);
+        deleted.start_date = Some(today);
+        deleted.deleted = true;
+
+        assert!(filter_started_scheduled(&[active, deleted], today).is_empty());
+    }
+
+    #[test]
+    fn completed_between_includes_both_end_days() {
+        let from = date(2024, 3, 1);
+        let to = date(2024, 3, 5);
+        let mut tasks = Vec::new();
+        for (title, at) in [
+            ("day before", date(2024, 2, 29).and_hms_opt(23, 59, 59)),
+            ("first day", from.and_hms_opt(0, 0, 0)),
+            ("last day", to.and_hms_opt(23, 59, 59)),
+            ("day after", date(2024, 3, 6).and_hms_opt(0, 0, 0)),
+        ] {
+            let mut t = task(title, TaskStatus::Completed);
+            t.completed_at = at.map(|at| at.and_utc());
+            tasks.push(t);
+        }
+
+        assert_eq!(
+            titles(filter_completed_between(&tasks, from, to)),
+            vec!["first day", "last day"]
+        );
+    }
+
+    #[test]
+    fn completed_between_skips_reopened_tasks() {
+        let day = date(2024, 3, 1);
+        let mut reopened = task("reopened", TaskStatus::Active);
+        reopened.completed_at = day.and_hms_opt(12, 0, 0).map(|at| at.and_utc());
+
+        assert!(filter_completed_between(&[reopened], day, day).is_empty());
+    }
+
+    #[test]
+    fn select_requires_every_predicate_and_keeps_order() {
+        let mut tasks = Vec::new();
+        for (title, status, flag, project) in [
+            ("a", TaskStatus::Active, true, Some("p1")),
+            ("b", TaskStatus::Active, false, Some("p1")),
+            ("c", TaskStatus::Inbox, true, Some("p1")),
+            ("d", TaskStatus::Active, true, Some("p2")),
+            ("e", TaskStatus::Active, true, Some("p1")),
+        ] {
+            let mut t = task(title, status);
+            t.flagged = flag;
+            t.project_id = project.map(str::to_string);
+            tasks.push(t);
+        }
+        tasks[4].deleted = true;
+
+        let selected = select(
+            &tasks,
+            &[&not_deleted, &has_status(TaskStatus::Active), &flagged, &has_project("p1")],
+        );
+        assert_eq!(titles(selected), vec!["a"]);
+    }
+
+    #[test]
+    fn select_without_predicates_keeps_everything() {
+        let tasks = vec![task("a", TaskStatus::Inbox), task("b", TaskStatus::Cancelled)];
+        assert_eq!(titles(select(&tasks, &[])), vec!["a", "b"]);
+    }
+
+    fn due_tasks() -> Vec<Task> {
+        let today = Utc::now().date_naive();
+        let mut tasks = Vec::new();
+        for (title, status, due) in [
+            ("overdue", TaskStatus::Active, today.pred_opt()),
+            ("today", TaskStatus::Inbox, Some(today)),
+            ("tomorrow", TaskStatus::Active, today.succ_opt()),
+            ("undated", TaskStatus::Active, None),
+            ("done today", TaskStatus::Completed, Some(today)),
+            ("cancelled overdue", TaskStatus::Cancelled, today.pred_opt()),
+        ] {
+            let mut t = task(title, status);
+            t.due_date = due;
+            tasks.push(t);
+        }
+        tasks
+    }
+
+    #[test]
+    fn today_lists_open_tasks_due_today() {
+        let tasks = due_tasks();
+        assert_eq!(titles(filter_today(&tasks, false)), vec!["today"]);
+    }
+
+    #[test]
+    fn today_adds_open_overdue_tasks_when_asked() {
+        let tasks = due_tasks();
+        assert_eq!(titles(filter_today(&tasks, true)), vec!["overdue", "today"]);
+    }
+
+    #[test]
+    fn group_by_project_puts_unknown_projects_with_no_project() {
+        let mut tasks = Vec::new();
+        for (title, project) in [("a", Some("p1")), ("b", None), ("c", Some("gone")), ("d", Some("p1"))] {
+            let mut t = task(title, TaskStatus::Completed);
+            t.project_id = project.map(str::to_string);
+            tasks.push(t);
+        }
+        let groups = group_by_project(tasks.iter().collect(), |id| id == "p1");
+        let groups: Vec<(Option<String>, Vec<&str>)> =
+            groups.into_iter().map(|(id, tasks)| (id, titles(tasks))).collect();
+        assert_eq!(
+            groups,
+            vec![(Some("p1".to_string()), vec!["a", "d"]), (None, vec!["b", "c"])]
+        );
+    }
+}
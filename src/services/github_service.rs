@@ -1,5 +1,10 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use crate::error::{AppError, Result};
+use std::collections::{HashMap, HashSet};
+use crate::config::DEFAULT_HTTP_RETRIES;
+use crate::error::{http_error_message, AppError, Result};
+use crate::models::Task;
+use crate::services::{http_client, send_with_retry};
 
 const GITHUB_API_BASE: &str = "https://api.github.com";
 
@@ -25,9 +30,18 @@ pub struct GitHubIssue {
     pub repository_url: Option<String>,
     pub user: Option<GitHubUser>,
     pub pull_request: Option<serde_json::Value>,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl GitHubIssue {
+    /// How long ago the item was opened, e.g. "5h", "2d", "3w"
+    pub fn age(&self) -> Option<String> {
+        self.created_at.map(|created| format_age(Utc::now() - created))
+    }
+
     /// Get the repository full name (owner/repo)
     pub fn repo_name(&self) -> String {
         if let Some(ref repo) = self.repository {
@@ -61,26 +75,101 @@ pub struct GitHubSearchResult {
     pub items: Vec<GitHubIssue>,
 }
 
+/// The update a re-sync makes to the existing task for `item`: a closed item
+/// completes it unless it's already completed or cancelled, and a missing project
+/// or repo tag is filled in. Title and notes
+/// are never taken from GitHub again, as they may have been edited locally.
+/// None when nothing changes.
+pub fn merge_github_item(
+    task: &Task,
+    item: &GitHubIssue,
+    project_id: Option<&str>,
+    tag_id: Option<&str>,
+) -> Option<Task> {
+    let mut updated = task.clone();
+    let mut changed = false;
+
+    if item.state == "closed" && task.status.is_open() {
+        updated.complete();
+        changed = true;
+    }
+
+    if task.project_id.is_none() {
+        if let Some(project_id) = project_id {
+            updated.project_id = Some(project_id.to_string());
+            changed = true;
+        }
+    }
+
+    if let Some(tag_id) = tag_id {
+        if !task.tags.iter().any(|t| t == tag_id) {
+            updated.tags.push(tag_id.to_string());
+            changed = true;
+        }
+    }
+
+    changed.then(|| {
+        updated.updated_at = Utc::now();
+        updated
+    })
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct GitHubData {
     pub review_prs: Vec<GitHubIssue>,
     pub my_prs: Vec<GitHubIssue>,
     pub assigned_issues: Vec<GitHubIssue>,
+    /// Fetch errors for columns that failed, keyed "review", "mine" or "assigned"
+    pub errors: HashMap<&'static str, String>,
+}
+
+impl GitHubData {
+    /// Remove items already listed in an earlier column (review, mine, assigned order).
+    /// Returns, per kept item URL, the labels of the other columns it also appeared in.
+    pub fn dedup_by_url(&mut self) -> HashMap<String, Vec<&'static str>> {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut also_in: HashMap<String, Vec<&'static str>> = HashMap::new();
+
+        for (items, label) in [
+            (&mut self.review_prs, "review"),
+            (&mut self.my_prs, "mine"),
+            (&mut self.assigned_issues, "assigned"),
+        ] {
+            items.retain(|item| {
+                if seen.insert(item.html_url.clone()) {
+                    true
+                } else {
+                    also_in.entry(item.html_url.clone()).or_default().push(label);
+                    false
+                }
+            });
+        }
+
+        also_in
+    }
 }
 
 pub struct GitHubService {
     client: reqwest::Client,
     token: String,
+    retries: u32,
 }
 
 impl GitHubService {
     pub fn new(token: String) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: http_client(),
             token,
+            retries: DEFAULT_HTTP_RETRIES,
         }
     }
 
+    /// Retry transient failures (5xx gateway errors, timeouts) this many times
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
     /// Fetch issues assigned to the authenticated user (excluding PRs)
     pub async fn fetch_assigned_issues(&self) -> Result<Vec<GitHubIssue>> {
         let url = format!("{}/issues?filter=assigned&state=open&per_page=100", GITHUB_API_BASE);
@@ -133,37 +222,55 @@ impl GitHubService {
         Ok(prs)
     }
 
-    /// Fetch all GitHub data in parallel
+    /// Fetch all GitHub data in parallel.
+    /// A failing query only empties its own column; it's an error only if all fail.
     pub async fn fetch_all(&self) -> Result<GitHubData> {
-        let (review_prs, my_prs, assigned_issues) = tokio::try_join!(
+        let (review_prs, my_prs, assigned_issues) = tokio::join!(
             self.fetch_review_requested_prs(),
             self.fetch_my_open_prs(),
             self.fetch_assigned_issues(),
-        )?;
+        );
+
+        if let (Err(e), Err(_), Err(_)) = (&review_prs, &my_prs, &assigned_issues) {
+            return Err(AppError::GitHub(e.to_string()));
+        }
+
+        let mut errors = HashMap::new();
+        let mut column = |result: Result<Vec<GitHubIssue>>, label: &'static str| {
+            result.unwrap_or_else(|e| {
+                errors.insert(label, e.to_string());
+                Vec::new()
+            })
+        };
+        let review_prs = column(review_prs, "review");
+        let my_prs = column(my_prs, "mine");
+        let assigned_issues = column(assigned_issues, "assigned");
 
         Ok(GitHubData {
             review_prs,
             my_prs,
             assigned_issues,
+            errors,
         })
     }
 
     async fn fetch_with_auth(&self, url: &str) -> Result<String> {
-        let response = self
-            .client
-            .get(url)
-            .header("Authorization", format!("token {}", self.token))
-            .header("Accept", "application/vnd.github.v3+json")
-            .header("User-Agent", "phitodo-tui")
-            .send()
-            .await?;
+        let request = || {
+            self.client
+                .get(url)
+                .header("Authorization", format!("token {}", self.token))
+                .header("Accept", "application/vnd.github.v3+json")
+                .header("User-Agent", "phitodo-tui")
+        };
+        let response = send_with_retry(request, self.retries).await?;
 
         let status = response.status();
         if status == 401 {
             return Err(AppError::GitHub("Invalid token. Check Settings.".to_string()));
         }
         if !status.is_success() {
-            return Err(AppError::GitHub(format!("HTTP error: {}", status)));
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::GitHub(http_error_message(status, &body)));
         }
 
         let text = response.text().await?;
@@ -171,6 +278,25 @@ impl GitHubService {
     }
 }
 
+/// Compact relative age: minutes, hours, days, weeks, months, years
+pub fn format_age(age: chrono::Duration) -> String {
+    let minutes = age.num_minutes().max(0);
+    let days = age.num_days();
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else if days < 1 {
+        format!("{}h", age.num_hours())
+    } else if days < 14 {
+        format!("{}d", days)
+    } else if days < 60 {
+        format!("{}w", days / 7)
+    } else if days < 365 {
+        format!("{}mo", days / 30)
+    } else {
+        format!("{}y", days / 365)
+    }
+}
+
 /// Extract owner/repo from repository_url (e.g., https://api.github.com/repos/owner/repo)
 fn extract_repo_from_url(url: &str) -> Option<String> {
     let parts: Vec<&str> = url.split('/').collect();
@@ -202,3 +328,117 @@ fn normalize_issue(mut issue: GitHubIssue) -> GitHubIssue {
     }
     issue
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TaskStatus;
+
+    fn issue(title: &str, state: &str) -> GitHubIssue {
+        GitHubIssue {
+            id: 1,
+            number: 7,
+            title: title.to_string(),
+            html_url: "https://github.com/owner/repo/issues/7".to_string(),
+            state: state.to_string(),
+            body: Some("Body from GitHub".to_string()),
+            repository: None,
+            repository_url: None,
+            user: None,
+            pull_request: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn synced_task() -> Task {
+        let mut task = Task::new("Original title".to_string());
+        task.context_url = Some("https://github.com/owner/repo/issues/7".to_string());
+        task.notes = Some("Body from GitHub".to_string());
+        task.project_id = Some("project".to_string());
+        task
+    }
+
+    #[test]
+    fn merge_keeps_a_locally_renamed_title_and_notes() {
+        let mut task = synced_task();
+        task.title = "My own title".to_string();
+        task.notes = Some("My notes".to_string());
+
+        // Nothing else changed, so there is nothing to save
+        let open = issue("Renamed upstream", "open");
+        assert!(merge_github_item(&task, &open, Some("project"), None).is_none());
+
+        let merged = merge_github_item(&task, &issue("Renamed upstream", "closed"), None, None).unwrap();
+        assert_eq!(merged.title, "My own title");
+        assert_eq!(merged.notes.as_deref(), Some("My notes"));
+        assert_eq!(merged.status, TaskStatus::Completed);
+    }
+
+    #[test]
+    fn merge_fills_in_missing_project_and_tag_only() {
+        let mut task = synced_task();
+        task.project_id = None;
+        let merged = merge_github_item(&task, &issue("t", "open"), Some("repo"), Some("tag")).unwrap();
+        assert_eq!(merged.project_id.as_deref(), Some("repo"));
+        assert_eq!(merged.tags, vec!["tag".to_string()]);
+
+        // An existing project is left alone, and the tag isn't added twice
+        let again = merge_github_item(&merged, &issue("t", "open"), Some("other"), Some("tag"));
+        assert!(again.is_none());
+    }
+
+    fn issue_at(url: &str) -> GitHubIssue {
+        GitHubIssue {
+            html_url: url.to_string(),
+            ..issue(url, "open")
+        }
+    }
+
+    fn urls(items: &[GitHubIssue]) -> Vec<&str> {
+        items.iter().map(|i| i.html_url.as_str()).collect()
+    }
+
+    #[test]
+    fn dedup_keeps_each_item_in_its_first_column() {
+        let mut data = GitHubData {
+            review_prs: vec![issue_at("pr/1"), issue_at("pr/2")],
+            my_prs: vec![issue_at("pr/2"), issue_at("pr/3")],
+            assigned_issues: vec![issue_at("pr/2"), issue_at("pr/3"), issue_at("issue/4")],
+            ..GitHubData::default()
+        };
+
+        let also_in = data.dedup_by_url();
+
+        assert_eq!(urls(&data.review_prs), vec!["pr/1", "pr/2"]);
+        assert_eq!(urls(&data.my_prs), vec!["pr/3"]);
+        assert_eq!(urls(&data.assigned_issues), vec!["issue/4"]);
+        assert_eq!(also_in.len(), 2);
+        assert_eq!(also_in["pr/2"], vec!["mine", "assigned"]);
+        assert_eq!(also_in["pr/3"], vec!["assigned"]);
+    }
+
+    #[test]
+    fn merge_completes_a_closed_item_like_a_local_completion() {
+        let mut task = synced_task();
+        task.status = TaskStatus::Active;
+
+        let merged = merge_github_item(&task, &issue("t", "closed"), None, None).unwrap();
+        assert_eq!(merged.status, TaskStatus::Completed);
+        assert!(merged.completed_at.is_some());
+
+        // Reopening restores the status it had before GitHub closed it
+        let mut reopened = merged.clone();
+        reopened.reopen();
+        assert_eq!(reopened.status, TaskStatus::Active);
+    }
+
+    #[test]
+    fn merge_leaves_cancelled_and_completed_tasks_alone() {
+        for status in [TaskStatus::Cancelled, TaskStatus::Completed] {
+            let mut task = synced_task();
+            task.status = status;
+            assert!(merge_github_item(&task, &issue("t", "closed"), None, None).is_none());
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use reqwest::{RequestBuilder, Response, StatusCode};
+
+/// Delay before the first retry, doubled for each one after it
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Longest a GitHub/Toggl request may take before it fails as a (retried) timeout
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// HTTP client for the sync services; requests time out after `REQUEST_TIMEOUT`
+/// so `send_with_retry` can retry them instead of waiting forever
+pub fn http_client() -> reqwest::Client {
+    client_with_timeout(REQUEST_TIMEOUT)
+}
+
+fn client_with_timeout(timeout: Duration) -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .expect("failed to build the HTTP client")
+}
+
+/// Send the request made by `build`, retrying gateway errors (502/503/504) and
+/// timeouts up to `retries` more times with exponential backoff. Anything else,
+/// including client errors such as 401/403/422, is returned right away; so is the
+/// last response once the retries run out.
+pub async fn send_with_retry(
+    build: impl Fn() -> RequestBuilder,
+    retries: u32,
+) -> reqwest::Result<Response> {
+    let mut attempt = 0;
+    loop {
+        let result = build().send().await;
+        let transient = match &result {
+            Ok(response) => is_transient_status(response.status()),
+            Err(e) => e.is_timeout(),
+        };
+        if !transient || attempt >= retries {
+            return result;
+        }
+        tokio::time::sleep(RETRY_BASE_DELAY * 2u32.saturating_pow(attempt)).await;
+        attempt += 1;
+    }
+}
+
+fn is_transient_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    /// Serve one canned status per connection ("hang" never answers); the handle
+    /// yields how many connections were accepted
+    fn serve(statuses: &'static [&'static str]) -> (String, JoinHandle<usize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut served = 0;
+            let mut hung = Vec::new();
+            for status in statuses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf).unwrap();
+                served += 1;
+                if *status == "hang" {
+                    hung.push(stream);
+                    continue;
+                }
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            served
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn retries_a_gateway_error_until_the_request_succeeds() {
+        let (url, server) = serve(&["503 Service Unavailable", "200 OK"]);
+        let client = reqwest::Client::new();
+
+        let response = send_with_retry(|| client.get(&url), 2).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(server.join().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn returns_client_errors_without_retrying() {
+        let (url, server) = serve(&["404 Not Found"]);
+        let client = reqwest::Client::new();
+
+        let response = send_with_retry(|| client.get(&url), 2).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(server.join().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_the_last_retry() {
+        let (url, server) = serve(&["502 Bad Gateway", "502 Bad Gateway"]);
+        let client = reqwest::Client::new();
+
+        let response = send_with_retry(|| client.get(&url), 1).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(server.join().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn retries_a_request_that_times_out() {
+        let (url, server) = serve(&["hang", "200 OK"]);
+        let client = client_with_timeout(Duration::from_millis(200));
+
+        let response = send_with_retry(|| client.get(&url), 1).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(server.join().unwrap(), 2);
+    }
+}
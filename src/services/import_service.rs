@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::{AppError, Result};
+use crate::models::{Project, Tag, Task, TaskPriority, TaskStatus};
+
+/// Source app for `phitodo-tui import --from <source>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportSource {
+    Todoist,
+}
+
+impl ImportSource {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "todoist" => Some(ImportSource::Todoist),
+            _ => None,
+        }
+    }
+}
+
+/// New records to insert, plus how many source items could not be imported
+#[derive(Debug, Default)]
+pub struct ImportResult {
+    pub projects: Vec<Project>,
+    pub tags: Vec<Tag>,
+    pub tasks: Vec<Task>,
+    pub skipped: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct TodoistExport {
+    #[serde(default)]
+    projects: Vec<TodoistProject>,
+    #[serde(default)]
+    items: Vec<TodoistItem>,
+    #[serde(default)]
+    labels: Vec<TodoistLabel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TodoistProject {
+    id: Value,
+    name: String,
+    #[serde(default)]
+    is_deleted: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct TodoistLabel {
+    id: Value,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TodoistItem {
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    project_id: Option<Value>,
+    /// 1 (normal) to 4 (urgent)
+    #[serde(default)]
+    priority: Option<i64>,
+    #[serde(default)]
+    due: Option<TodoistDue>,
+    /// Label names (current exports) or label ids (older exports)
+    #[serde(default)]
+    labels: Vec<Value>,
+    #[serde(default)]
+    checked: bool,
+    #[serde(default)]
+    is_deleted: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct TodoistDue {
+    /// "YYYY-MM-DD" or a date-time starting with it
+    date: String,
+}
+
+/// Todoist ids are numbers in older exports and strings in newer ones
+fn id_key(id: &Value) -> String {
+    match id {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn map_priority(priority: Option<i64>) -> TaskPriority {
+    match priority {
+        Some(4) => TaskPriority::High,
+        Some(3) => TaskPriority::Medium,
+        Some(2) => TaskPriority::Low,
+        _ => TaskPriority::None,
+    }
+}
+
+/// Map a Todoist export onto new projects, tags and tasks. Projects and tags whose
+/// names already exist are reused rather than duplicated.
+pub fn parse_todoist(json: &str, existing_projects: &[Project], existing_tags: &[Tag]) -> Result<ImportResult> {
+    let export: TodoistExport = serde_json::from_str(json)
+        .map_err(|e| AppError::Other(format!("Not a Todoist export: {}", e)))?;
+    let mut result = ImportResult::default();
+
+    // Project ids by name, then Todoist project id -> our project id
+    let mut project_ids: HashMap<String, String> = existing_projects
+        .iter()
+        .map(|p| (p.name.clone(), p.id.clone()))
+        .collect();
+    let mut project_map: HashMap<String, String> = HashMap::new();
+    for project in export.projects.iter().filter(|p| !p.is_deleted) {
+        let id = project_ids.entry(project.name.clone()).or_insert_with(|| {
+            let new_project = Project::new(project.name.clone());
+            let id = new_project.id.clone();
+            result.projects.push(new_project);
+            id
+        });
+        project_map.insert(id_key(&project.id), id.clone());
+    }
+
+    // Label ids and names both resolve to a label name
+    let label_names: HashMap<String, String> = export
+        .labels
+        .iter()
+        .map(|l| (id_key(&l.id), l.name.clone()))
+        .collect();
+    let mut tag_ids: HashMap<String, String> = existing_tags
+        .iter()
+        .map(|t| (Tag::key(&t.name), t.id.clone()))
+        .collect();
+
+    for item in &export.items {
+        if item.is_deleted {
+            continue;
+        }
+        let title = item.content.trim();
+        if title.is_empty() {
+            result.skipped += 1;
+            continue;
+        }
+
+        let mut task = Task::new(title.to_string());
+        task.notes = item.description.clone().filter(|d| !d.trim().is_empty());
+        task.priority = map_priority(item.priority);
+        task.project_id = item
+            .project_id
+            .as_ref()
+            .and_then(|id| project_map.get(&id_key(id)).cloned());
+        task.due_date = item
+            .due
+            .as_ref()
+            .and_then(|due| due.date.get(..10))
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+        if item.checked {
+            task.status = TaskStatus::Completed;
+            task.completed_at = Some(Utc::now());
+        }
+
+        for label in &item.labels {
+            let key = id_key(label);
+            let name = label_names.get(&key).cloned().unwrap_or(key);
+            let name = Tag::clean_name(&name).to_string();
+            let id = tag_ids.entry(Tag::key(&name)).or_insert_with(|| {
+                let tag = Tag::new(name);
+                let id = tag.id.clone();
+                result.tags.push(tag);
+                id
+            });
+            if !task.tags.contains(id) {
+                task.tags.push(id.clone());
+            }
+        }
+
+        result.tasks.push(task);
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = r#"{
+        "projects": [
+            {"id": "p1", "name": "Work"},
+            {"id": 2, "name": "Home"},
+            {"id": "p3", "name": "Old", "is_deleted": true}
+        ],
+        "sections": [{"id": "s1", "name": "Next week", "project_id": "p1"}],
+        "labels": [{"id": 10, "name": "errands"}],
+        "items": [
+            {"content": "urgent", "priority": 4, "project_id": "p1", "section_id": "s1"},
+            {"content": "high", "priority": 3, "project_id": 2, "labels": ["PHONE", 10]},
+            {"content": "medium", "priority": 2, "due": {"date": "2024-05-01"}},
+            {"content": "normal", "priority": 1, "due": {"date": "2024-05-02T09:30:00Z"}},
+            {"content": "no priority", "due": {"date": "someday"}, "checked": true},
+            {"content": "   "},
+            {"content": "gone", "is_deleted": true}
+        ]
+    }"#;
+
+    fn task<'a>(result: &'a ImportResult, title: &str) -> &'a Task {
+        result.tasks.iter().find(|t| t.title == title).unwrap()
+    }
+
+    #[test]
+    fn maps_todoist_priorities() {
+        let result = parse_todoist(EXPORT, &[], &[]).unwrap();
+        let priority = |title| task(&result, title).priority;
+        assert_eq!(priority("urgent"), TaskPriority::High);
+        assert_eq!(priority("high"), TaskPriority::Medium);
+        assert_eq!(priority("medium"), TaskPriority::Low);
+        assert_eq!(priority("normal"), TaskPriority::None);
+        assert_eq!(priority("no priority"), TaskPriority::None);
+    }
+
+    #[test]
+    fn reads_due_dates_and_date_times() {
+        let result = parse_todoist(EXPORT, &[], &[]).unwrap();
+        assert_eq!(task(&result, "medium").due_date, NaiveDate::from_ymd_opt(2024, 5, 1));
+        assert_eq!(task(&result, "normal").due_date, NaiveDate::from_ymd_opt(2024, 5, 2));
+        assert_eq!(task(&result, "no priority").due_date, None);
+        assert_eq!(task(&result, "urgent").due_date, None);
+    }
+
+    #[test]
+    fn maps_projects_and_keeps_section_items_in_their_project() {
+        let home = Project::new("Home".to_string());
+        let result = parse_todoist(EXPORT, std::slice::from_ref(&home), &[]).unwrap();
+
+        // "Home" already exists and "Old" is deleted, so only "Work" is new
+        let names: Vec<&str> = result.projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Work"]);
+        assert_eq!(task(&result, "urgent").project_id, Some(result.projects[0].id.clone()));
+        assert_eq!(task(&result, "high").project_id, Some(home.id.clone()));
+        assert_eq!(task(&result, "medium").project_id, None);
+    }
+
+    #[test]
+    fn maps_labels_by_name_or_id_reusing_existing_tags() {
+        let phone = Tag::new("Phone".to_string());
+        let result = parse_todoist(EXPORT, &[], std::slice::from_ref(&phone)).unwrap();
+
+        let names: Vec<&str> = result.tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["errands"]);
+        assert_eq!(task(&result, "high").tags, vec![phone.id.clone(), result.tags[0].id.clone()]);
+    }
+
+    #[test]
+    fn completes_checked_items_and_skips_blank_or_deleted_ones() {
+        let result = parse_todoist(EXPORT, &[], &[]).unwrap();
+        assert_eq!(result.tasks.len(), 5);
+        assert_eq!(result.skipped, 1);
+        assert!(result.tasks.iter().all(|t| t.title != "gone"));
+        let done = task(&result, "no priority");
+        assert_eq!(done.status, TaskStatus::Completed);
+        assert!(done.completed_at.is_some());
+    }
+
+    #[test]
+    fn rejects_malformed_exports() {
+        assert!(parse_todoist("", &[], &[]).is_err());
+        assert!(parse_todoist("{\"items\": [", &[], &[]).is_err());
+        assert!(parse_todoist("[1, 2]", &[], &[]).is_err());
+        assert!(parse_todoist("{\"items\": [{\"content\": 5}]}", &[], &[]).is_err());
+
+        let empty = parse_todoist("{}", &[], &[]).unwrap();
+        assert!(empty.tasks.is_empty() && empty.projects.is_empty());
+    }
+}
@@ -2,8 +2,18 @@ mod task_service;
 mod filter_service;
 mod github_service;
 mod toggl_service;
+mod quick_add_service;
+mod export_service;
+mod import_service;
+mod clipboard_service;
+mod http_retry;
 
 pub use task_service::*;
 pub use filter_service::*;
 pub use github_service::*;
 pub use toggl_service::*;
+pub use quick_add_service::*;
+pub use export_service::*;
+pub use import_service::*;
+pub use clipboard_service::*;
+pub use http_retry::*;
@@ -0,0 +1,195 @@
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+use crate::models::TaskPriority;
+
+/// A task parsed from a quick-add line, e.g. "Call Bob #work !!"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickAdd {
+    pub title: String,
+    pub tags: Vec<String>,
+    pub priority: TaskPriority,
+}
+
+/// Parse a quick-add line: `#tag` adds a tag, `!`/`!!`/`!!!` or `!low`/`!medium`/`!high`
+/// set the priority, and the remaining words form the title
+pub fn parse_quick_add(line: &str) -> QuickAdd {
+    let mut title_words = Vec::new();
+    let mut tags = Vec::new();
+    let mut priority = TaskPriority::None;
+
+    for word in line.split_whitespace() {
+        if let Some(tag) = word.strip_prefix('#').filter(|t| !t.is_empty()) {
+            tags.push(tag.to_string());
+        } else if let Some(p) = parse_priority_token(word) {
+            priority = p;
+        } else {
+            title_words.push(word);
+        }
+    }
+
+    QuickAdd {
+        title: title_words.join(" "),
+        tags,
+        priority,
+    }
+}
+
+fn parse_priority_token(word: &str) -> Option<TaskPriority> {
+    match word {
+        "!" => Some(TaskPriority::Low),
+        "!!" => Some(TaskPriority::Medium),
+        "!!!" => Some(TaskPriority::High),
+        _ => match word.strip_prefix('!')? {
+            "low" => Some(TaskPriority::Low),
+            "medium" | "med" => Some(TaskPriority::Medium),
+            "high" => Some(TaskPriority::High),
+            _ => None,
+        },
+    }
+}
+
+/// Longest duration `parse_estimate` accepts: 10,000 hours
+pub const MAX_DURATION_MINUTES: i64 = 10_000 * 60;
+
+/// Parse a time estimate into minutes: `90`, `45m`, `2h`, `1.5h`, `1h30m` or an
+/// ISO 8601 duration like `PT1H30M`. None for anything unparsable, zero or
+/// longer than `MAX_DURATION_MINUTES`
+pub fn parse_estimate(input: &str) -> Option<i64> {
+    let input: String = input.to_lowercase().split_whitespace().collect();
+    let input = input.strip_prefix("pt").unwrap_or(&input).to_string();
+    let valid = |total: i64| (0 < total && total <= MAX_DURATION_MINUTES).then_some(total);
+    if let Ok(minutes) = input.parse::<i64>() {
+        return valid(minutes);
+    }
+    let (hours, rest) = match input.split_once('h') {
+        Some((hours, rest)) => (hours.parse::<f64>().ok()?, rest),
+        None => (0.0, input.as_str()),
+    };
+    // Rejects NaN, infinite and out-of-range hours before the cast, which saturates
+    let hour_minutes = hours * 60.0;
+    if !(0.0..=MAX_DURATION_MINUTES as f64).contains(&hour_minutes) {
+        return None;
+    }
+    let minutes = match rest.strip_suffix('m').unwrap_or(rest) {
+        "" => 0,
+        m => m.parse::<i64>().ok().filter(|m| *m >= 0)?,
+    };
+    valid((hour_minutes.round() as i64).checked_add(minutes)?)
+}
+
+/// Format an estimate the way `parse_estimate` reads it: `45m`, `2h`, `1h30m`
+pub fn format_estimate(minutes: i64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h{}m", h, m),
+    }
+}
+
+/// Parse a due date relative to `today`: `YYYY-MM-DD`, `today`, `tomorrow`, a weekday
+/// name (its next occurrence) or an offset like `+3d`/`+2w`. None when the result
+/// would fall outside the supported date range.
+pub fn parse_due_date(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let input = input.trim().to_lowercase();
+    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
+        return Some(date);
+    }
+    match input.as_str() {
+        "today" | "tod" => return Some(today),
+        "tomorrow" | "tom" => return today.checked_add_days(Days::new(1)),
+        _ => {}
+    }
+    if let Some(weekday) = parse_weekday(&input) {
+        let ahead = (weekday.num_days_from_monday() + 7
+            - today.weekday().num_days_from_monday()
+            - 1)
+            % 7
+            + 1;
+        return today.checked_add_days(Days::new(ahead.into()));
+    }
+    let offset = input.strip_prefix('+')?;
+    if let Some(days) = offset.strip_suffix('d') {
+        return today.checked_add_days(Days::new(days.parse().ok()?));
+    }
+    let weeks: u64 = offset.strip_suffix('w')?.parse().ok()?;
+    today.checked_add_days(Days::new(weeks.checked_mul(7)?))
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    match word {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thur" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_estimate_reads_minutes_and_hours() {
+        assert_eq!(parse_estimate("90"), Some(90));
+        assert_eq!(parse_estimate("45m"), Some(45));
+        assert_eq!(parse_estimate("1.5h"), Some(90));
+        assert_eq!(parse_estimate("1h30m"), Some(90));
+    }
+
+    #[test]
+    fn parse_estimate_reads_iso_durations() {
+        assert_eq!(parse_estimate("PT1H30M"), Some(90));
+        assert_eq!(parse_estimate("PT2H"), Some(120));
+        assert_eq!(parse_estimate("pt45m"), Some(45));
+        assert_eq!(parse_estimate("PT"), None);
+    }
+
+    #[test]
+    fn parse_estimate_rejects_zero_and_garbage() {
+        assert_eq!(parse_estimate("0"), None);
+        assert_eq!(parse_estimate(""), None);
+        assert_eq!(parse_estimate("soon"), None);
+        assert_eq!(parse_estimate("-5"), None);
+        assert_eq!(parse_estimate("1h-5m"), None);
+    }
+
+    #[test]
+    fn parse_estimate_rejects_overflowing_input() {
+        assert_eq!(parse_estimate("1e300h5m"), None);
+        assert_eq!(parse_estimate("infh1m"), None);
+        assert_eq!(parse_estimate("nanh"), None);
+        assert_eq!(parse_estimate("-1h"), None);
+        assert_eq!(parse_estimate("1h9223372036854775807m"), None);
+        assert_eq!(parse_estimate("9223372036854775807"), None);
+    }
+
+    #[test]
+    fn parse_estimate_caps_at_max_duration() {
+        assert_eq!(parse_estimate("10000h"), Some(MAX_DURATION_MINUTES));
+        assert_eq!(parse_estimate("10000h1m"), None);
+    }
+
+    #[test]
+    fn parse_due_date_reads_offsets() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 13).unwrap();
+        assert_eq!(parse_due_date("tomorrow", today), NaiveDate::from_ymd_opt(2024, 3, 14));
+        assert_eq!(parse_due_date("+3d", today), NaiveDate::from_ymd_opt(2024, 3, 16));
+        assert_eq!(parse_due_date("+2w", today), NaiveDate::from_ymd_opt(2024, 3, 27));
+        assert_eq!(parse_due_date("fri", today), NaiveDate::from_ymd_opt(2024, 3, 15));
+    }
+
+    #[test]
+    fn parse_due_date_rejects_offsets_past_the_date_range() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 13).unwrap();
+        assert_eq!(parse_due_date("+100000000d", today), None);
+        assert_eq!(parse_due_date("+18446744073709551615d", today), None);
+        assert_eq!(parse_due_date("+100000000w", today), None);
+        assert_eq!(parse_due_date("+3074457345618258603w", today), None);
+        assert_eq!(parse_due_date("tomorrow", NaiveDate::MAX), None);
+        assert_eq!(parse_due_date("mon", NaiveDate::MAX), None);
+    }
+}
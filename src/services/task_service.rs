@@ -1,5 +1,5 @@
 use chrono::Utc;
-use crate::db::Repository;
+use crate::db::{OrderedTable, Repository};
 use crate::error::Result;
 use crate::models::{Project, Tag, Task, TaskStatus};
 
@@ -15,7 +15,7 @@ impl<'a> TaskService<'a> {
     /// Create a new task
     pub fn create_task(&self, title: String) -> Result<Task> {
         let mut task = Task::new(title);
-        task.order_index = self.repo.get_next_order_index("tasks")?;
+        task.order_index = self.repo.get_next_order_index(OrderedTable::Tasks)?;
         self.repo.insert_task(&task)?;
         Ok(task)
     }
@@ -29,13 +29,8 @@ impl<'a> TaskService<'a> {
 
     /// Set task status
     pub fn set_status(&self, task: &mut Task, status: TaskStatus) -> Result<()> {
-        task.status = status;
+        task.set_status(status);
         task.updated_at = Utc::now();
-        if status == TaskStatus::Completed {
-            task.completed_at = Some(Utc::now());
-        } else {
-            task.completed_at = None;
-        }
         self.repo.update_task(task)?;
         Ok(())
     }
@@ -43,10 +38,12 @@ impl<'a> TaskService<'a> {
     /// Toggle task completion
     pub fn toggle_completed(&self, task: &mut Task) -> Result<()> {
         if task.status == TaskStatus::Completed {
-            self.set_status(task, TaskStatus::Inbox)
+            task.reopen();
         } else {
-            self.set_status(task, TaskStatus::Completed)
+            task.complete();
         }
+        task.updated_at = Utc::now();
+        self.repo.update_task(task)
     }
 
     /// Soft delete a task
@@ -69,7 +66,7 @@ impl<'a> TaskService<'a> {
     /// Create a new project
     pub fn create_project(&self, name: String) -> Result<Project> {
         let mut project = Project::new(name);
-        project.order_index = self.repo.get_next_order_index("projects")?;
+        project.order_index = self.repo.get_next_order_index(OrderedTable::Projects)?;
         self.repo.insert_project(&project)?;
         Ok(project)
     }
@@ -100,7 +97,7 @@ impl<'a> TaskService<'a> {
 
     /// Create a new tag
     pub fn create_tag(&self, name: String) -> Result<Tag> {
-        let tag = Tag::new(name);
+        let tag = Tag::new(Tag::clean_name(&name).to_string());
         self.repo.insert_tag(&tag)?;
         Ok(tag)
     }
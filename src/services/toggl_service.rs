@@ -2,7 +2,9 @@ use base64::Engine;
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use crate::error::{AppError, Result};
+use crate::config::DEFAULT_HTTP_RETRIES;
+use crate::error::{http_error_message, AppError, Result};
+use crate::services::{http_client, send_with_retry};
 
 const TOGGL_API_BASE: &str = "https://api.track.toggl.com/api/v9";
 
@@ -42,6 +44,25 @@ impl TogglTimeEntry {
         }
     }
 
+    /// Get the seconds of this entry that fall on the given date.
+    /// Entries crossing midnight are split across the days they cover.
+    pub fn seconds_on_date(&self, date: NaiveDate) -> i64 {
+        let Ok(start) = DateTime::parse_from_rfc3339(&self.start) else {
+            return 0;
+        };
+        let Some(day_start) = date.and_hms_opt(0, 0, 0) else {
+            return 0;
+        };
+        let day_end = day_start + chrono::Duration::days(1);
+
+        // Compare in the entry's own offset, matching start_date()
+        let start = start.naive_local();
+        let end = start + chrono::Duration::seconds(self.duration_secs());
+
+        let overlap = end.min(day_end) - start.max(day_start);
+        overlap.num_seconds().max(0)
+    }
+
     /// Format duration as HH:MM:SS
     pub fn format_duration(&self) -> String {
         let secs = self.duration_secs();
@@ -81,16 +102,24 @@ impl TogglData {
     pub fn duration_for_date(&self, date: NaiveDate) -> i64 {
         self.entries
             .iter()
-            .filter(|e| e.start_date() == Some(date))
-            .map(|e| e.duration_secs())
+            .map(|e| e.seconds_on_date(date))
+            .sum()
+    }
+
+    /// Get total duration from `from` through `to` (inclusive)
+    pub fn duration_between(&self, from: NaiveDate, to: NaiveDate) -> i64 {
+        from.iter_days()
+            .take_while(|date| *date <= to)
+            .map(|date| self.duration_for_date(date))
             .sum()
     }
 
-    /// Get duration by project
-    pub fn duration_by_project(&self) -> Vec<(String, i64)> {
+    /// Get duration by project of the entries started on or after `since`
+    pub fn duration_by_project(&self, since: NaiveDate) -> Vec<(String, i64)> {
         let mut by_project: HashMap<String, i64> = HashMap::new();
 
-        for entry in &self.entries {
+        let entries = self.entries.iter().filter(|e| e.start_date().is_some_and(|d| d >= since));
+        for entry in entries {
             let project_name = entry
                 .project_name
                 .clone()
@@ -128,16 +157,24 @@ impl TogglData {
 pub struct TogglService {
     client: reqwest::Client,
     token: String,
+    retries: u32,
 }
 
 impl TogglService {
     pub fn new(token: String) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: http_client(),
             token,
+            retries: DEFAULT_HTTP_RETRIES,
         }
     }
 
+    /// Retry transient failures (5xx gateway errors, timeouts) this many times
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
     /// Fetch time entries for a date range
     pub async fn fetch_time_entries(
         &self,
@@ -198,13 +235,13 @@ impl TogglService {
         let auth = base64::engine::general_purpose::STANDARD
             .encode(format!("{}:api_token", self.token));
 
-        let response = self
-            .client
-            .get(url)
-            .header("Authorization", format!("Basic {}", auth))
-            .header("Content-Type", "application/json")
-            .send()
-            .await?;
+        let request = || {
+            self.client
+                .get(url)
+                .header("Authorization", format!("Basic {}", auth))
+                .header("Content-Type", "application/json")
+        };
+        let response = send_with_retry(request, self.retries).await?;
 
         let status = response.status();
         if status.as_u16() == 402 {
@@ -214,7 +251,8 @@ impl TogglService {
             return Err(AppError::Toggl("Invalid token. Check Settings.".to_string()));
         }
         if !status.is_success() {
-            return Err(AppError::Toggl(format!("HTTP error: {}", status)));
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::Toggl(http_error_message(status, &body)));
         }
 
         let text = response.text().await?;
@@ -245,3 +283,44 @@ pub fn format_hours(seconds: i64) -> String {
     let hours = seconds as f64 / 3600.0;
     format!("{:.1}h", hours)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(start: &str, duration: i64) -> TogglTimeEntry {
+        TogglTimeEntry {
+            id: 1,
+            description: None,
+            duration,
+            start: start.to_string(),
+            stop: None,
+            project_id: None,
+            project_name: None,
+        }
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn seconds_on_date_splits_an_entry_crossing_midnight() {
+        // 23:30 to 01:15 the next day
+        let late = entry("2024-03-10T23:30:00+01:00", 105 * 60);
+        assert_eq!(late.seconds_on_date(date(2024, 3, 9)), 0);
+        assert_eq!(late.seconds_on_date(date(2024, 3, 10)), 30 * 60);
+        assert_eq!(late.seconds_on_date(date(2024, 3, 11)), 75 * 60);
+        assert_eq!(late.seconds_on_date(date(2024, 3, 12)), 0);
+    }
+
+    #[test]
+    fn seconds_on_date_counts_a_same_day_entry_once() {
+        let data = TogglData {
+            entries: vec![entry("2024-03-10T09:00:00Z", 3600)],
+            ..TogglData::default()
+        };
+        assert_eq!(data.duration_for_date(date(2024, 3, 10)), 3600);
+        assert_eq!(data.duration_for_date(date(2024, 3, 11)), 0);
+    }
+}
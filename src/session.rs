@@ -0,0 +1,49 @@
+use crate::config::Config;
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// UI state carried across restarts, kept apart from the user's config
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    /// Key of the view that was open, e.g. "inbox" or "project"
+    pub view: Option<String>,
+    /// Project shown by the project view
+    pub project_id: Option<String>,
+    /// Selected task id per view key
+    pub selected_tasks: HashMap<String, String>,
+    /// When the last weekly review was completed
+    pub last_review: Option<DateTime<Utc>>,
+    /// Sidebar areas whose projects are hidden
+    pub collapsed_areas: Vec<String>,
+}
+
+impl Session {
+    /// Returns the session file path (~/.local/share/phitodo-tui/session.json)
+    pub fn path() -> Result<PathBuf> {
+        Ok(Config::data_dir()?.join("session.json"))
+    }
+
+    /// Load the last session; a missing or unreadable file gives an empty session
+    pub fn load() -> Self {
+        Self::path()
+            .ok()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save via a temporary file and rename, so an interrupted write never leaves
+    /// a truncated session behind
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+}
@@ -1,12 +1,15 @@
+use std::collections::HashMap;
+
 use ratatui::{
     layout::Rect,
     style::Style,
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 
 use crate::services::GitHubIssue;
+use crate::ui::components::truncate;
 use crate::ui::theme::Theme;
 
 pub struct GitHubColumnState {
@@ -14,6 +17,10 @@ pub struct GitHubColumnState {
     pub list_state: ListState,
     pub title: String,
     pub focused: bool,
+    /// Other columns each item also appears in, keyed by item URL
+    pub also_in: HashMap<String, Vec<&'static str>>,
+    /// Fetch error for this column only (other columns may still have data)
+    pub error: Option<String>,
 }
 
 impl GitHubColumnState {
@@ -23,6 +30,8 @@ impl GitHubColumnState {
             list_state: ListState::default(),
             title: title.into(),
             focused: false,
+            also_in: HashMap::new(),
+            error: None,
         }
     }
 
@@ -87,10 +96,21 @@ pub fn render_github_column(frame: &mut Frame, area: Rect, state: &mut GitHubCol
         .borders(Borders::ALL)
         .border_style(Theme::border_style(state.focused));
 
+    if let Some(ref error) = state.error {
+        let error_msg = Paragraph::new(Line::from(vec![
+            Span::styled("Error: ", Style::default().fg(Theme::ERROR)),
+            Span::raw(error.clone()),
+        ]))
+        .wrap(Wrap { trim: true })
+        .block(block);
+        frame.render_widget(error_msg, area);
+        return;
+    }
+
     let items: Vec<ListItem> = state
         .items
         .iter()
-        .map(|issue| create_github_item(issue))
+        .map(|issue| create_github_item(issue, state.also_in.get(&issue.html_url)))
         .collect();
 
     let list = List::new(items)
@@ -101,18 +121,22 @@ pub fn render_github_column(frame: &mut Frame, area: Rect, state: &mut GitHubCol
     frame.render_stateful_widget(list, area, &mut state.list_state);
 }
 
-fn create_github_item(issue: &GitHubIssue) -> ListItem<'static> {
+fn create_github_item(issue: &GitHubIssue, also_in: Option<&Vec<&'static str>>) -> ListItem<'static> {
     let repo_name = issue.repo_name();
     let short_repo = repo_name.split('/').last().unwrap_or(&repo_name).to_string();
 
-    let icon = if issue.is_pr() { "" } else { "" };
+    let icon = if issue.is_pr() {
+        Theme::icon("", "[PR]")
+    } else {
+        Theme::icon("", "[ISS]")
+    };
     let icon_color = if issue.is_pr() {
         Theme::SUCCESS
     } else {
         Theme::INFO
     };
 
-    ListItem::new(Line::from(vec![
+    let mut spans = vec![
         Span::styled(icon.to_string(), Style::default().fg(icon_color)),
         Span::raw(" "),
         Span::styled(
@@ -123,13 +147,21 @@ fn create_github_item(issue: &GitHubIssue) -> ListItem<'static> {
         Span::styled(truncate(&issue.title, 40), Style::default().fg(Theme::FG)),
         Span::raw(" "),
         Span::styled(short_repo, Style::default().fg(Theme::FG_MUTED)),
-    ]))
-}
+    ];
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len - 3])
+    if let Some(age) = issue.age() {
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(age, Style::default().fg(Theme::FG_DIM)));
     }
+
+    // Mark items that were also returned for other columns
+    if let Some(labels) = also_in {
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(
+            format!("+{}", labels.join(",")),
+            Style::default().fg(Theme::ACCENT),
+        ));
+    }
+
+    ListItem::new(Line::from(spans))
 }
@@ -8,7 +8,8 @@ use ratatui::{
 
 use crate::ui::theme::Theme;
 
-pub fn render_help_overlay(frame: &mut Frame, area: Rect) {
+/// Render the help overlay, clamping `scroll` to the available content
+pub fn render_help_overlay(frame: &mut Frame, area: Rect, scroll: &mut u16) {
     // Center the help panel
     let width = area.width.min(70);
     let height = area.height.min(30);
@@ -37,7 +38,7 @@ pub fn render_help_overlay(frame: &mut Frame, area: Rect) {
 
     // Header
     let header = Paragraph::new(Line::from(Span::styled(
-        "Press ? to close this help",
+        "j/k or PgUp/PgDn to scroll, ? or Esc to close",
         Theme::muted_style(),
     )));
     frame.render_widget(header, chunks[0]);
@@ -48,20 +49,54 @@ pub fn render_help_overlay(frame: &mut Frame, area: Rect) {
             ("Alt+1-9", "Switch views (Inbox, Today, etc.)"),
             ("j/k or ↓/↑", "Move selection down/up"),
             ("g/G", "Go to first/last item"),
+            ("]/[", "Jump to next/previous overdue task"),
+            ("C-n/C-p", "Next/previous project (in a project)"),
+            ("A", "Agenda view (overdue, due today, next actions)"),
+            ("K", "This Week view (due in the next 7 days, by day)"),
+            ("D", "Deferred view (start date in the future)"),
+            ("#", "Stats view (open tasks by status, kind, priority)"),
+            ("@", "Scheduled view (status=scheduled)"),
             ("Tab", "Cycle focus (sidebar → list → detail)"),
             ("Enter", "Open selected item"),
         ]),
         ("Task Actions", vec![
             ("Space", "Toggle task completion"),
+            ("x", "Complete task and move to the next"),
             ("n", "New task"),
             ("N", "New project"),
+            ("P", "Paste tasks (one per line, #tag !priority)"),
             ("e", "Edit selected"),
-            ("d", "Delete (with confirmation)"),
+            ("f", "Flag/unflag task (F: Flagged view)"),
+            ("T/B", "Move task to top/bottom of a manually ordered list"),
+            ("d", "Delete (with confirmation; project when in sidebar)"),
+            ("m", "Set metadata field (key=value)"),
+            ("y", "Copy task URL (or id) to the clipboard"),
             ("1-4", "Set priority (None/Low/Medium/High)"),
             ("i/a/s", "Move to Inbox/Active/Scheduled"),
+            ("b", "Back to Inbox, unscheduled (re-triage)"),
+            ("u", "Clear due/start dates (move to Anytime)"),
+            ("c", "Cycle status (Inbox → Active → Scheduled → Completed → Cancelled)"),
         ]),
         ("Other", vec![
-            ("/", "Search/filter"),
+            ("/", "Filter the current list (Esc clears)"),
+            ("!", "Show one priority: high/medium/low/none/all"),
+            ("p", "Toggle notes preview in lists"),
+            ("z", "Toggle focus mode (list only)"),
+            ("Z", "Toggle full-width task details"),
+            ("H", "Hide/show the detail metadata (dates, URL)"),
+            ("C", "Expand/collapse the task's checklist"),
+            ("J", "Log time spent on the task (30m, 1h30m)"),
+            ("O", "Append a line to the task's notes"),
+            ("X", "Mark/unmark task for a batch action (Esc clears)"),
+            ("L", "Add a tag to the marked tasks (or the selected one)"),
+            ("V", "Full-screen preview of the selected task"),
+            ("I", "Triage the inbox one task at a time"),
+            ("w/W", "Weekly review (from Review): next step/stop"),
+            ("v", "Group Completed by project"),
+            ("R", "Limit Completed to a period (week, month, dates)"),
+            ("S", "Sort sidebar projects (manual/name/open tasks)"),
+            ("M", "Set the project's area (Enter on an area collapses it)"),
+            ("t", "Pin/unpin the project at the top of the sidebar"),
             ("r", "Refresh data"),
             ("?", "Show/hide help"),
             ("q", "Quit"),
@@ -88,8 +123,11 @@ pub fn render_help_overlay(frame: &mut Frame, area: Rect) {
         }
     }
 
+    let max_scroll = rows.len().saturating_sub(chunks[1].height as usize) as u16;
+    *scroll = (*scroll).min(max_scroll);
+
     let table = Table::new(
-        rows,
+        rows.into_iter().skip(*scroll as usize),
         [Constraint::Length(20), Constraint::Min(30)],
     )
     .style(Style::default().fg(Theme::FG))
@@ -41,6 +41,11 @@ impl InputState {
         self.cursor += 1;
     }
 
+    pub fn insert_str(&mut self, s: &str) {
+        self.value.insert_str(self.cursor, s);
+        self.cursor += s.len();
+    }
+
     pub fn backspace(&mut self) {
         if self.cursor > 0 {
             self.cursor -= 1;
@@ -138,9 +143,10 @@ pub fn render_input_modal(frame: &mut Frame, area: Rect, state: &InputState, tit
             ""
         };
 
-        let mut spans = vec![Span::raw(before.to_string())];
+        // Pasted multi-line values are shown with line markers
+        let mut spans = vec![Span::raw(before.replace('\n', "↵"))];
 
-        if let Some(c) = cursor_char {
+        if let Some(c) = cursor_char.map(|c| if c == '\n' { '↵' } else { c }) {
             spans.push(Span::styled(
                 c.to_string(),
                 Style::default()
@@ -155,7 +161,7 @@ pub fn render_input_modal(frame: &mut Frame, area: Rect, state: &InputState, tit
             ));
         }
 
-        spans.push(Span::raw(after.to_string()));
+        spans.push(Span::raw(after.replace('\n', "↵")));
         spans
     };
 
@@ -171,7 +177,7 @@ pub fn render_search_bar(frame: &mut Frame, area: Rect, state: &InputState, focu
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let icon = "";
+    let icon = Theme::icon("", "/");
     let display = if state.value.is_empty() {
         vec![
             Span::styled(icon, Style::default().fg(Theme::FG_DIM)),
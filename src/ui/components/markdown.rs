@@ -0,0 +1,283 @@
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::ui::theme::Theme;
+
+/// Render Markdown text as styled lines: headings, lists, quotes, code, emphasis
+/// and links get distinct styles, everything else is shown as plain text. Links
+/// keep their text, followed by the URL when it differs.
+pub fn markdown_lines(text: &str, base: Style) -> Vec<Line<'static>> {
+    let mut renderer = MarkdownRenderer::new(base);
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    for event in Parser::new_ext(text, options) {
+        renderer.handle(event);
+    }
+    renderer.flush();
+    renderer.lines
+}
+
+struct MarkdownRenderer {
+    base: Style,
+    lines: Vec<Line<'static>>,
+    current: Vec<Span<'static>>,
+    styles: Vec<Style>,
+    /// Open lists with the next number of ordered ones
+    lists: Vec<Option<u64>>,
+    quote_depth: usize,
+    in_code_block: bool,
+    /// Open links and images: the destination and the text shown so far
+    links: Vec<(String, String)>,
+}
+
+impl MarkdownRenderer {
+    fn new(base: Style) -> Self {
+        Self {
+            base,
+            lines: Vec::new(),
+            current: Vec::new(),
+            styles: Vec::new(),
+            lists: Vec::new(),
+            quote_depth: 0,
+            in_code_block: false,
+            links: Vec::new(),
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.styles.last().copied().unwrap_or(self.base)
+    }
+
+    fn push_style(&mut self, style: Style) {
+        self.styles.push(self.style().patch(style));
+    }
+
+    fn push_text(&mut self, text: impl Into<String>, style: Style) {
+        if self.current.is_empty() && self.quote_depth > 0 {
+            let bar = Theme::icon("│ ", "| ").repeat(self.quote_depth);
+            self.current.push(Span::styled(bar, Theme::muted_style()));
+        }
+        let text = text.into();
+        if let Some((_, shown)) = self.links.last_mut() {
+            shown.push_str(&text);
+        }
+        self.current.push(Span::styled(text, style));
+    }
+
+    /// Close a link or image: show the URL if the text did not already
+    fn end_link(&mut self) {
+        let style = self.style();
+        self.styles.pop();
+        let Some((url, shown)) = self.links.pop() else {
+            return;
+        };
+        if url.is_empty() || shown == url || shown == url.trim_start_matches("mailto:") {
+            return;
+        }
+        if shown.is_empty() {
+            self.push_text(url, style);
+        } else {
+            self.push_text(format!(" ({})", url), Theme::muted_style());
+        }
+    }
+
+    fn flush(&mut self) {
+        if !self.current.is_empty() {
+            self.lines.push(Line::from(std::mem::take(&mut self.current)));
+        }
+    }
+
+    /// Separate top-level blocks with a blank line
+    fn block_gap(&mut self) {
+        self.flush();
+        if self.lists.is_empty() && self.lines.last().is_some_and(|l| l.width() > 0) {
+            self.lines.push(Line::default());
+        }
+    }
+
+    fn handle(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) if self.in_code_block => {
+                let style = Style::default().fg(Theme::ACCENT);
+                for line in text.lines() {
+                    self.push_text(format!("  {}", line), style);
+                    self.flush();
+                }
+            }
+            Event::Text(text) => self.push_text(text.to_string(), self.style()),
+            Event::Code(code) => self.push_text(code.to_string(), Style::default().fg(Theme::ACCENT)),
+            Event::Html(html) | Event::InlineHtml(html) => {
+                self.push_text(html.trim_end().to_string(), Theme::muted_style())
+            }
+            Event::SoftBreak => self.push_text(" ", self.style()),
+            Event::HardBreak => self.flush(),
+            Event::Rule => {
+                self.block_gap();
+                self.push_text(Theme::icon("────────", "--------"), Theme::muted_style());
+                self.flush();
+            }
+            Event::TaskListMarker(checked) => {
+                self.push_text(if checked { "[x] " } else { "[ ] " }, self.style())
+            }
+            Event::FootnoteReference(label) => {
+                self.push_text(format!("[{}]", label), Theme::muted_style())
+            }
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph if self.lists.is_empty() => self.block_gap(),
+            Tag::Heading { .. } => {
+                self.block_gap();
+                self.push_style(
+                    Style::default()
+                        .fg(Theme::PRIMARY)
+                        .add_modifier(Modifier::BOLD),
+                );
+            }
+            Tag::BlockQuote => {
+                self.block_gap();
+                self.quote_depth += 1;
+                self.push_style(Style::default().add_modifier(Modifier::ITALIC));
+            }
+            Tag::CodeBlock(_) => {
+                self.block_gap();
+                self.in_code_block = true;
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.block_gap();
+                } else {
+                    self.flush();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{}{}. ", indent, *n - 1)
+                    }
+                    _ => format!("{}{} ", indent, Theme::icon("•", "-")),
+                };
+                self.push_text(marker, Style::default().fg(Theme::PRIMARY));
+            }
+            Tag::Emphasis => self.push_style(Style::default().add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self.push_style(Style::default().add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => {
+                self.push_style(Style::default().add_modifier(Modifier::CROSSED_OUT))
+            }
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.links.push((dest_url.to_string(), String::new()));
+                self.push_style(
+                    Style::default()
+                        .fg(Theme::INFO)
+                        .add_modifier(Modifier::UNDERLINED),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::Item | TagEnd::TableRow => self.flush(),
+            TagEnd::Heading(_) => {
+                self.styles.pop();
+                self.flush();
+            }
+            TagEnd::BlockQuote => {
+                self.flush();
+                self.styles.pop();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+            }
+            TagEnd::CodeBlock => {
+                self.flush();
+                self.in_code_block = false;
+            }
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+            }
+            TagEnd::TableCell => self.push_text("  ", self.style()),
+            TagEnd::Link | TagEnd::Image => self.end_link(),
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => {
+                self.styles.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(text: &str) -> Vec<String> {
+        markdown_lines(text, Style::default())
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn renders_blocks_with_gaps() {
+        let bullet = Theme::icon("•", "-");
+        assert_eq!(
+            texts("# Title\n\nSome *text* here\n\n- one\n- two\n\n1. first\n2. second"),
+            vec![
+                "Title".to_string(),
+                String::new(),
+                "Some text here".to_string(),
+                String::new(),
+                format!("{} one", bullet),
+                format!("{} two", bullet),
+                String::new(),
+                "1. first".to_string(),
+                "2. second".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn styles_headings_and_emphasis() {
+        let lines = markdown_lines("# Title\n\n**bold** and `code`", Style::default());
+        assert!(lines[0].spans[0].style.add_modifier.contains(Modifier::BOLD));
+        let body = &lines[2].spans;
+        assert_eq!(body[0].content, "bold");
+        assert!(body[0].style.add_modifier.contains(Modifier::BOLD));
+        assert!(!body[1].style.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(body[2].style.fg, Some(Theme::ACCENT));
+    }
+
+    #[test]
+    fn links_keep_their_text_and_url() {
+        assert_eq!(
+            texts("See [the docs](https://example.com/docs) first"),
+            vec!["See the docs (https://example.com/docs) first"]
+        );
+        assert_eq!(texts("<https://example.com>"), vec!["https://example.com"]);
+        assert_eq!(texts("[](https://example.com)"), vec!["https://example.com"]);
+    }
+
+    #[test]
+    fn task_list_markers_and_code_blocks() {
+        let bullet = Theme::icon("•", "-");
+        assert_eq!(
+            texts("- [x] done\n- [ ] todo\n\n```\nlet a = 1;\n```"),
+            vec![
+                format!("{} [x] done", bullet),
+                format!("{} [ ] todo", bullet),
+                String::new(),
+                "  let a = 1;".to_string(),
+            ]
+        );
+    }
+}
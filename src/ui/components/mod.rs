@@ -8,6 +8,10 @@ mod input;
 mod modal;
 mod help;
 mod status_bar;
+mod markdown;
+mod triage;
+mod weekly_review;
+mod text;
 
 pub use sidebar::*;
 pub use task_list::*;
@@ -19,3 +23,7 @@ pub use input::*;
 pub use modal::*;
 pub use help::*;
 pub use status_bar::*;
+pub use markdown::*;
+pub use triage::*;
+pub use weekly_review::*;
+pub use text::*;
@@ -15,19 +15,43 @@ pub struct ConfirmModal {
     pub confirm_text: String,
     pub cancel_text: String,
     pub selected: bool, // true = confirm, false = cancel
+    /// Optional third button, shown after confirm
+    pub alternative_text: Option<String>,
+    pub alternative_selected: bool,
+    /// Word that must be typed to confirm, replacing the buttons
+    pub confirm_word: Option<String>,
+    pub typed: String,
 }
 
 impl ConfirmModal {
+    /// Confirmation for a benign action: Confirm is preselected. Destructive
+    /// actions use their own constructors, which preselect Cancel.
     pub fn new(title: impl Into<String>, message: impl Into<String>) -> Self {
         Self {
             title: title.into(),
             message: message.into(),
             confirm_text: "Confirm".to_string(),
             cancel_text: "Cancel".to_string(),
-            selected: false,
+            selected: true,
+            alternative_text: None,
+            alternative_selected: false,
+            confirm_word: None,
+            typed: String::new(),
         }
     }
 
+    /// Require typing `word` (case-insensitive) and Enter to confirm
+    pub fn require_word(mut self, word: impl Into<String>) -> Self {
+        self.confirm_word = Some(word.into());
+        self
+    }
+
+    pub fn word_typed(&self) -> bool {
+        self.confirm_word
+            .as_ref()
+            .is_some_and(|word| self.typed.trim().eq_ignore_ascii_case(word))
+    }
+
     pub fn delete(item_name: impl Into<String>) -> Self {
         let name = item_name.into();
         Self {
@@ -36,11 +60,68 @@ impl ConfirmModal {
             confirm_text: "Delete".to_string(),
             cancel_text: "Cancel".to_string(),
             selected: false,
+            alternative_text: None,
+            alternative_selected: false,
+            confirm_word: None,
+            typed: String::new(),
+        }
+    }
+
+    pub fn quit() -> Self {
+        Self {
+            confirm_text: "Quit".to_string(),
+            ..Self::new("Quit", "Quit phitodo?")
         }
     }
 
+    pub fn vacuum(days: i64) -> Self {
+        Self {
+            title: "Vacuum Database".to_string(),
+            message: format!(
+                "Permanently remove items deleted more than {} days ago and compact the database? This cannot be undone.",
+                days
+            ),
+            confirm_text: "Vacuum".to_string(),
+            cancel_text: "Cancel".to_string(),
+            selected: false,
+            alternative_text: None,
+            alternative_selected: false,
+            confirm_word: None,
+            typed: String::new(),
+        }
+    }
+
+    /// Delete a project that still has open tasks: move them to the Inbox (confirm)
+    /// or delete them with the project (alternative)
+    pub fn delete_project(project_name: impl Into<String>, open_tasks: i64) -> Self {
+        let name = project_name.into();
+        let noun = if open_tasks == 1 { "task" } else { "tasks" };
+        Self {
+            title: "Delete Project".to_string(),
+            message: format!(
+                "\"{}\" has {} open {}. Move them to the Inbox or delete them too?",
+                name, open_tasks, noun
+            ),
+            confirm_text: "Move to Inbox".to_string(),
+            cancel_text: "Cancel".to_string(),
+            selected: false,
+            alternative_text: Some("Delete all".to_string()),
+            alternative_selected: false,
+            confirm_word: None,
+            typed: String::new(),
+        }
+    }
+
+    /// Cycle cancel → confirm → alternative (if any) → cancel
     pub fn toggle(&mut self) {
-        self.selected = !self.selected;
+        if self.alternative_selected {
+            self.alternative_selected = false;
+        } else if self.selected && self.alternative_text.is_some() {
+            self.selected = false;
+            self.alternative_selected = true;
+        } else {
+            self.selected = !self.selected;
+        }
     }
 }
 
@@ -76,6 +157,24 @@ pub fn render_confirm_modal(frame: &mut Frame, area: Rect, modal: &ConfirmModal)
         .style(Style::default().fg(Theme::FG));
     frame.render_widget(message, chunks[0]);
 
+    // Typed confirmation instead of buttons
+    if let Some(ref word) = modal.confirm_word {
+        let typed_style = if modal.word_typed() {
+            Style::default().fg(Theme::ERROR).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Theme::FG)
+        };
+        let prompt = Paragraph::new(vec![
+            Line::from(vec![
+                Span::styled(format!("Type \"{}\" to confirm: ", word), Theme::dimmed_style()),
+                Span::styled(format!("{}_", modal.typed), typed_style),
+            ]),
+            Line::from(Span::styled("Enter confirm, Esc cancel", Theme::muted_style())),
+        ]);
+        frame.render_widget(prompt, chunks[1]);
+        return;
+    }
+
     // Buttons
     let confirm_style = if modal.selected {
         Style::default()
@@ -86,7 +185,16 @@ pub fn render_confirm_modal(frame: &mut Frame, area: Rect, modal: &ConfirmModal)
         Style::default().fg(Theme::FG_DIM)
     };
 
-    let cancel_style = if !modal.selected {
+    let alternative_style = if modal.alternative_selected {
+        Style::default()
+            .fg(Theme::BG)
+            .bg(Theme::ERROR)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Theme::FG_DIM)
+    };
+
+    let cancel_style = if !modal.selected && !modal.alternative_selected {
         Style::default()
             .fg(Theme::BG)
             .bg(Theme::PRIMARY)
@@ -95,12 +203,17 @@ pub fn render_confirm_modal(frame: &mut Frame, area: Rect, modal: &ConfirmModal)
         Style::default().fg(Theme::FG_DIM)
     };
 
-    let buttons = Line::from(vec![
+    let mut buttons = vec![
         Span::raw("  "),
         Span::styled(format!(" {} ", modal.cancel_text), cancel_style),
         Span::raw("   "),
         Span::styled(format!(" {} ", modal.confirm_text), confirm_style),
-    ]);
+    ];
+    if let Some(ref alternative) = modal.alternative_text {
+        buttons.push(Span::raw("   "));
+        buttons.push(Span::styled(format!(" {} ", alternative), alternative_style));
+    }
+    let buttons = Line::from(buttons);
 
     let buttons_para = Paragraph::new(buttons);
     frame.render_widget(buttons_para, chunks[1]);
@@ -128,22 +241,29 @@ impl NotificationModal {
     }
 }
 
+/// Most message lines a notification grows to before the rest is cut off
+const MAX_NOTIFICATION_LINES: u16 = 8;
+
 pub fn render_notification(frame: &mut Frame, area: Rect, notification: &NotificationModal) {
-    // Bottom of screen
-    let width = area.width.min(60);
-    let height = 3;
+    let (border_color, icon) = if notification.is_error {
+        (Theme::ERROR, Theme::icon("", "!"))
+    } else {
+        (Theme::SUCCESS, Theme::icon("", "i"))
+    };
+
+    // As wide as the message (up to 60 columns) and as tall as it wraps to,
+    // anchored to the bottom of the screen
+    let text = format!("{} {}", icon, notification.message);
+    let longest = text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+    let width = (longest as u16 + 2).clamp(20, 60).min(area.width);
+    let lines = wrapped_height(&text, width.saturating_sub(2) as usize) as u16;
+    let height = (lines.min(MAX_NOTIFICATION_LINES) + 2).min(area.height.saturating_sub(1));
     let x = area.x + (area.width - width) / 2;
-    let y = area.y + area.height - height - 1;
+    let y = area.y + area.height.saturating_sub(height + 1);
     let notif_area = Rect::new(x, y, width, height);
 
     frame.render_widget(Clear, notif_area);
 
-    let (border_color, icon) = if notification.is_error {
-        (Theme::ERROR, "")
-    } else {
-        (Theme::SUCCESS, "")
-    };
-
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color))
@@ -152,10 +272,43 @@ pub fn render_notification(frame: &mut Frame, area: Rect, notification: &Notific
     let inner = block.inner(notif_area);
     frame.render_widget(block, notif_area);
 
-    let content = Paragraph::new(Line::from(vec![
+    let mut message_lines = notification.message.lines();
+    let mut content = vec![Line::from(vec![
         Span::styled(icon, Style::default().fg(border_color)),
         Span::raw(" "),
-        Span::styled(&notification.message, Style::default().fg(Theme::FG)),
-    ]));
+        Span::styled(message_lines.next().unwrap_or_default(), Style::default().fg(Theme::FG)),
+    ])];
+    content.extend(message_lines.map(|l| Line::styled(l, Style::default().fg(Theme::FG))));
+    let content = Paragraph::new(content).wrap(Wrap { trim: false });
     frame.render_widget(content, inner);
 }
+
+/// Rows `text` takes when word-wrapped to `width` columns
+fn wrapped_height(text: &str, width: usize) -> usize {
+    if width == 0 {
+        return 1;
+    }
+    text.lines()
+        .map(|line| {
+            let mut rows = 1;
+            let mut used = 0;
+            for word in line.split(' ') {
+                let len = word.chars().count();
+                if used > 0 && used + 1 + len > width {
+                    rows += 1;
+                    used = 0;
+                } else if used > 0 {
+                    used += 1;
+                }
+                used += len;
+                // Words longer than a row are broken across rows
+                while used > width {
+                    rows += 1;
+                    used -= width;
+                }
+            }
+            rows
+        })
+        .sum::<usize>()
+        .max(1)
+}
@@ -1,30 +1,161 @@
+use std::collections::{HashMap, HashSet};
+
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
+use crate::config::Config;
 use crate::models::Project;
 use crate::ui::theme::{SidebarItem, Theme};
 
 pub struct SidebarState {
     pub selected_item: SidebarItem,
     pub selected_project: Option<String>,
+    /// Area header selected in the projects section
+    pub selected_area: Option<String>,
+    /// Areas whose projects are hidden
+    pub collapsed_areas: HashSet<String>,
     pub projects: Vec<Project>,
     pub focused: bool,
     pub counts: SidebarCounts,
+    /// Index of the first visible project in the projects section
+    pub project_scroll: usize,
+    /// Display order of `projects`
+    pub project_sort: ProjectSort,
+    pub layout: SidebarLayout,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SidebarSection {
+    Main,
+    Projects,
+    Footer,
+}
+
+impl SidebarSection {
+    pub const ALL: [SidebarSection; 3] =
+        [SidebarSection::Main, SidebarSection::Projects, SidebarSection::Footer];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "main" => Some(SidebarSection::Main),
+            "projects" => Some(SidebarSection::Projects),
+            "footer" => Some(SidebarSection::Footer),
+            _ => None,
+        }
+    }
+}
+
+/// Order of the sidebar sections and the items of the two navigation sections
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SidebarLayout {
+    pub sections: Vec<SidebarSection>,
+    pub main: Vec<SidebarItem>,
+    pub footer: Vec<SidebarItem>,
+}
+
+impl SidebarLayout {
+    /// Layout from the `sidebar_*` config lists. Unknown names are skipped,
+    /// sections left out follow in the default order, and items left out of both
+    /// lists are only reachable by their key
+    pub fn from_config(config: &Config) -> Self {
+        let mut sections: Vec<SidebarSection> = Vec::new();
+        let named = config.sidebar_sections.iter().filter_map(|s| SidebarSection::from_name(s));
+        for section in named.chain(SidebarSection::ALL) {
+            if !sections.contains(&section) {
+                sections.push(section);
+            }
+        }
+        let mut main: Vec<SidebarItem> = Vec::new();
+        for item in config.sidebar_main.iter().filter_map(|s| SidebarItem::from_name(s)) {
+            if !main.contains(&item) {
+                main.push(item);
+            }
+        }
+        let mut footer: Vec<SidebarItem> = Vec::new();
+        for item in config.sidebar_footer.iter().filter_map(|s| SidebarItem::from_name(s)) {
+            if !main.contains(&item) && !footer.contains(&item) {
+                footer.push(item);
+            }
+        }
+        Self {
+            sections,
+            main,
+            footer,
+        }
+    }
+}
+
+impl Default for SidebarLayout {
+    fn default() -> Self {
+        Self::from_config(&Config::default())
+    }
 }
 
-#[derive(Default)]
+/// A place `j/k` can select in the sidebar
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SidebarStop {
+    Item(SidebarItem),
+    /// An area header or project, as (area, project id)
+    Row(Option<String>, Option<String>),
+}
+
+/// How the sidebar orders projects (display only, order_index is untouched)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectSort {
+    /// By order_index
+    Manual,
+    Name,
+    /// Most open tasks first
+    OpenTasks,
+}
+
+impl ProjectSort {
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "name" => ProjectSort::Name,
+            "count" => ProjectSort::OpenTasks,
+            _ => ProjectSort::Manual,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            ProjectSort::Manual => ProjectSort::Name,
+            ProjectSort::Name => ProjectSort::OpenTasks,
+            ProjectSort::OpenTasks => ProjectSort::Manual,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProjectSort::Manual => "manual order",
+            ProjectSort::Name => "name",
+            ProjectSort::OpenTasks => "open tasks",
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
 pub struct SidebarCounts {
     pub inbox: i64,
+    pub active: i64,
+    pub scheduled: i64,
     pub today: i64,
+    pub agenda: i64,
+    pub week: i64,
     pub upcoming: i64,
     pub anytime: i64,
+    pub flagged: i64,
+    pub deferred: i64,
     pub completed: i64,
     pub review: i64,
+    /// Open tasks per project id
+    pub projects: HashMap<String, i64>,
 }
 
 impl Default for SidebarState {
@@ -32,82 +163,184 @@ impl Default for SidebarState {
         Self {
             selected_item: SidebarItem::Inbox,
             selected_project: None,
+            selected_area: None,
+            collapsed_areas: HashSet::new(),
             projects: Vec::new(),
             focused: false,
             counts: SidebarCounts::default(),
+            project_scroll: 0,
+            project_sort: ProjectSort::Manual,
+            layout: SidebarLayout::default(),
+        }
+    }
+}
+
+/// A line of the projects section
+pub enum ProjectRow<'a> {
+    Area(&'a str),
+    Project(&'a Project),
+}
+
+impl ProjectRow<'_> {
+    /// The (area, project id) selection pointing at this row
+    fn selection(&self) -> (Option<String>, Option<String>) {
+        match self {
+            ProjectRow::Area(area) => (Some(area.to_string()), None),
+            ProjectRow::Project(project) => (None, Some(project.id.clone())),
         }
     }
 }
 
 impl SidebarState {
-    pub fn select_next(&mut self) {
-        // If a project is selected, navigate within projects or to footer
-        if let Some(ref proj_id) = self.selected_project {
-            if let Some(pos) = self.projects.iter().position(|p| &p.id == proj_id) {
-                if pos + 1 < self.projects.len() {
-                    // Move to next project
-                    self.selected_project = Some(self.projects[pos + 1].id.clone());
-                } else {
-                    // Move to Review (first footer item)
-                    self.selected_project = None;
-                    self.selected_item = SidebarItem::Review;
-                }
+    /// Rows of the projects section: pinned projects, projects without an area,
+    /// then each area (in order of its first project) followed by its projects
+    /// unless collapsed
+    pub fn project_rows(&self) -> Vec<ProjectRow<'_>> {
+        let mut rows: Vec<ProjectRow> = self
+            .projects
+            .iter()
+            .filter(|p| p.pinned)
+            .chain(self.projects.iter().filter(|p| !p.pinned && p.area.is_none()))
+            .map(ProjectRow::Project)
+            .collect();
+        let mut areas: Vec<&str> = Vec::new();
+        for area in self.area_projects().filter_map(|p| p.area.as_deref()) {
+            if !areas.contains(&area) {
+                areas.push(area);
+            }
+        }
+        for area in areas {
+            rows.push(ProjectRow::Area(area));
+            if !self.collapsed_areas.contains(area) {
+                rows.extend(
+                    self.area_projects()
+                        .filter(|p| p.area.as_deref() == Some(area))
+                        .map(ProjectRow::Project),
+                );
             }
-            return;
         }
+        rows
+    }
 
-        let items = SidebarItem::all();
-        if let Some(pos) = items.iter().position(|&i| i == self.selected_item) {
-            // At Completed, go to projects if any, otherwise skip to Review
-            if self.selected_item == SidebarItem::Completed && !self.projects.is_empty() {
-                self.selected_project = Some(self.projects[0].id.clone());
-            } else {
-                let next_pos = (pos + 1) % items.len();
-                self.selected_item = items[next_pos];
+    /// Projects listed under their area, i.e. not pinned
+    fn area_projects(&self) -> impl Iterator<Item = &Project> {
+        self.projects.iter().filter(|p| !p.pinned)
+    }
+
+    /// Row of the selected area or project; a project hidden in a collapsed area
+    /// is represented by the area's header
+    fn selected_row(&self, rows: &[ProjectRow]) -> Option<usize> {
+        let hidden_in = self
+            .selected_project
+            .as_ref()
+            .and_then(|id| self.area_projects().find(|p| &p.id == id))
+            .and_then(|p| p.area.as_deref())
+            .filter(|area| self.collapsed_areas.contains(*area));
+        rows.iter().position(|row| match row {
+            ProjectRow::Area(area) => {
+                self.selected_area.as_deref() == Some(area) || hidden_in == Some(area)
+            }
+            ProjectRow::Project(project) => self.selected_project.as_ref() == Some(&project.id),
+        })
+    }
+
+    fn select_row(&mut self, (area, project): (Option<String>, Option<String>)) {
+        self.selected_area = area;
+        self.selected_project = project;
+    }
+
+    /// Collapse or expand the selected area
+    pub fn toggle_selected_area(&mut self) {
+        if let Some(area) = self.selected_area.clone() {
+            if !self.collapsed_areas.remove(&area) {
+                self.collapsed_areas.insert(area);
             }
         }
     }
 
-    pub fn select_previous(&mut self) {
-        // If a project is selected, navigate within projects or to main items
-        if let Some(ref proj_id) = self.selected_project {
-            if let Some(pos) = self.projects.iter().position(|p| &p.id == proj_id) {
-                if pos > 0 {
-                    // Move to previous project
-                    self.selected_project = Some(self.projects[pos - 1].id.clone());
-                } else {
-                    // Move to Completed (last main item before projects)
-                    self.selected_project = None;
-                    self.selected_item = SidebarItem::Completed;
+    /// Everything `j/k` visit, top to bottom: the items and project rows of each
+    /// section in the configured order
+    fn stops(&self) -> Vec<SidebarStop> {
+        let mut stops = Vec::new();
+        for section in &self.layout.sections {
+            match section {
+                SidebarSection::Main => {
+                    stops.extend(self.layout.main.iter().map(|&item| SidebarStop::Item(item)))
+                }
+                SidebarSection::Projects => stops.extend(self.project_rows().iter().map(|row| {
+                    let (area, project) = row.selection();
+                    SidebarStop::Row(area, project)
+                })),
+                SidebarSection::Footer => {
+                    stops.extend(self.layout.footer.iter().map(|&item| SidebarStop::Item(item)))
                 }
             }
-            return;
         }
+        stops
+    }
 
-        let items = SidebarItem::all();
-        if let Some(pos) = items.iter().position(|&i| i == self.selected_item) {
-            // At Review, go to projects if any, otherwise go to Completed
-            if self.selected_item == SidebarItem::Review && !self.projects.is_empty() {
-                self.selected_project = Some(self.projects.last().unwrap().id.clone());
-            } else {
-                let prev_pos = if pos == 0 { items.len() - 1 } else { pos - 1 };
-                self.selected_item = items[prev_pos];
+    /// Position of the selection among `stops` (None for an item left out of the
+    /// sidebar)
+    fn current_stop(&self, stops: &[SidebarStop]) -> Option<usize> {
+        let rows = self.project_rows();
+        let current = match self.selected_row(&rows) {
+            Some(pos) => {
+                let (area, project) = rows[pos].selection();
+                SidebarStop::Row(area, project)
+            }
+            None => SidebarStop::Item(self.selected_item),
+        };
+        stops.iter().position(|stop| *stop == current)
+    }
+
+    fn go_to(&mut self, stop: SidebarStop) {
+        match stop {
+            SidebarStop::Item(item) => {
+                self.select_row((None, None));
+                self.selected_item = item;
             }
+            SidebarStop::Row(area, project) => self.select_row((area, project)),
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        let stops = self.stops();
+        if stops.is_empty() {
+            return;
         }
+        let next = match self.current_stop(&stops) {
+            Some(pos) => (pos + 1) % stops.len(),
+            None => 0,
+        };
+        self.go_to(stops[next].clone());
+    }
+
+    pub fn select_previous(&mut self) {
+        let stops = self.stops();
+        if stops.is_empty() {
+            return;
+        }
+        let previous = match self.current_stop(&stops) {
+            Some(pos) => (pos + stops.len() - 1) % stops.len(),
+            None => stops.len() - 1,
+        };
+        self.go_to(stops[previous].clone());
     }
 
     pub fn select_first(&mut self) {
-        self.selected_project = None;
-        self.selected_item = SidebarItem::Inbox;
+        if let Some(first) = self.stops().into_iter().next() {
+            self.go_to(first);
+        }
     }
 
     pub fn select_last(&mut self) {
-        self.selected_project = None;
-        self.selected_item = SidebarItem::Settings;
+        if let Some(last) = self.stops().pop() {
+            self.go_to(last);
+        }
     }
 }
 
-pub fn render_sidebar(frame: &mut Frame, area: Rect, state: &SidebarState) {
+pub fn render_sidebar(frame: &mut Frame, area: Rect, state: &mut SidebarState) {
     let block = Block::default()
         .borders(Borders::RIGHT)
         .border_style(Theme::border_style(state.focused))
@@ -116,26 +349,28 @@ pub fn render_sidebar(frame: &mut Frame, area: Rect, state: &SidebarState) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Layout: header, main items, projects, footer items
-    let chunks = Layout::vertical([
-        Constraint::Length(3), // Header
-        Constraint::Length(7), // Main nav (5 items + spacing)
-        Constraint::Min(3),    // Projects
-        Constraint::Length(6), // Footer nav (4 items + spacing)
-    ])
-    .split(inner);
+    // Layout: header, then the main items, projects and footer items in the
+    // configured order; each nav section gets its items plus spacing
+    let nav_height = |items: &[SidebarItem]| Constraint::Length(items.len() as u16 + 2);
+    let mut constraints = vec![Constraint::Length(3)];
+    constraints.extend(state.layout.sections.iter().map(|section| match section {
+        SidebarSection::Main => nav_height(&state.layout.main),
+        SidebarSection::Projects => Constraint::Min(3),
+        SidebarSection::Footer => nav_height(&state.layout.footer),
+    }));
+    let chunks = Layout::vertical(constraints).split(inner);
 
     // Header
     render_header(frame, chunks[0]);
 
-    // Main navigation items (Inbox, Today, Upcoming, Anytime, Completed)
-    render_main_nav(frame, chunks[1], state);
-
-    // Projects section
-    render_projects(frame, chunks[2], state);
-
-    // Footer items (Review, GitHub, Toggl, Settings)
-    render_footer_nav(frame, chunks[3], state);
+    for (i, section) in state.layout.sections.clone().into_iter().enumerate() {
+        let area = chunks[i + 1];
+        match section {
+            SidebarSection::Main => render_nav(frame, area, state, &state.layout.main),
+            SidebarSection::Projects => render_projects(frame, area, state),
+            SidebarSection::Footer => render_nav(frame, area, state, &state.layout.footer),
+        }
+    }
 }
 
 fn render_header(frame: &mut Frame, area: Rect) {
@@ -151,23 +386,17 @@ fn render_header(frame: &mut Frame, area: Rect) {
     frame.render_widget(header, area);
 }
 
-fn render_main_nav(frame: &mut Frame, area: Rect, state: &SidebarState) {
-    let items: Vec<ListItem> = [
-        SidebarItem::Inbox,
-        SidebarItem::Today,
-        SidebarItem::Upcoming,
-        SidebarItem::Anytime,
-        SidebarItem::Completed,
-    ]
-    .iter()
-    .map(|item| create_nav_item(item, state, get_count(item, &state.counts)))
-    .collect();
+fn render_nav(frame: &mut Frame, area: Rect, state: &SidebarState, nav: &[SidebarItem]) {
+    let items: Vec<ListItem> = nav
+        .iter()
+        .map(|item| create_nav_item(item, state, get_count(item, &state.counts)))
+        .collect();
 
     let list = List::new(items).style(Style::default().bg(Theme::BG_SECONDARY));
     frame.render_widget(list, area);
 }
 
-fn render_projects(frame: &mut Frame, area: Rect, state: &SidebarState) {
+fn render_projects(frame: &mut Frame, area: Rect, state: &mut SidebarState) {
     if area.height < 2 {
         return;
     }
@@ -194,54 +423,94 @@ fn render_projects(frame: &mut Frame, area: Rect, state: &SidebarState) {
         return;
     }
 
-    let items: Vec<ListItem> = state
-        .projects
+    let rows = state.project_rows();
+    let selected = state.selected_row(&rows);
+    let count_span = |count: i64| {
+        Span::styled(format!(" ({})", count), Style::default().fg(Theme::FG_DIM))
+    };
+    let items: Vec<ListItem> = rows
         .iter()
-        .map(|project| {
-            let is_selected = state.selected_project.as_ref() == Some(&project.id);
+        .enumerate()
+        .map(|(i, row)| {
+            let is_selected = selected == Some(i);
             let style = if is_selected {
                 Theme::selected_style()
             } else {
                 Style::default().fg(Theme::FG)
             };
 
-            ListItem::new(Line::from(vec![
-                Span::raw("  "),
-                Span::styled(project.display_icon(), style),
+            let project = match row {
+                ProjectRow::Area(area) => {
+                    let marker = if state.collapsed_areas.contains(*area) {
+                        Theme::icon("▸", "+")
+                    } else {
+                        Theme::icon("▾", "-")
+                    };
+                    let count: i64 = state
+                        .area_projects()
+                        .filter(|p| p.area.as_deref() == Some(*area))
+                        .filter_map(|p| state.counts.projects.get(&p.id))
+                        .sum();
+                    let mut spans = vec![
+                        Span::raw("  "),
+                        Span::styled(marker, style),
+                        Span::raw(" "),
+                        Span::styled(area.to_string(), style.add_modifier(Modifier::BOLD)),
+                    ];
+                    if count > 0 {
+                        spans.push(count_span(count));
+                    }
+                    return ListItem::new(Line::from(spans));
+                }
+                ProjectRow::Project(project) => project,
+            };
+
+            let icon_style = match project.color.as_deref().and_then(Theme::parse_color) {
+                Some(color) if !is_selected => style.fg(color),
+                _ => style,
+            };
+            let indent = if project.area.is_some() && !project.pinned { "    " } else { "  " };
+            let mut spans = vec![
+                Span::raw(indent),
+                Span::styled(Theme::project_icon(project), icon_style),
                 Span::raw(" "),
-                Span::styled(&project.name, style),
-            ]))
+                Span::styled(project.name.clone(), style),
+            ];
+            if project.pinned {
+                spans.push(Span::styled(
+                    format!(" {}", Theme::icon("󰐃", "*")),
+                    Style::default().fg(Theme::FG_DIM),
+                ));
+            }
+            if let Some(&count) = state.counts.projects.get(&project.id).filter(|&&c| c > 0) {
+                spans.push(count_span(count));
+            }
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
+    // Scroll just enough to keep the selected row visible
+    let mut list_state = ListState::default()
+        .with_offset(state.project_scroll.min(rows.len() - 1))
+        .with_selected(selected);
+
     let list = List::new(items).style(Style::default().bg(Theme::BG_SECONDARY));
-    frame.render_widget(
+    frame.render_stateful_widget(
         list,
         Rect {
             y: area.y + 1,
             height: area.height.saturating_sub(1),
             ..area
         },
+        &mut list_state,
     );
-}
-
-fn render_footer_nav(frame: &mut Frame, area: Rect, state: &SidebarState) {
-    let items: Vec<ListItem> = [
-        SidebarItem::Review,
-        SidebarItem::GitHub,
-        SidebarItem::Toggl,
-        SidebarItem::Settings,
-    ]
-    .iter()
-    .map(|item| create_nav_item(item, state, get_count(item, &state.counts)))
-    .collect();
-
-    let list = List::new(items).style(Style::default().bg(Theme::BG_SECONDARY));
-    frame.render_widget(list, area);
+    state.project_scroll = list_state.offset();
 }
 
 fn create_nav_item(item: &SidebarItem, state: &SidebarState, count: Option<i64>) -> ListItem<'static> {
-    let is_selected = state.selected_item == *item && state.selected_project.is_none();
+    let is_selected = state.selected_item == *item
+        && state.selected_project.is_none()
+        && state.selected_area.is_none();
     let style = if is_selected {
         Theme::selected_style()
     } else {
@@ -282,9 +551,15 @@ fn create_nav_item(item: &SidebarItem, state: &SidebarState, count: Option<i64>)
 fn get_count(item: &SidebarItem, counts: &SidebarCounts) -> Option<i64> {
     match item {
         SidebarItem::Inbox => Some(counts.inbox),
+        SidebarItem::Active => Some(counts.active),
+        SidebarItem::Scheduled => Some(counts.scheduled),
         SidebarItem::Today => Some(counts.today),
+        SidebarItem::Agenda => Some(counts.agenda),
+        SidebarItem::Week => Some(counts.week),
         SidebarItem::Upcoming => Some(counts.upcoming),
         SidebarItem::Anytime => Some(counts.anytime),
+        SidebarItem::Flagged => Some(counts.flagged),
+        SidebarItem::Deferred => Some(counts.deferred),
         SidebarItem::Completed => Some(counts.completed),
         SidebarItem::Review => Some(counts.review),
         _ => None,
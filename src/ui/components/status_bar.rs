@@ -49,7 +49,29 @@ pub fn render_status_bar(frame: &mut Frame, area: Rect, ctx: &StatusBarContext)
         _ => vec![("?", "help"), ("q", "quit")],
     };
 
-    let spans: Vec<Span> = shortcuts
+    // Hints are listed most relevant first; when they don't all fit, keep as
+    // many whole hints as possible and point to the help overlay for the rest
+    const SEPARATOR: &str = "  │  ";
+    const OVERFLOW: &str = "… ? for more";
+    let hint_width = |(key, action): &(&str, &str)| key.chars().count() + 1 + action.chars().count();
+    let total_width = |hints: &[(&str, &str)]| {
+        hints.iter().map(hint_width).sum::<usize>()
+            + SEPARATOR.chars().count() * hints.len().saturating_sub(1)
+    };
+
+    let available = area.width as usize;
+    let mut visible: &[(&str, &str)] = &shortcuts;
+    let overflow = total_width(visible) > available;
+    if overflow {
+        let reserve = SEPARATOR.chars().count() + OVERFLOW.chars().count();
+        let mut count = shortcuts.len();
+        while count > 0 && total_width(&shortcuts[..count]) + reserve > available {
+            count -= 1;
+        }
+        visible = &shortcuts[..count];
+    }
+
+    let mut spans: Vec<Span> = visible
         .iter()
         .enumerate()
         .flat_map(|(i, (key, action))| {
@@ -62,13 +84,20 @@ pub fn render_status_bar(frame: &mut Frame, area: Rect, ctx: &StatusBarContext)
                 ),
                 Span::styled(format!(" {}", action), Style::default().fg(Theme::FG_DIM)),
             ];
-            if i < shortcuts.len() - 1 {
-                s.push(Span::styled("  │  ", Style::default().fg(Theme::BORDER)));
+            if i < visible.len() - 1 {
+                s.push(Span::styled(SEPARATOR, Style::default().fg(Theme::BORDER)));
             }
             s
         })
         .collect();
 
+    if overflow {
+        if !visible.is_empty() {
+            spans.push(Span::styled(SEPARATOR, Style::default().fg(Theme::BORDER)));
+        }
+        spans.push(Span::styled(OVERFLOW, Style::default().fg(Theme::FG_MUTED)));
+    }
+
     let help_line = Paragraph::new(Line::from(spans))
         .style(Style::default().bg(Theme::BG_SECONDARY));
 
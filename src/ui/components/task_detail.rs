@@ -2,14 +2,32 @@ use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 
-use crate::models::Task;
+use crate::models::{is_internal_metadata_key, Task};
+use crate::services::format_estimate;
+use crate::ui::components::markdown_lines;
 use crate::ui::theme::Theme;
 
-pub fn render_task_detail(frame: &mut Frame, area: Rect, task: Option<&Task>, focused: bool) {
+/// How the detail pane is drawn, from the app's display settings
+#[derive(Debug, Clone, Copy)]
+pub struct DetailOptions {
+    /// Hide the metadata block (due, created/updated, URL), leaving its rows to
+    /// the notes
+    pub metadata_collapsed: bool,
+    /// Render the notes as Markdown rather than plain text
+    pub markdown_notes: bool,
+}
+
+pub fn render_task_detail(
+    frame: &mut Frame,
+    area: Rect,
+    task: Option<&Task>,
+    focused: bool,
+    options: DetailOptions,
+) {
     let block = Block::default()
         .title(" Task Details ")
         .title_style(Theme::title_style())
@@ -26,11 +44,15 @@ pub fn render_task_detail(frame: &mut Frame, area: Rect, task: Option<&Task>, fo
         return;
     };
 
+    let field_lines = create_field_lines(task);
+    let metadata_height = if options.metadata_collapsed { 0 } else { 4 };
+
     let chunks = Layout::vertical([
-        Constraint::Length(2), // Title
-        Constraint::Length(3), // Status line
-        Constraint::Min(3),    // Notes
-        Constraint::Length(4), // Metadata
+        Constraint::Length(2),                        // Title
+        Constraint::Length(3),                        // Status line
+        Constraint::Min(3),                           // Notes
+        Constraint::Length(field_lines.len() as u16), // Custom fields
+        Constraint::Length(metadata_height),          // Metadata
     ])
     .split(inner);
 
@@ -52,9 +74,12 @@ pub fn render_task_detail(frame: &mut Frame, area: Rect, task: Option<&Task>, fo
 
     // Notes
     if let Some(ref notes) = task.notes {
-        let notes_para = Paragraph::new(notes.as_str())
-            .style(Theme::dimmed_style())
-            .wrap(Wrap { trim: false });
+        let notes_para = if options.markdown_notes {
+            Paragraph::new(markdown_lines(notes, Theme::dimmed_style()))
+        } else {
+            Paragraph::new(notes.as_str()).style(Theme::dimmed_style())
+        }
+        .wrap(Wrap { trim: false });
         frame.render_widget(notes_para, chunks[2]);
     } else {
         let empty_notes = Paragraph::new("No notes")
@@ -62,28 +87,156 @@ pub fn render_task_detail(frame: &mut Frame, area: Rect, task: Option<&Task>, fo
         frame.render_widget(empty_notes, chunks[2]);
     }
 
+    // Custom fields
+    frame.render_widget(Paragraph::new(field_lines), chunks[3]);
+
     // Metadata
     let metadata_lines = create_metadata_lines(task);
     let metadata = Paragraph::new(metadata_lines);
-    frame.render_widget(metadata, chunks[3]);
+    frame.render_widget(metadata, chunks[4]);
+}
+
+pub struct TaskPreviewContext<'a> {
+    pub task: &'a Task,
+    pub project_name: Option<&'a str>,
+    pub tag_names: Vec<&'a str>,
+    pub markdown_notes: bool,
+}
+
+/// Full-screen, read-only layout of every task field, for reading or sharing a
+/// task; clamps `scroll` to the content like the help overlay
+pub fn render_task_preview(frame: &mut Frame, area: Rect, ctx: &TaskPreviewContext, scroll: &mut u16) {
+    let task = ctx.task;
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Task Preview ")
+        .title_style(Theme::title_style())
+        .borders(Borders::ALL)
+        .border_style(Theme::border_style(true))
+        .style(Style::default().bg(Theme::BG));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).split(inner);
+
+    let field = |label: &str, value: String, style: Style| {
+        Line::from(vec![
+            Span::styled(format!("{:<11}", label), Theme::dimmed_style()),
+            Span::styled(value, style),
+        ])
+    };
+    let plain = Style::default().fg(Theme::FG);
+    let date = |d: Option<chrono::NaiveDate>| d.map(|d| d.to_string()).unwrap_or_else(|| "none".to_string());
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            task.title.clone(),
+            Style::default().fg(Theme::FG).add_modifier(Modifier::BOLD),
+        )),
+        Line::default(),
+    ];
+    lines.extend(create_status_line(task));
+    lines.push(Line::default());
+
+    let tags = if ctx.tag_names.is_empty() {
+        "none".to_string()
+    } else {
+        ctx.tag_names.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ")
+    };
+    let due_style = if task.is_overdue() { Style::default().fg(Theme::ERROR) } else { plain };
+    lines.push(field("Project", ctx.project_name.unwrap_or("none").to_string(), plain));
+    lines.push(field("Tags", tags, plain));
+    lines.push(field("Due", date(task.due_date), due_style));
+    lines.push(field("Start", date(task.start_date), plain));
+    if let Some(completed) = task.completed_at {
+        lines.push(field("Completed", completed.format("%Y-%m-%d %H:%M").to_string(), plain));
+    }
+    lines.push(field("Flagged", if task.flagged { "yes" } else { "no" }.to_string(), plain));
+    lines.push(field("Assignee", task.assignee.clone().unwrap_or_else(|| "none".to_string()), plain));
+    if let Some(ref url) = task.context_url {
+        lines.push(field("URL", url.clone(), Style::default().fg(Theme::INFO)));
+    }
+    lines.push(field(
+        "Created",
+        task.created_at.format("%Y-%m-%d %H:%M").to_string(),
+        Theme::muted_style(),
+    ));
+    lines.push(field(
+        "Updated",
+        task.updated_at.format("%Y-%m-%d %H:%M").to_string(),
+        Theme::muted_style(),
+    ));
+    lines.push(field("Id", task.id.clone(), Theme::muted_style()));
+
+    let field_lines = create_field_lines(task);
+    if !field_lines.is_empty() {
+        lines.push(Line::default());
+        lines.extend(field_lines);
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled("Notes", Theme::dimmed_style())));
+    match task.notes {
+        Some(ref notes) if ctx.markdown_notes => lines.extend(markdown_lines(notes, plain)),
+        Some(ref notes) => lines.extend(notes.lines().map(|l| Line::styled(l.to_string(), plain))),
+        None => lines.push(Line::from(Span::styled("No notes", Theme::muted_style()))),
+    }
+
+    let max_scroll = lines.len().saturating_sub(chunks[0].height as usize) as u16;
+    *scroll = (*scroll).min(max_scroll);
+    let body = Paragraph::new(lines).wrap(Wrap { trim: false }).scroll((*scroll, 0));
+    frame.render_widget(body, chunks[0]);
+
+    let footer = Paragraph::new(Span::styled(
+        "j/k or PgUp/PgDn to scroll, Esc to close",
+        Theme::muted_style(),
+    ));
+    frame.render_widget(footer, chunks[1]);
+}
+
+fn create_field_lines(task: &Task) -> Vec<Line<'static>> {
+    let mut keys: Vec<&String> = task
+        .metadata
+        .keys()
+        .filter(|key| !is_internal_metadata_key(key))
+        .collect();
+    if keys.is_empty() {
+        return vec![];
+    }
+    keys.sort();
+
+    let mut lines = vec![Line::from(Span::styled("Fields", Theme::dimmed_style()))];
+    for key in keys {
+        // Sync-managed keys are shown muted since they can't be edited
+        let key_style = if key.starts_with("github_") {
+            Theme::muted_style()
+        } else {
+            Style::default().fg(Theme::PRIMARY)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}: ", key), key_style),
+            Span::styled(task.metadata[key].clone(), Style::default().fg(Theme::FG)),
+        ]));
+    }
+    lines
 }
 
 fn create_status_line(task: &Task) -> Vec<Line<'static>> {
     let mut spans = vec![];
 
-    // Status
+    // Status and priority are always shown and padded so the line doesn't reflow
+    spans.push(Span::styled("Status: ", Theme::dimmed_style()));
     spans.push(Span::styled(
-        format!("Status: {} ", task.status.as_str()),
-        Theme::dimmed_style(),
+        format!("{:<10}", task.status.as_str()),
+        Style::default().fg(Theme::status_color(&task.status)),
     ));
 
-    // Priority
-    if task.priority != crate::models::TaskPriority::None {
-        spans.push(Span::styled(
-            format!("Priority: {} ", task.priority.as_str()),
-            Style::default().fg(Theme::priority_color(&task.priority)),
-        ));
-    }
+    spans.push(Span::styled("Priority: ", Theme::dimmed_style()));
+    spans.push(Span::styled(
+        format!("{:<7}", task.priority.as_str()),
+        Style::default().fg(Theme::priority_color(&task.priority)),
+    ));
 
     // Kind
     if let Some(ref kind) = task.kind {
@@ -101,6 +254,22 @@ fn create_status_line(task: &Task) -> Vec<Line<'static>> {
         ));
     }
 
+    // Estimate
+    if let Some(minutes) = task.estimate_minutes {
+        spans.push(Span::styled(
+            format!("Est: {} ", format_estimate(minutes)),
+            Theme::dimmed_style(),
+        ));
+    }
+
+    // Logged time
+    if task.time_spent_minutes > 0 {
+        spans.push(Span::styled(
+            format!("Spent: {} ", format_estimate(task.time_spent_minutes)),
+            Theme::dimmed_style(),
+        ));
+    }
+
     vec![Line::from(spans)]
 }
 
@@ -2,11 +2,12 @@ use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
 use crate::models::{Project, Task, TaskKind, TaskPriority, TaskSize, TaskStatus};
+use crate::services::{format_estimate, fuzzy_matches, parse_due_date, parse_estimate};
 use crate::ui::theme::Theme;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,6 +20,7 @@ pub enum TaskFormField {
     Status,
     Kind,
     Size,
+    Estimate,
 }
 
 impl TaskFormField {
@@ -32,6 +34,7 @@ impl TaskFormField {
             TaskFormField::Status,
             TaskFormField::Kind,
             TaskFormField::Size,
+            TaskFormField::Estimate,
         ]
     }
 
@@ -45,6 +48,24 @@ impl TaskFormField {
             TaskFormField::Status => "Status",
             TaskFormField::Kind => "Kind",
             TaskFormField::Size => "Size",
+            TaskFormField::Estimate => "Estimate",
+        }
+    }
+}
+
+/// What Enter does outside the Project field (which always opens the picker);
+/// Ctrl+S saves either way
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormEnter {
+    Save,
+    NextField,
+}
+
+impl FormEnter {
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "next" => FormEnter::NextField,
+            _ => FormEnter::Save,
         }
     }
 }
@@ -56,8 +77,28 @@ pub struct TaskFormState {
     pub title_input: String,
     pub notes_input: String,
     pub due_date_input: String,
+    pub estimate_input: String,
     pub available_projects: Vec<Project>,
     pub selected_project_index: Option<usize>,
+    /// Open while choosing a project by typing part of its name
+    pub project_picker: Option<ProjectPicker>,
+    /// The project carries over to new tasks until unpinned or the view changes
+    pub project_pinned: bool,
+    pub enter: FormEnter,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProjectPicker {
+    pub query: String,
+    /// Index into the current matches
+    pub selected: usize,
+}
+
+/// The option after (or before) `current` in `options`, wrapping around
+fn step<T: Clone + PartialEq>(options: &[T], current: T, forward: bool) -> T {
+    let len = options.len();
+    let pos = options.iter().position(|o| *o == current).unwrap_or(0);
+    options[if forward { (pos + 1) % len } else { (pos + len - 1) % len }].clone()
 }
 
 impl TaskFormState {
@@ -69,8 +110,12 @@ impl TaskFormState {
             title_input: String::new(),
             notes_input: String::new(),
             due_date_input: String::new(),
+            estimate_input: String::new(),
             available_projects: projects,
             selected_project_index: None,
+            project_picker: None,
+            project_pinned: false,
+            enter: FormEnter::Save,
         }
     }
 
@@ -78,6 +123,7 @@ impl TaskFormState {
         let title_input = task.title.clone();
         let notes_input = task.notes.clone().unwrap_or_default();
         let due_date_input = task.due_date.map(|d| d.to_string()).unwrap_or_default();
+        let estimate_input = task.estimate_minutes.map(format_estimate).unwrap_or_default();
 
         // Find current project index
         let selected_project_index = task.project_id.as_ref().and_then(|pid| {
@@ -91,25 +137,89 @@ impl TaskFormState {
             title_input,
             notes_input,
             due_date_input,
+            estimate_input,
             available_projects: projects,
             selected_project_index,
+            project_picker: None,
+            project_pinned: false,
+            enter: FormEnter::Save,
         }
     }
 
-    pub fn cycle_project(&mut self) {
+    /// Start a new task with the given project (if it still exists) and kind
+    pub fn preselect(&mut self, project_id: Option<&str>, kind: Option<TaskKind>) {
+        let index = project_id
+            .and_then(|id| self.available_projects.iter().position(|p| p.id == id));
+        self.set_project_index(index);
+        self.task.kind = kind;
+    }
+
+    /// Step to the next (or previous) project, with "none" between the last and
+    /// the first
+    pub fn cycle_project(&mut self, forward: bool) {
         if self.available_projects.is_empty() {
             return;
         }
-        self.selected_project_index = match self.selected_project_index {
-            None => Some(0),
-            Some(i) if i + 1 < self.available_projects.len() => Some(i + 1),
-            Some(_) => None,
-        };
+        let options: Vec<Option<usize>> = std::iter::once(None)
+            .chain((0..self.available_projects.len()).map(Some))
+            .collect();
+        self.set_project_index(step(&options, self.selected_project_index, forward));
+    }
+
+    fn set_project_index(&mut self, index: Option<usize>) {
+        self.selected_project_index = index;
         // Update task's project_id
         self.task.project_id = self.selected_project_index
             .map(|i| self.available_projects[i].id.clone());
     }
 
+    pub fn open_project_picker(&mut self) {
+        if !self.available_projects.is_empty() {
+            self.project_picker = Some(ProjectPicker::default());
+        }
+    }
+
+    /// Indices of the projects whose names fuzzy-match the picker query
+    pub fn picker_matches(&self) -> Vec<usize> {
+        let query = self.project_picker.as_ref().map_or("", |p| p.query.as_str());
+        (0..self.available_projects.len())
+            .filter(|&i| fuzzy_matches(query, &self.available_projects[i].name))
+            .collect()
+    }
+
+    pub fn picker_input(&mut self, c: Option<char>) {
+        if let Some(picker) = self.project_picker.as_mut() {
+            match c {
+                Some(c) => picker.query.push(c),
+                None => {
+                    picker.query.pop();
+                }
+            }
+            picker.selected = 0;
+        }
+    }
+
+    pub fn picker_move(&mut self, forward: bool) {
+        let count = self.picker_matches().len();
+        if let Some(picker) = self.project_picker.as_mut().filter(|_| count > 0) {
+            picker.selected = if forward {
+                (picker.selected + 1) % count
+            } else {
+                (picker.selected + count - 1) % count
+            };
+        }
+    }
+
+    /// Use the highlighted match as the task's project and close the picker
+    pub fn picker_confirm(&mut self) {
+        let matches = self.picker_matches();
+        if let Some(picker) = self.project_picker.take() {
+            if let Some(&index) = matches.get(picker.selected) {
+                self.set_project_index(Some(index));
+            }
+        }
+    }
+
     pub fn selected_project_name(&self) -> &str {
         match self.selected_project_index {
             Some(i) => &self.available_projects[i].name,
@@ -131,46 +241,52 @@ impl TaskFormState {
         }
     }
 
-    pub fn cycle_priority(&mut self) {
-        self.task.priority = match self.task.priority {
-            TaskPriority::None => TaskPriority::Low,
-            TaskPriority::Low => TaskPriority::Medium,
-            TaskPriority::Medium => TaskPriority::High,
-            TaskPriority::High => TaskPriority::None,
-        };
+    pub fn cycle_priority(&mut self, forward: bool) {
+        self.task.priority = step(
+            &[
+                TaskPriority::None,
+                TaskPriority::Low,
+                TaskPriority::Medium,
+                TaskPriority::High,
+            ],
+            self.task.priority,
+            forward,
+        );
     }
 
-    pub fn cycle_status(&mut self) {
-        self.task.status = match self.task.status {
-            TaskStatus::Inbox => TaskStatus::Active,
-            TaskStatus::Active => TaskStatus::Scheduled,
-            TaskStatus::Scheduled => TaskStatus::Completed,
-            TaskStatus::Completed => TaskStatus::Cancelled,
-            TaskStatus::Cancelled => TaskStatus::Inbox,
-        };
+    pub fn cycle_status(&mut self, forward: bool) {
+        self.task.status = step(
+            &[
+                TaskStatus::Inbox,
+                TaskStatus::Active,
+                TaskStatus::Scheduled,
+                TaskStatus::Completed,
+                TaskStatus::Cancelled,
+            ],
+            self.task.status,
+            forward,
+        );
     }
 
-    pub fn cycle_kind(&mut self) {
-        self.task.kind = match self.task.kind {
-            None => Some(TaskKind::Task),
-            Some(TaskKind::Task) => Some(TaskKind::Bug),
-            Some(TaskKind::Bug) => Some(TaskKind::Feature),
-            Some(TaskKind::Feature) => Some(TaskKind::Chore),
-            Some(TaskKind::Chore) => Some(TaskKind::GhIssue),
-            Some(TaskKind::GhIssue) => Some(TaskKind::GhPr),
-            Some(TaskKind::GhPr) => Some(TaskKind::GhReview),
-            Some(TaskKind::GhReview) => None,
-        };
+    pub fn cycle_kind(&mut self, forward: bool) {
+        let options: Vec<Option<TaskKind>> = std::iter::once(None)
+            .chain(Theme::kind_choices().into_iter().map(Some))
+            .collect();
+        self.task.kind = step(&options, self.task.kind.take(), forward);
     }
 
-    pub fn cycle_size(&mut self) {
-        self.task.size = match self.task.size {
-            None => Some(TaskSize::Xs),
-            Some(TaskSize::Xs) => Some(TaskSize::S),
-            Some(TaskSize::S) => Some(TaskSize::M),
-            Some(TaskSize::M) => Some(TaskSize::L),
-            Some(TaskSize::L) => None,
-        };
+    pub fn cycle_size(&mut self, forward: bool) {
+        self.task.size = step(
+            &[
+                None,
+                Some(TaskSize::Xs),
+                Some(TaskSize::S),
+                Some(TaskSize::M),
+                Some(TaskSize::L),
+            ],
+            self.task.size,
+            forward,
+        );
     }
 
     pub fn apply_inputs(&mut self) {
@@ -180,14 +296,20 @@ impl TaskFormState {
         } else {
             Some(self.notes_input.clone())
         };
-        self.task.due_date = chrono::NaiveDate::parse_from_str(&self.due_date_input, "%Y-%m-%d").ok();
+        self.task.due_date = self.parsed_due_date();
+        self.task.estimate_minutes = parse_estimate(&self.estimate_input);
+    }
+
+    /// The due date the current input resolves to, if it parses
+    pub fn parsed_due_date(&self) -> Option<chrono::NaiveDate> {
+        parse_due_date(&self.due_date_input, chrono::Utc::now().date_naive())
     }
 }
 
 pub fn render_task_form(frame: &mut Frame, area: Rect, state: &TaskFormState) {
     // Center the form
     let width = area.width.min(60);
-    let height = area.height.min(22);
+    let height = area.height.min(25);
     let x = area.x + (area.width - width) / 2;
     let y = area.y + (area.height - height) / 2;
     let form_area = Rect::new(x, y, width, height);
@@ -220,6 +342,7 @@ pub fn render_task_form(frame: &mut Frame, area: Rect, state: &TaskFormState) {
         Constraint::Length(2), // Status
         Constraint::Length(2), // Kind
         Constraint::Length(2), // Size
+        Constraint::Length(3), // Estimate
         Constraint::Min(1),    // Help text
     ])
     .split(inner);
@@ -242,21 +365,37 @@ pub fn render_task_form(frame: &mut Frame, area: Rect, state: &TaskFormState) {
         state.current_field == TaskFormField::Notes,
     );
 
-    // Due Date field
+    // Due Date field, with the parsed date (or an error) below the input
     render_text_field(
         frame,
         chunks[2],
-        "Due Date (YYYY-MM-DD)",
+        "Due Date",
         &state.due_date_input,
         state.current_field == TaskFormField::DueDate,
     );
+    let due_hint = if state.due_date_input.trim().is_empty() {
+        Span::styled("  YYYY-MM-DD, today, tomorrow, fri, +3d", Theme::muted_style())
+    } else if let Some(date) = state.parsed_due_date() {
+        Span::styled(format!("  → {}", date), Style::default().fg(Theme::SUCCESS))
+    } else {
+        Span::styled("  invalid date", Style::default().fg(Theme::ERROR))
+    };
+    if chunks[2].height > 1 {
+        let hint_area = Rect::new(chunks[2].x, chunks[2].y + 1, chunks[2].width, 1);
+        frame.render_widget(Paragraph::new(Line::from(due_hint)), hint_area);
+    }
 
     // Project field
+    let project_name = if state.project_pinned {
+        format!("{} (pinned)", state.selected_project_name())
+    } else {
+        state.selected_project_name().to_string()
+    };
     render_select_field(
         frame,
         chunks[3],
         "Project",
-        state.selected_project_name(),
+        &project_name,
         state.current_field == TaskFormField::Project,
     );
 
@@ -283,7 +422,7 @@ pub fn render_task_form(frame: &mut Frame, area: Rect, state: &TaskFormState) {
         frame,
         chunks[6],
         "Kind",
-        state.task.kind.map(|k| k.as_str()).unwrap_or("none"),
+        state.task.kind.as_ref().map(|k| k.as_str()).unwrap_or("none"),
         state.current_field == TaskFormField::Kind,
     );
 
@@ -296,19 +435,106 @@ pub fn render_task_form(frame: &mut Frame, area: Rect, state: &TaskFormState) {
         state.current_field == TaskFormField::Size,
     );
 
+    // Estimate field, with the parsed estimate (or the size's default) below
+    render_text_field(
+        frame,
+        chunks[8],
+        "Estimate",
+        &state.estimate_input,
+        state.current_field == TaskFormField::Estimate,
+    );
+    let estimate_hint = if !state.estimate_input.trim().is_empty() {
+        match parse_estimate(&state.estimate_input) {
+            Some(minutes) => Span::styled(
+                format!("  → {}", format_estimate(minutes)),
+                Style::default().fg(Theme::SUCCESS),
+            ),
+            None => Span::styled("  invalid estimate", Style::default().fg(Theme::ERROR)),
+        }
+    } else if let Some(size) = state.task.size {
+        Span::styled(
+            format!("  {} from size {}", format_estimate(size.default_minutes()), size.display()),
+            Theme::muted_style(),
+        )
+    } else {
+        Span::styled("  minutes, or 2h, 1h30m", Theme::muted_style())
+    };
+    if chunks[8].height > 1 {
+        let hint_area = Rect::new(chunks[8].x, chunks[8].y + 1, chunks[8].width, 1);
+        frame.render_widget(Paragraph::new(Line::from(estimate_hint)), hint_area);
+    }
+
     // Help text
-    let help = Paragraph::new(Line::from(vec![
+    let enter_action = match (state.current_field, state.enter) {
+        (TaskFormField::Project, _) => ": Pick | ",
+        (_, FormEnter::Save) => ": Save | ",
+        (_, FormEnter::NextField) => ": Next | ",
+    };
+    let mut help_spans = vec![
         Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
         Span::raw("/"),
         Span::styled("Shift+Tab", Style::default().add_modifier(Modifier::BOLD)),
         Span::raw(": Navigate | "),
         Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
-        Span::raw(": Save | "),
-        Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
-        Span::raw(": Cancel"),
-    ]))
-    .style(Theme::muted_style());
-    frame.render_widget(help, chunks[8]);
+        Span::raw(enter_action),
+    ];
+    if state.enter == FormEnter::NextField || state.current_field == TaskFormField::Project {
+        help_spans.push(Span::styled("Ctrl+S", Style::default().add_modifier(Modifier::BOLD)));
+        help_spans.push(Span::raw(": Save | "));
+    }
+    help_spans.push(Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)));
+    help_spans.push(Span::raw(": Cancel"));
+    let help = Paragraph::new(Line::from(help_spans)).style(Theme::muted_style());
+    frame.render_widget(help, chunks[9]);
+
+    if let Some(ref picker) = state.project_picker {
+        let below_field = chunks[3].y + 1;
+        let picker_area = Rect::new(
+            chunks[3].x,
+            below_field,
+            chunks[3].width,
+            (inner.bottom().saturating_sub(below_field)).min(10),
+        );
+        render_project_picker(frame, picker_area, state, picker);
+    }
+}
+
+/// Dropdown under the Project field listing the projects matching the query
+fn render_project_picker(
+    frame: &mut Frame,
+    area: Rect,
+    state: &TaskFormState,
+    picker: &ProjectPicker,
+) {
+    frame.render_widget(Clear, area);
+
+    let items: Vec<ListItem> = state
+        .picker_matches()
+        .into_iter()
+        .map(|i| {
+            let project = &state.available_projects[i];
+            ListItem::new(format!("{} {}", Theme::project_icon(project), project.name))
+        })
+        .collect();
+    let title = if items.is_empty() {
+        format!(" Project: {}_ (no match) ", picker.query)
+    } else {
+        format!(" Project: {}_ ", picker.query)
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(title)
+                .title_style(Theme::title_style())
+                .borders(Borders::ALL)
+                .border_style(Theme::border_style(true))
+                .style(Style::default().bg(Theme::BG_SECONDARY)),
+        )
+        .highlight_style(Theme::selected_style())
+        .highlight_symbol("› ");
+    let mut list_state = ListState::default().with_selected(Some(picker.selected));
+    frame.render_stateful_widget(list, area, &mut list_state);
 }
 
 fn render_text_field(frame: &mut Frame, area: Rect, label: &str, value: &str, focused: bool) {
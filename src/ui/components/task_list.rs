@@ -1,19 +1,95 @@
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
 use ratatui::{
     layout::Rect,
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState},
     Frame,
 };
 
 use crate::models::{Task, TaskPriority};
+use crate::services::{format_hours, fuzzy_matches};
+use crate::ui::components::truncate;
 use crate::ui::theme::Theme;
 
+/// How a task row shows its project (or tag) color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowAccent {
+    Off,
+    /// A colored bar at the start of the row
+    Bar,
+    /// Colored task title
+    Title,
+}
+
+impl RowAccent {
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "bar" => RowAccent::Bar,
+            "title" => RowAccent::Title,
+            _ => RowAccent::Off,
+        }
+    }
+}
+
+/// What happens to a task title too long for its row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TitleOverflow {
+    /// Cut with an ellipsis so the due date and project stay on screen
+    Truncate,
+    /// Continue the title on a second line
+    Wrap,
+}
+
+impl TitleOverflow {
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "wrap" => TitleOverflow::Wrap,
+            _ => TitleOverflow::Truncate,
+        }
+    }
+}
+
+/// Titles are never squeezed below this many characters, even if the rest of
+/// the row then doesn't fit
+const MIN_TITLE_WIDTH: usize = 10;
+
 pub struct TaskListState {
     pub tasks: Vec<Task>,
     pub list_state: ListState,
     pub focused: bool,
     pub title: String,
+    pub show_notes: bool,
+    /// Show each task's project at the end of the row (off in single-project views)
+    pub show_project: bool,
+    /// Project display labels keyed by project id
+    pub project_labels: HashMap<String, String>,
+    /// Section headers as (index of the section's first task, label)
+    pub sections: Vec<(usize, String)>,
+    /// Fuzzy filter on task titles; `tasks` holds only the matches
+    pub filter: String,
+    /// Show only tasks of this priority
+    pub priority_filter: Option<TaskPriority>,
+    /// All tasks and sections of the view, before filtering
+    source_tasks: Vec<Task>,
+    source_sections: Vec<(usize, String)>,
+    pub row_accent: RowAccent,
+    pub title_overflow: TitleOverflow,
+    /// Widest a title may get, in characters (0: as wide as the pane allows)
+    pub max_title_width: usize,
+    /// Parsed project and tag colors keyed by id, used for the row accent; shared
+    /// by all lists
+    pub project_colors: Rc<HashMap<String, Color>>,
+    pub tag_colors: Rc<HashMap<String, Color>>,
+    /// Ids of the tasks marked for a batch action; marks of tasks that leave the
+    /// view are dropped
+    pub marked: HashSet<String>,
+    /// Ids of the tasks whose checklist is shown inline under the row
+    pub expanded: HashSet<String>,
+    /// Ids of just-completed tasks kept in the list for now, drawn as completed
+    pub struck: HashSet<String>,
 }
 
 impl TaskListState {
@@ -23,12 +99,116 @@ impl TaskListState {
             list_state: ListState::default(),
             focused: false,
             title: title.into(),
+            show_notes: false,
+            show_project: true,
+            project_labels: HashMap::new(),
+            sections: Vec::new(),
+            filter: String::new(),
+            priority_filter: None,
+            source_tasks: Vec::new(),
+            source_sections: Vec::new(),
+            row_accent: RowAccent::Off,
+            title_overflow: TitleOverflow::Truncate,
+            max_title_width: 0,
+            project_colors: Rc::default(),
+            tag_colors: Rc::default(),
+            marked: HashSet::new(),
+            expanded: HashSet::new(),
+            struck: HashSet::new(),
         }
     }
 
     pub fn set_tasks(&mut self, tasks: Vec<Task>) {
+        self.source_tasks = tasks;
+        self.marked.retain(|id| self.source_tasks.iter().any(|t| &t.id == id));
+        self.apply_filter();
+    }
+
+    /// Mark or unmark the selected task and move on to the next one
+    pub fn toggle_marked(&mut self) {
+        let Some(id) = self.selected_task().map(|t| t.id.clone()) else {
+            return;
+        };
+        if !self.marked.remove(&id) {
+            self.marked.insert(id);
+        }
+        self.select_next();
+    }
+
+    /// Show or hide the selected task's checklist items under its row
+    pub fn toggle_expanded(&mut self) {
+        let Some(task) = self.selected_task() else {
+            return;
+        };
+        if checklist(task).is_empty() {
+            return;
+        }
+        let id = task.id.clone();
+        if !self.expanded.remove(&id) {
+            self.expanded.insert(id);
+        }
+    }
+
+    /// Marked tasks in list order, including ones hidden by the filters
+    pub fn marked_tasks(&self) -> Vec<&Task> {
+        self.source_tasks.iter().filter(|t| self.marked.contains(&t.id)).collect()
+    }
+
+    /// Set section headers as (index of the section's first task, label)
+    pub fn set_sections(&mut self, sections: Vec<(usize, String)>) {
+        self.source_sections = sections;
+        self.apply_filter();
+    }
+
+    pub fn set_filter(&mut self, filter: impl Into<String>) {
+        self.filter = filter.into();
+        self.apply_filter();
+    }
+
+    /// Step the priority filter: off → high → medium → low → none → off
+    pub fn cycle_priority_filter(&mut self) {
+        self.priority_filter = match self.priority_filter {
+            None => Some(TaskPriority::High),
+            Some(TaskPriority::High) => Some(TaskPriority::Medium),
+            Some(TaskPriority::Medium) => Some(TaskPriority::Low),
+            Some(TaskPriority::Low) => Some(TaskPriority::None),
+            Some(TaskPriority::None) => None,
+        };
+        self.apply_filter();
+    }
+
+    /// Rebuild the visible tasks and sections from the view's tasks and the filters
+    fn apply_filter(&mut self) {
+        let selected_id = self.selected_task().map(|t| t.id.clone());
+
+        let matching: Vec<usize> = (0..self.source_tasks.len())
+            .filter(|&i| {
+                let task = &self.source_tasks[i];
+                fuzzy_matches(&self.filter, &task.title)
+                    && self.priority_filter.map_or(true, |p| task.priority == p)
+            })
+            .collect();
+        // Sections start at their first matching task; sections with none are dropped
+        self.sections = self
+            .source_sections
+            .iter()
+            .enumerate()
+            .filter_map(|(n, (start, label))| {
+                let end = self.source_sections.get(n + 1).map_or(usize::MAX, |(s, _)| *s);
+                let first = matching.iter().position(|&i| i >= *start && i < end)?;
+                Some((first, label.clone()))
+            })
+            .collect();
+        let tasks: Vec<Task> = matching.iter().map(|&i| self.source_tasks[i].clone()).collect();
         self.tasks = tasks;
-        // Reset selection if out of bounds
+
+        // Keep the same task selected if it's still listed
+        if let Some(pos) = selected_id.and_then(|id| self.tasks.iter().position(|t| t.id == id)) {
+            self.list_state.select(Some(pos));
+            return;
+        }
+
+        // Otherwise keep the index, clamped to the new length
         if let Some(selected) = self.list_state.selected() {
             if selected >= self.tasks.len() {
                 self.list_state.select(if self.tasks.is_empty() {
@@ -48,6 +228,27 @@ impl TaskListState {
             .and_then(|i| self.tasks.get(i))
     }
 
+    /// Ids to try when restoring the selection after the list changes: the selected
+    /// task itself, then the tasks after it, then the tasks before it (nearest first)
+    pub fn selection_fallback_ids(&self) -> Vec<String> {
+        let Some(selected) = self.list_state.selected() else {
+            return Vec::new();
+        };
+        let mut ids: Vec<String> = self.tasks.iter().skip(selected).map(|t| t.id.clone()).collect();
+        ids.extend(self.tasks.iter().take(selected).rev().map(|t| t.id.clone()));
+        ids
+    }
+
+    /// Select the first task from `ids` that is still in the list
+    pub fn select_first_present(&mut self, ids: &[String]) {
+        for id in ids {
+            if let Some(pos) = self.tasks.iter().position(|t| &t.id == id) {
+                self.list_state.select(Some(pos));
+                return;
+            }
+        }
+    }
+
     pub fn select_next(&mut self) {
         if self.tasks.is_empty() {
             return;
@@ -82,6 +283,26 @@ impl TaskListState {
         self.list_state.select(Some(i));
     }
 
+    /// Move the selection to the next (or previous) overdue task, wrapping around
+    pub fn select_overdue(&mut self, forward: bool) {
+        let len = self.tasks.len();
+        if len == 0 {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(if forward { len - 1 } else { 0 });
+        for step in 1..=len {
+            let i = if forward {
+                (current + step) % len
+            } else {
+                (current + len - step % len) % len
+            };
+            if self.tasks[i].is_overdue() {
+                self.list_state.select(Some(i));
+                return;
+            }
+        }
+    }
+
     pub fn select_first(&mut self) {
         if !self.tasks.is_empty() {
             self.list_state.select(Some(0));
@@ -96,16 +317,81 @@ impl TaskListState {
 }
 
 pub fn render_task_list(frame: &mut Frame, area: Rect, state: &mut TaskListState) {
+    // Open tasks' estimates (sizes stand in for missing ones), to judge whether
+    // the list is realistic
+    let estimate: i64 = state
+        .tasks
+        .iter()
+        .filter(|t| t.status.is_open())
+        .filter_map(Task::effective_estimate)
+        .sum();
+    let mut title = if estimate > 0 {
+        format!(" {} ({} · ~{}) ", state.title, state.tasks.len(), format_hours(estimate * 60))
+    } else {
+        format!(" {} ({}) ", state.title, state.tasks.len())
+    };
+    if let Some(priority) = state.priority_filter {
+        title.push_str(&format!("!{} ", priority.as_str()));
+    }
+    if !state.filter.is_empty() {
+        title.push_str(&format!("/{} ", state.filter));
+    }
     let block = Block::default()
-        .title(format!(" {} ({}) ", state.title, state.tasks.len()))
+        .title(title)
         .title_style(Theme::title_style())
         .borders(Borders::ALL)
         .border_style(Theme::border_style(state.focused));
 
-    let items: Vec<ListItem> = state
-        .tasks
+    // Rows are section headers interleaved with tasks; only the rows that fit in
+    // the viewport are turned into list items, so long lists stay cheap to draw
+    let mut rows: Vec<ListRow> = Vec::with_capacity(state.tasks.len() + state.sections.len());
+    let mut sections = state.sections.iter().peekable();
+    let mut selected_row = None;
+    for i in 0..state.tasks.len() {
+        while let Some((_, label)) = sections.next_if(|(start, _)| *start <= i) {
+            rows.push(ListRow::Section(label));
+        }
+        if state.list_state.selected() == Some(i) {
+            selected_row = Some(rows.len());
+        }
+        rows.push(ListRow::Task(i));
+    }
+
+    // Room for a row's spans: the list's inner width minus the highlight symbol
+    let width = block.inner(area).width.saturating_sub(2) as usize;
+    let row_height = |row: &ListRow| match row {
+        ListRow::Task(i) => {
+            let task = &state.tasks[*i];
+            let wrapped = state.title_overflow == TitleOverflow::Wrap
+                && title_layout(&row_parts(task, state), task, width, state.max_title_width).1;
+            let items = if state.expanded.contains(&task.id) {
+                checklist(task).len()
+            } else {
+                0
+            };
+            1 + usize::from(wrapped)
+                + usize::from(state.show_notes && first_note_line(task).is_some())
+                + items
+        }
+        ListRow::Section(_) => 1,
+    };
+    let (first, last) = visible_rows(
+        &rows,
+        row_height,
+        state.list_state.offset(),
+        selected_row,
+        block.inner(area).height as usize,
+    );
+
+    let items: Vec<ListItem> = rows[first..last]
         .iter()
-        .map(|task| create_task_item(task))
+        .map(|row| match *row {
+            ListRow::Section(label) => ListItem::new(Line::from(Span::styled(
+                label.to_string(),
+                Theme::title_style().add_modifier(Modifier::UNDERLINED),
+            ))),
+            ListRow::Task(i) => task_item(&state.tasks[i], state, width),
+        })
         .collect();
 
     let list = List::new(items)
@@ -113,28 +399,251 @@ pub fn render_task_list(frame: &mut Frame, area: Rect, state: &mut TaskListState
         .highlight_style(Theme::selected_style())
         .highlight_symbol("› ");
 
-    frame.render_stateful_widget(list, area, &mut state.list_state);
+    let mut window_state = ListState::default().with_selected(selected_row.map(|row| row - first));
+    frame.render_stateful_widget(list, area, &mut window_state);
+    *state.list_state.offset_mut() = first;
+}
+
+enum ListRow<'a> {
+    Section(&'a str),
+    /// Index into `TaskListState::tasks`
+    Task(usize),
+}
+
+/// The `first..last` range of rows to draw in `height` lines: scrolled from the
+/// previous offset just enough to keep the selected row in view
+fn visible_rows(
+    rows: &[ListRow],
+    row_height: impl Fn(&ListRow) -> usize,
+    offset: usize,
+    selected: Option<usize>,
+    height: usize,
+) -> (usize, usize) {
+    if rows.is_empty() {
+        return (0, 0);
+    }
+    let mut first = offset.min(rows.len() - 1);
+    if let Some(selected) = selected {
+        first = first.min(selected);
+        let mut used: usize = rows[first..=selected].iter().map(&row_height).sum();
+        while used > height && first < selected {
+            used -= row_height(&rows[first]);
+            first += 1;
+        }
+    }
+
+    let mut last = first;
+    let mut used = 0;
+    while last < rows.len() && (last == first || used + row_height(&rows[last]) <= height) {
+        used += row_height(&rows[last]);
+        last += 1;
+    }
+    (first, last)
+}
+
+fn task_item(task: &Task, state: &TaskListState, width: usize) -> ListItem<'static> {
+    let parts = row_parts(task, state);
+    let (budget, overflows) = title_layout(&parts, task, width, state.max_title_width);
+    let lead_width: usize = parts.lead.iter().map(Span::width).sum();
+    // The bar runs down every line of the row, which is indented past it
+    let bar = (state.row_accent == RowAccent::Bar).then(|| parts.lead[0].clone());
+    let bar_width = bar.as_ref().map_or(0, Span::width);
+    let indent = |width: usize| {
+        let mut spans: Vec<Span<'static>> = bar.iter().cloned().collect();
+        spans.push(Span::raw(" ".repeat(width.saturating_sub(bar_width))));
+        spans
+    };
+
+    let mut first = parts.lead;
+    let mut second = None;
+    if !overflows {
+        first.push(Span::styled(task.title.clone(), parts.title_style));
+    } else if state.title_overflow == TitleOverflow::Wrap {
+        let (head, rest) = split_title(&task.title, budget);
+        first.push(Span::styled(head, parts.title_style));
+        let rest = truncate(&rest, width.saturating_sub(lead_width).max(MIN_TITLE_WIDTH));
+        let mut spans = indent(lead_width);
+        spans.push(Span::styled(rest, parts.title_style));
+        second = Some(Line::from(spans));
+    } else {
+        first.push(Span::styled(truncate(&task.title, budget), parts.title_style));
+    }
+    first.extend(parts.trail);
+
+    let mut lines = vec![Line::from(first)];
+    lines.extend(second);
+
+    // Add notes preview line
+    if state.show_notes {
+        if let Some(first_line) = first_note_line(task) {
+            let mut spans = indent(4);
+            spans.push(Span::styled(truncate(first_line.trim(), 60), Theme::muted_style()));
+            lines.push(Line::from(spans));
+        }
+    }
+
+    if state.expanded.contains(&task.id) {
+        for (done, text) in checklist(task) {
+            let style = if done {
+                Theme::muted_style()
+            } else {
+                Style::default()
+            };
+            let mut spans = indent(lead_width.min(6 + bar_width));
+            spans.push(Span::styled(if done { "[x] " } else { "[ ] " }, style));
+            spans.push(Span::styled(
+                truncate(text, width.saturating_sub(10 + bar_width).max(MIN_TITLE_WIDTH)),
+                style,
+            ));
+            lines.push(Line::from(spans));
+        }
+    }
+
+    ListItem::new(lines)
+}
+
+/// Markdown checklist items (`- [ ] ...`, `- [x] ...`) in the task's notes, as
+/// (done, text)
+pub fn checklist(task: &Task) -> Vec<(bool, &str)> {
+    let Some(notes) = task.notes.as_deref() else {
+        return Vec::new();
+    };
+    notes
+        .lines()
+        .filter_map(|line| {
+            let item = line.trim_start();
+            let item = item.strip_prefix("- ").or_else(|| item.strip_prefix("* "))?;
+            if let Some(text) = item.strip_prefix("[ ] ") {
+                Some((false, text.trim()))
+            } else {
+                item.strip_prefix("[x] ")
+                    .or_else(|| item.strip_prefix("[X] "))
+                    .map(|text| (true, text.trim()))
+            }
+        })
+        .collect()
+}
+
+/// A five-cell bar and count for the checklist, e.g. "▓▓▓░░ 3/5"
+fn checklist_progress(items: &[(bool, &str)]) -> String {
+    let done = items.iter().filter(|(done, _)| *done).count();
+    let filled = done * 5 / items.len();
+    format!(
+        "{}{} {}/{}",
+        Theme::icon("▓", "#").repeat(filled),
+        Theme::icon("░", "-").repeat(5 - filled),
+        done,
+        items.len()
+    )
+}
+
+/// Title width available in a row `width` wide, and whether the task's title
+/// exceeds it
+fn title_layout(parts: &RowParts, task: &Task, width: usize, max_title_width: usize) -> (usize, bool) {
+    let used: usize = parts.lead.iter().chain(&parts.trail).map(Span::width).sum();
+    let mut budget = width.saturating_sub(used);
+    if max_title_width > 0 {
+        budget = budget.min(max_title_width);
+    }
+    let budget = budget.max(MIN_TITLE_WIDTH);
+    (budget, task.title.chars().count() > budget)
+}
+
+/// Split a title after at most `at` characters, at the last space if there is one
+fn split_title(title: &str, at: usize) -> (String, String) {
+    let head: String = title.chars().take(at).collect();
+    let cut = head.rfind(' ').filter(|&i| i > 0).unwrap_or(head.len());
+    (title[..cut].to_string(), title[cut..].trim_start().to_string())
+}
+
+/// First non-blank line of the task's notes, shown by the notes preview
+fn first_note_line(task: &Task) -> Option<&str> {
+    task.notes
+        .as_deref()
+        .and_then(|n| n.lines().find(|l| !l.trim().is_empty()))
+}
+
+/// The task's project color, falling back to the color of its first colored tag
+fn accent_color(task: &Task, state: &TaskListState) -> Option<Color> {
+    task.project_id
+        .as_ref()
+        .and_then(|id| state.project_colors.get(id))
+        .or_else(|| task.tags.iter().find_map(|id| state.tag_colors.get(id)))
+        .copied()
+}
+
+/// The bar column of a row: colored, or blank so every row's content starts in
+/// the same column
+fn accent_bar(task: &Task, state: &TaskListState) -> Span<'static> {
+    match accent_color(task, state) {
+        Some(color) => Span::styled(Theme::icon("▎", "|"), Style::default().fg(color)),
+        None => Span::raw(" "),
+    }
+}
+
+/// A task row's spans before and after the title, which is laid out separately
+/// to fit between them
+struct RowParts {
+    lead: Vec<Span<'static>>,
+    title_style: Style,
+    trail: Vec<Span<'static>>,
 }
 
-fn create_task_item(task: &Task) -> ListItem<'static> {
-    let checkbox = if task.is_completed() {
+fn row_parts(task: &Task, state: &TaskListState) -> RowParts {
+    let project_label = if state.show_project {
+        task.project_id
+            .as_ref()
+            .and_then(|id| state.project_labels.get(id))
+            .map(|label| label.as_str())
+    } else {
+        None
+    };
+    let completed = task.is_completed() || state.struck.contains(&task.id);
+    let checkbox = if completed {
         "[x]"
     } else {
         "[ ]"
     };
 
-    let checkbox_style = if task.is_completed() {
+    let checkbox_style = if completed {
         Style::default().fg(Theme::SUCCESS)
     } else {
         Style::default().fg(Theme::FG_DIM)
     };
 
-    let title_style = Theme::status_style(task.is_completed(), task.is_overdue());
+    let mut title_style = Theme::status_style(completed, task.is_overdue() && !completed);
+
+    let mut spans = Vec::new();
+
+    // Add accent: a colored bar, or a tinted title unless completed/overdue
+    match state.row_accent {
+        RowAccent::Bar => spans.push(accent_bar(task, state)),
+        RowAccent::Title if !completed && !task.is_overdue() => {
+            if let Some(color) = accent_color(task, state) {
+                title_style = title_style.fg(color);
+            }
+        }
+        _ => {}
+    }
+
+    if state.marked.contains(&task.id) {
+        spans.push(Span::styled(
+            format!("{} ", Theme::icon("●", "*")),
+            Style::default().fg(Theme::ACCENT),
+        ));
+    }
+
+    spans.push(Span::styled(checkbox, checkbox_style));
+    spans.push(Span::raw(" "));
 
-    let mut spans = vec![
-        Span::styled(checkbox, checkbox_style),
-        Span::raw(" "),
-    ];
+    // Add flag indicator
+    if task.flagged {
+        spans.push(Span::styled(
+            Theme::icon("★", "*"),
+            Style::default().fg(Theme::WARNING),
+        ));
+        spans.push(Span::raw(" "));
+    }
 
     // Add priority indicator
     if task.priority != TaskPriority::None {
@@ -148,7 +657,7 @@ fn create_task_item(task: &Task) -> ListItem<'static> {
     // Add kind indicator
     if let Some(ref kind) = task.kind {
         spans.push(Span::styled(
-            kind.symbol(),
+            Theme::kind_symbol(kind),
             Style::default().fg(Theme::kind_color(kind)),
         ));
         spans.push(Span::raw(" "));
@@ -163,8 +672,14 @@ fn create_task_item(task: &Task) -> ListItem<'static> {
         spans.push(Span::raw(" "));
     }
 
-    // Add title
-    spans.push(Span::styled(task.title.clone(), title_style));
+    let mut trail = Vec::new();
+
+    // Add checklist progress
+    let items = checklist(task);
+    if !items.is_empty() {
+        trail.push(Span::raw(" "));
+        trail.push(Span::styled(checklist_progress(&items), Theme::dimmed_style()));
+    }
 
     // Add due date if present
     if let Some(due) = task.due_date {
@@ -177,9 +692,186 @@ fn create_task_item(task: &Task) -> ListItem<'static> {
         } else {
             Theme::dimmed_style()
         };
-        spans.push(Span::raw(" "));
-        spans.push(Span::styled(format!("({})", due), due_style));
+        trail.push(Span::raw(" "));
+        trail.push(Span::styled(format!("({})", due), due_style));
+    }
+
+    // Add start date (dimmed) while the task is deferred
+    if let Some(start) = task.start_date.filter(|_| task.is_deferred()) {
+        trail.push(Span::raw(" "));
+        trail.push(Span::styled(format!("starts {}", start), Theme::dimmed_style()));
+    }
+
+    // Add project label
+    if let Some(label) = project_label {
+        trail.push(Span::raw(" "));
+        trail.push(Span::styled(label.to_string(), Theme::muted_style()));
+    }
+
+    RowParts {
+        lead: spans,
+        title_style,
+        trail,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+
+    fn list(titles: &[&str]) -> (TaskListState, Vec<Task>) {
+        let tasks: Vec<Task> = titles.iter().map(|t| Task::new(t.to_string())).collect();
+        let mut list = TaskListState::new("Tasks");
+        list.set_tasks(tasks.clone());
+        (list, tasks)
+    }
+
+    fn selected_title(list: &TaskListState) -> Option<&str> {
+        list.selected_task().map(|t| t.title.as_str())
+    }
+
+    fn select(list: &mut TaskListState, title: &str) {
+        let pos = list.tasks.iter().position(|t| t.title == title);
+        list.list_state.select(pos);
+    }
+
+    #[test]
+    fn inserting_at_the_top_keeps_the_selected_task() {
+        let (mut list, mut tasks) = list(&["a", "b", "c"]);
+        select(&mut list, "b");
+
+        tasks.insert(0, Task::new("new".to_string()));
+        list.set_tasks(tasks);
+
+        assert_eq!(selected_title(&list), Some("b"));
+        assert_eq!(list.list_state.selected(), Some(2));
+    }
+
+    #[test]
+    fn removing_another_task_keeps_the_selected_task() {
+        let (mut list, mut tasks) = list(&["a", "b", "c", "d"]);
+        select(&mut list, "c");
+
+        tasks.remove(1);
+        list.set_tasks(tasks);
+
+        assert_eq!(selected_title(&list), Some("c"));
     }
 
-    ListItem::new(Line::from(spans))
+    #[test]
+    fn removing_the_selected_task_moves_to_the_next_one() {
+        let (mut list, mut tasks) = list(&["a", "b", "c", "d"]);
+        select(&mut list, "b");
+        let fallback = list.selection_fallback_ids();
+
+        tasks.remove(1);
+        list.set_tasks(tasks);
+        list.select_first_present(&fallback);
+
+        assert_eq!(selected_title(&list), Some("c"));
+    }
+
+    #[test]
+    fn removing_the_selected_last_task_moves_to_the_previous_one() {
+        let (mut list, mut tasks) = list(&["a", "b", "c"]);
+        select(&mut list, "c");
+        let fallback = list.selection_fallback_ids();
+        assert_eq!(fallback, vec![tasks[2].id.clone(), tasks[1].id.clone(), tasks[0].id.clone()]);
+
+        tasks.pop();
+        list.set_tasks(tasks);
+        list.select_first_present(&fallback);
+
+        assert_eq!(selected_title(&list), Some("b"));
+    }
+
+    fn long_list() -> TaskListState {
+        let tasks: Vec<Task> = (0..5000)
+            .map(|i| {
+                let mut task = Task::new(format!("task {:04}", i));
+                task.notes = Some(format!("note for task {:04}", i));
+                task
+            })
+            .collect();
+        let mut list = TaskListState::new("Tasks");
+        list.show_notes = true;
+        list.set_tasks(tasks);
+        list
+    }
+
+    fn draw(terminal: &mut Terminal<TestBackend>, list: &mut TaskListState) -> String {
+        terminal
+            .draw(|frame| render_task_list(frame, frame.size(), list))
+            .unwrap();
+        terminal.backend().buffer().content.iter().map(|c| c.symbol()).collect()
+    }
+
+    #[test]
+    fn long_lists_draw_the_rows_around_the_selection() {
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        let mut list = long_list();
+
+        let screen = draw(&mut terminal, &mut list);
+        assert!(screen.contains("task 0000"));
+
+        list.list_state.select(Some(4000));
+        let screen = draw(&mut terminal, &mut list);
+        assert!(screen.contains("task 4000"));
+        assert!(!screen.contains("task 0000"));
+        assert!(list.list_state.offset() > 3900);
+    }
+
+    /// Timing for a 5000-task list; run with
+    /// `cargo test --release -- --ignored --nocapture long_list_draw_timing`
+    #[test]
+    #[ignore]
+    fn long_list_draw_timing() {
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        let mut list = long_list();
+        let frames = 200;
+        let start = std::time::Instant::now();
+        for i in 0..frames {
+            list.list_state.select(Some(i * 25));
+            draw(&mut terminal, &mut list);
+        }
+        let elapsed = start.elapsed();
+        println!(
+            "{} frames of 5000 tasks: {:?} ({:?} per frame)",
+            frames,
+            elapsed,
+            elapsed / frames as u32
+        );
+    }
+
+    #[test]
+    fn bar_accent_keeps_rows_aligned() {
+        let mut colored = Task::new("colored task with a title long enough to wrap".to_string());
+        colored.project_id = Some("p1".to_string());
+        colored.notes = Some("a note".to_string());
+        let plain = Task::new("plain".to_string());
+        let mut list = TaskListState::new("Tasks");
+        list.set_tasks(vec![colored, plain]);
+        list.row_accent = RowAccent::Bar;
+        list.title_overflow = TitleOverflow::Wrap;
+        list.show_notes = true;
+        list.project_colors = Rc::new(HashMap::from([("p1".to_string(), Color::Red)]));
+
+        let mut terminal = Terminal::new(TestBackend::new(34, 8)).unwrap();
+        draw(&mut terminal, &mut list);
+        let buffer = terminal.backend().buffer();
+        let row = |y: u16| -> String { (0..34).map(|x| buffer.get(x, y).symbol()).collect() };
+        let column = |text: &str, pat: &str| text.find(pat).map(|i| text[..i].chars().count());
+
+        let bar = Theme::icon("▎", "|");
+        let bar_column = column(&row(1), bar).unwrap();
+        assert_eq!(column(&row(1), "[ ]"), Some(bar_column + 1));
+        // The wrapped title and the note continue the bar
+        assert_eq!(column(&row(2), bar), Some(bar_column));
+        assert_eq!(column(&row(3), bar), Some(bar_column));
+        assert_eq!(column(&row(3), "a note"), Some(bar_column + 4));
+        // A row without a color keeps the bar's column blank
+        assert_eq!(column(&row(4), "[ ]"), Some(bar_column + 1));
+    }
 }
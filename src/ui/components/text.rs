@@ -0,0 +1,22 @@
+/// Cut `s` to at most `max_len` characters, ending in "..." when it was cut
+pub fn truncate(s: &str, max_len: usize) -> String {
+    if s.chars().count() <= max_len {
+        s.to_string()
+    } else {
+        let cut: String = s.chars().take(max_len.saturating_sub(3)).collect();
+        format!("{}...", cut)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_counts_characters_not_bytes() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("a longer title", 10), "a longe...");
+        assert_eq!(truncate("ünïcödé tïtlé", 8), "ünïcö...");
+        assert_eq!(truncate("abcdef", 2), "...");
+    }
+}
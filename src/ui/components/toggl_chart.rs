@@ -7,12 +7,16 @@ use ratatui::{
     Frame,
 };
 
+use chrono::{Duration, NaiveDate, Utc, Weekday};
+
 use crate::services::{format_hours, TogglData, TogglTimeEntry};
+use crate::ui::components::truncate;
 use crate::ui::theme::Theme;
 
 pub struct TogglChartState {
     pub data: TogglData,
-    pub days: i64,
+    /// First day of the week shown by the duration chart
+    pub week_start: Weekday,
     pub focused: bool,
 }
 
@@ -20,7 +24,7 @@ impl Default for TogglChartState {
     fn default() -> Self {
         Self {
             data: TogglData::default(),
-            days: 7,
+            week_start: Weekday::Mon,
             focused: false,
         }
     }
@@ -28,7 +32,7 @@ impl Default for TogglChartState {
 
 pub fn render_toggl_view(frame: &mut Frame, area: Rect, state: &TogglChartState) {
     let chunks = Layout::vertical([
-        Constraint::Length(12), // Bar chart
+        Constraint::Length(13), // Week comparison + bar chart
         Constraint::Min(5),     // Entries list
         Constraint::Length(8),  // Project distribution
     ])
@@ -40,21 +44,34 @@ pub fn render_toggl_view(frame: &mut Frame, area: Rect, state: &TogglChartState)
 }
 
 fn render_duration_chart(frame: &mut Frame, area: Rect, state: &TogglChartState) {
+    // The calendar week containing today; days still ahead show as empty bars
+    let today = Utc::now().date_naive();
+    let week_start = today.week(state.week_start).first_day();
+    let days: Vec<_> = (0..7).map(|i| week_start + Duration::days(i)).collect();
+    let total: i64 = days
+        .iter()
+        .filter(|date| **date <= today)
+        .map(|date| state.data.duration_for_date(*date))
+        .sum();
+
     let block = Block::default()
-        .title(" Duration by Day ")
+        .title(format!(" This Week: {} ", format_hours(total)))
         .title_style(Theme::title_style())
         .borders(Borders::ALL)
         .border_style(Theme::border_style(state.focused));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
+    let [comparison_area, chart_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+    frame.render_widget(
+        Paragraph::new(week_comparison(&state.data, week_start, today)),
+        comparison_area,
+    );
 
-    // Get last N days of data
-    let today = chrono::Utc::now().date_naive();
     let mut bars: Vec<Bar> = Vec::new();
 
-    for i in (0..state.days).rev() {
-        let date = today - chrono::Duration::days(i);
+    for date in days {
         let duration = state.data.duration_for_date(date);
         let hours = duration as f64 / 3600.0;
 
@@ -80,7 +97,39 @@ fn render_duration_chart(frame: &mut Frame, area: Rect, state: &TogglChartState)
         .data(BarGroup::default().bars(&bars))
         .max(100); // Max 10 hours
 
-    frame.render_widget(bar_chart, inner);
+    frame.render_widget(bar_chart, chart_area);
+}
+
+/// This week so far against the same days of last week, with last week's total
+fn week_comparison(data: &TogglData, week_start: NaiveDate, today: NaiveDate) -> Line<'static> {
+    let last_week_start = week_start - Duration::days(7);
+    let this_week = data.duration_between(week_start, today);
+    let last_week_so_far = data.duration_between(last_week_start, today - Duration::days(7));
+    let last_week = data.duration_between(last_week_start, week_start - Duration::days(1));
+
+    let mut spans = vec![Span::styled(
+        format!(" Last week: {} ", format_hours(last_week)),
+        Style::default().fg(Theme::FG_DIM),
+    )];
+    if last_week_so_far == 0 {
+        spans.push(Span::styled("(no time by this day)", Theme::muted_style()));
+        return Line::from(spans);
+    }
+    let change = (this_week - last_week_so_far) as f64 / last_week_so_far as f64 * 100.0;
+    let (arrow, color) = if this_week >= last_week_so_far {
+        (Theme::icon("▲", "^"), Theme::SUCCESS)
+    } else {
+        (Theme::icon("▼", "v"), Theme::ERROR)
+    };
+    spans.push(Span::styled(
+        format!("{} {:+.0}%", arrow, change),
+        Style::default().fg(color),
+    ));
+    spans.push(Span::styled(
+        format!(" vs {} by this day", format_hours(last_week_so_far)),
+        Theme::muted_style(),
+    ));
+    Line::from(spans)
 }
 
 fn render_entries_list(frame: &mut Frame, area: Rect, state: &TogglChartState) {
@@ -149,7 +198,7 @@ fn create_entry_item(entry: &TogglTimeEntry) -> ListItem<'static> {
 
 fn render_project_distribution(frame: &mut Frame, area: Rect, state: &TogglChartState) {
     let block = Block::default()
-        .title(" Project Distribution ")
+        .title(" Project Distribution (this week) ")
         .title_style(Theme::title_style())
         .borders(Borders::ALL)
         .border_style(Theme::border_style(false));
@@ -157,7 +206,8 @@ fn render_project_distribution(frame: &mut Frame, area: Rect, state: &TogglChart
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let by_project = state.data.duration_by_project();
+    let week_start = Utc::now().date_naive().week(state.week_start).first_day();
+    let by_project = state.data.duration_by_project(week_start);
     let total: i64 = by_project.iter().map(|(_, d)| *d).sum();
 
     if total == 0 {
@@ -166,20 +216,41 @@ fn render_project_distribution(frame: &mut Frame, area: Rect, state: &TogglChart
         return;
     }
 
-    let mut lines: Vec<Line> = Vec::new();
     let colors = [Theme::PRIMARY, Theme::SECONDARY, Theme::ACCENT, Theme::WARNING, Theme::INFO];
+    let width = inner.width as usize;
+    let legend_rows = (inner.height as usize).saturating_sub(1);
 
-    for (i, (project, duration)) in by_project.iter().take(5).enumerate() {
-        let percentage = (*duration as f64 / total as f64 * 100.0) as u16;
-        let bar_width = (percentage as usize * inner.width as usize / 100).min(inner.width as usize - 25);
-        let color = colors[i % colors.len()];
+    // Name at most 5 projects (one legend row each); the rest share an "…and N more" row
+    let mut shown = by_project.len().min(colors.len()).min(legend_rows);
+    if shown < by_project.len() {
+        shown = shown.saturating_sub(1);
+    }
+
+    // One stacked bar; segment ends are rounded from the running total so the
+    // segments always add up to exactly the available width
+    let mut bar: Vec<Span> = Vec::new();
+    let mut elapsed: i64 = 0;
+    let mut drawn: usize = 0;
+    let segments = by_project.iter().map(|(_, d)| *d).enumerate();
+    for (i, duration) in segments {
+        elapsed += duration;
+        let end = (elapsed as u128 * width as u128 / total as u128) as usize;
+        let color = if i < shown { colors[i] } else { Theme::FG_MUTED };
+        bar.push(Span::styled(
+            symbols::block::FULL.repeat(end.saturating_sub(drawn)),
+            Style::default().fg(color),
+        ));
+        drawn = drawn.max(end);
+    }
 
+    let mut lines: Vec<Line> = vec![Line::from(bar)];
+
+    for (i, (project, duration)) in by_project.iter().take(shown).enumerate() {
+        let percentage = *duration as f64 / total as f64 * 100.0;
         lines.push(Line::from(vec![
-            Span::styled(format!("{:>15} ", truncate(project, 15)), Theme::dimmed_style()),
-            Span::styled(
-                symbols::block::FULL.repeat(bar_width),
-                Style::default().fg(color),
-            ),
+            Span::styled(symbols::block::FULL, Style::default().fg(colors[i])),
+            Span::raw(" "),
+            Span::styled(truncate(project, 20), Theme::dimmed_style()),
             Span::styled(
                 format!(" {} ({:.0}%)", format_hours(*duration), percentage),
                 Style::default().fg(Theme::FG_DIM),
@@ -187,14 +258,23 @@ fn render_project_distribution(frame: &mut Frame, area: Rect, state: &TogglChart
         ]));
     }
 
+    if shown < by_project.len() && legend_rows > 0 {
+        let rest = &by_project[shown..];
+        let rest_duration: i64 = rest.iter().map(|(_, d)| *d).sum();
+        lines.push(Line::from(vec![
+            Span::styled(symbols::block::FULL, Style::default().fg(Theme::FG_MUTED)),
+            Span::styled(
+                format!(
+                    " …and {} more {} ({:.0}%)",
+                    rest.len(),
+                    format_hours(rest_duration),
+                    rest_duration as f64 / total as f64 * 100.0
+                ),
+                Theme::muted_style(),
+            ),
+        ]));
+    }
+
     let para = Paragraph::new(lines);
     frame.render_widget(para, inner);
 }
-
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
-    }
-}
@@ -0,0 +1,134 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::models::Task;
+use crate::ui::components::markdown_lines;
+use crate::ui::theme::Theme;
+
+pub struct TriageContext<'a> {
+    pub task: &'a Task,
+    /// 1-based position of the task in the inbox, and the inbox size
+    pub position: usize,
+    pub total: usize,
+    pub project_name: Option<&'a str>,
+    pub tag_names: Vec<&'a str>,
+    pub markdown_notes: bool,
+}
+
+/// Full-screen card for processing one inbox task at a time
+pub fn render_triage(frame: &mut Frame, area: Rect, ctx: &TriageContext) {
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Inbox triage ({} of {}) ", ctx.position, ctx.total))
+        .title_style(Theme::title_style())
+        .borders(Borders::ALL)
+        .border_style(Theme::border_style(true))
+        .style(Style::default().bg(Theme::BG));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(2), // Title
+        Constraint::Length(5), // Fields
+        Constraint::Min(1),    // Notes
+        Constraint::Length(4), // Keys
+    ])
+    .split(inner);
+
+    let title = Paragraph::new(Span::styled(
+        ctx.task.title.clone(),
+        Style::default().fg(Theme::FG).add_modifier(Modifier::BOLD),
+    ))
+    .wrap(Wrap { trim: false });
+    frame.render_widget(title, chunks[0]);
+
+    let field = |label: &str, value: String, style: Style| {
+        Line::from(vec![
+            Span::styled(format!("{:<10}", label), Theme::dimmed_style()),
+            Span::styled(value, style),
+        ])
+    };
+    let tags = if ctx.tag_names.is_empty() {
+        "none".to_string()
+    } else {
+        ctx.tag_names.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ")
+    };
+    let fields = vec![
+        field(
+            "Project",
+            ctx.project_name.unwrap_or("none").to_string(),
+            Style::default().fg(Theme::FG),
+        ),
+        field("Tags", tags, Style::default().fg(Theme::FG)),
+        field(
+            "Priority",
+            ctx.task.priority.as_str().to_string(),
+            Style::default().fg(Theme::priority_color(&ctx.task.priority)),
+        ),
+        field(
+            "Due",
+            ctx.task.due_date.map(|d| d.to_string()).unwrap_or_else(|| "none".to_string()),
+            Style::default().fg(Theme::FG),
+        ),
+        field(
+            "Flagged",
+            if ctx.task.flagged { "yes" } else { "no" }.to_string(),
+            Style::default().fg(Theme::FG),
+        ),
+    ];
+    frame.render_widget(Paragraph::new(fields), chunks[1]);
+
+    if let Some(ref notes) = ctx.task.notes {
+        let notes_para = if ctx.markdown_notes {
+            Paragraph::new(markdown_lines(notes, Theme::dimmed_style()))
+        } else {
+            Paragraph::new(notes.as_str()).style(Theme::dimmed_style())
+        }
+        .wrap(Wrap { trim: false });
+        frame.render_widget(notes_para, chunks[2]);
+    }
+
+    let key = |k: &'static str| {
+        Span::styled(k, Style::default().fg(Theme::PRIMARY).add_modifier(Modifier::BOLD))
+    };
+    let keys = vec![
+        Line::from(vec![
+            Span::styled("Move to  ", Theme::dimmed_style()),
+            key("a"),
+            Span::raw(" active  "),
+            key("s"),
+            Span::raw(" scheduled  "),
+            key("d"),
+            Span::raw(" done  "),
+            key("x"),
+            Span::raw(" cancelled"),
+        ]),
+        Line::from(vec![
+            Span::styled("Set      ", Theme::dimmed_style()),
+            key("p"),
+            Span::raw(" project  "),
+            key("t"),
+            Span::raw(" tag  "),
+            key("u"),
+            Span::raw(" due  "),
+            key("1-4"),
+            Span::raw(" priority  "),
+            key("f"),
+            Span::raw(" flag"),
+        ]),
+        Line::from(vec![
+            Span::styled("         ", Theme::dimmed_style()),
+            key("j/k"),
+            Span::raw(" skip/back  "),
+            key("Esc"),
+            Span::raw(" exit triage"),
+        ]),
+    ];
+    frame.render_widget(Paragraph::new(keys), chunks[3]);
+}
@@ -0,0 +1,44 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::ui::theme::Theme;
+
+pub struct ReviewProgress {
+    /// 1-based step number and the number of steps
+    pub step: usize,
+    pub total: usize,
+    pub label: String,
+}
+
+/// One-line step indicator shown above the list during a weekly review
+pub fn render_review_progress(frame: &mut Frame, area: Rect, progress: &ReviewProgress) {
+    let key_style = Style::default().fg(Theme::PRIMARY).add_modifier(Modifier::BOLD);
+    let dots: String = (1..=progress.total)
+        .map(|i| if i <= progress.step { Theme::icon("●", "*") } else { Theme::icon("○", ".") })
+        .collect();
+
+    let line = Line::from(vec![
+        Span::styled(" Weekly review ", Theme::title_style()),
+        Span::styled(dots, Style::default().fg(Theme::PRIMARY)),
+        Span::styled(
+            format!(" {}/{}  ", progress.step, progress.total),
+            Theme::dimmed_style(),
+        ),
+        Span::styled(progress.label.clone(), Style::default().fg(Theme::FG)),
+        Span::styled("  │  ", Style::default().fg(Theme::BORDER)),
+        Span::styled("w", key_style),
+        Span::styled(" next step  ", Style::default().fg(Theme::FG_DIM)),
+        Span::styled("W", key_style),
+        Span::styled(" stop", Style::default().fg(Theme::FG_DIM)),
+    ]);
+
+    frame.render_widget(
+        Paragraph::new(line).style(Style::default().bg(Theme::BG_SECONDARY)),
+        area,
+    );
+}
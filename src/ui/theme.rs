@@ -1,5 +1,77 @@
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::RwLock;
+
+use ratatui::buffer::Cell;
 use ratatui::style::{Color, Modifier, Style};
 
+use crate::config::{Config, CustomKind};
+use crate::models::{Project, TaskKind};
+
+static ASCII_ICONS: AtomicBool = AtomicBool::new(false);
+static PALETTE: AtomicU8 = AtomicU8::new(Palette::TrueColor as u8);
+static CUSTOM_KINDS: RwLock<Vec<CustomKind>> = RwLock::new(Vec::new());
+
+/// Which glyph set to draw icons with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IconStyle {
+    NerdFont,
+    Ascii,
+}
+
+impl IconStyle {
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "ascii" => IconStyle::Ascii,
+            _ => IconStyle::NerdFont,
+        }
+    }
+}
+
+/// How Theme colors are drawn on the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Palette {
+    /// The RGB theme colors as-is
+    TrueColor = 0,
+    /// Nearest of the 16 standard ANSI colors
+    Ansi16 = 1,
+    /// No colors, only modifiers (bold, reversed, underline)
+    Monochrome = 2,
+    /// Nearest of the xterm 256-color palette
+    Ansi256 = 3,
+}
+
+impl Palette {
+    /// Pick the palette from the `color_mode` config ("auto", "truecolor", "256" or "16"),
+    /// detecting color support from COLORTERM and TERM in auto mode
+    pub fn from_config(color_mode: &str, monochrome: bool) -> Self {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        if monochrome || no_color {
+            return Palette::Monochrome;
+        }
+        match color_mode {
+            "truecolor" => Palette::TrueColor,
+            "256" => Palette::Ansi256,
+            "16" => Palette::Ansi16,
+            _ => Self::detect(
+                &std::env::var("COLORTERM").unwrap_or_default(),
+                &std::env::var("TERM").unwrap_or_default(),
+            ),
+        }
+    }
+
+    /// True color when COLORTERM says so or TERM names a direct-color terminal,
+    /// 256 colors for a `*-256color` TERM, else 16 colors
+    fn detect(colorterm: &str, term: &str) -> Self {
+        if matches!(colorterm, "truecolor" | "24bit") || term.ends_with("-direct") {
+            Palette::TrueColor
+        } else if term.ends_with("256color") {
+            Palette::Ansi256
+        } else {
+            Palette::Ansi16
+        }
+    }
+}
+
 /// App color theme
 pub struct Theme;
 
@@ -28,6 +100,15 @@ impl Theme {
     pub const PRIORITY_LOW: Color = Color::Rgb(20, 140, 50);     // Green
     pub const PRIORITY_NONE: Color = Color::Rgb(100, 100, 100);  // Gray
 
+    // Completion heatmap, from no completions to the busiest days
+    pub const HEAT: [Color; 5] = [
+        Color::Rgb(225, 225, 225), // Light gray
+        Color::Rgb(160, 215, 170),
+        Color::Rgb(90, 180, 110),
+        Color::Rgb(40, 140, 65),
+        Color::Rgb(15, 90, 40),    // Dark green
+    ];
+
     // Task kind colors - vivid and distinct
     pub const KIND_TASK: Color = Color::Rgb(0, 90, 180);      // Blue
     pub const KIND_BUG: Color = Color::Rgb(190, 30, 30);      // Red
@@ -42,6 +123,225 @@ impl Theme {
     pub const SELECTION_BG: Color = Color::Rgb(0, 90, 180);
     pub const SELECTION_FG: Color = Color::Rgb(255, 255, 255);
 
+    /// Apply the display settings from the config (icons, palette and custom kinds)
+    pub fn apply_config(config: &Config) {
+        Self::set_icon_style(IconStyle::from_str(&config.icon_style));
+        Self::set_palette(Palette::from_config(&config.color_mode, config.monochrome));
+        if let Ok(mut kinds) = CUSTOM_KINDS.write() {
+            *kinds = config.custom_kinds.clone();
+        }
+    }
+
+    /// The config's definition of a kind, if any; names match ignoring surrounding
+    /// whitespace, as in `kind_choices`
+    fn custom_kind(kind: &TaskKind) -> Option<CustomKind> {
+        let kinds = CUSTOM_KINDS.read().ok()?;
+        kinds.iter().find(|k| k.name.trim() == kind.as_str().trim()).cloned()
+    }
+
+    /// Kinds the task form cycles through: the built-in ones, then the config's
+    pub fn kind_choices() -> Vec<TaskKind> {
+        let mut choices = TaskKind::BUILT_IN.to_vec();
+        if let Ok(kinds) = CUSTOM_KINDS.read() {
+            for kind in kinds.iter().filter_map(|k| TaskKind::from_str(k.name.trim())) {
+                if !choices.contains(&kind) {
+                    choices.push(kind);
+                }
+            }
+        }
+        choices
+    }
+
+    /// List symbol of a kind, as configured or the built-in default
+    pub fn kind_symbol(kind: &TaskKind) -> String {
+        Self::custom_kind(kind)
+            .and_then(|k| k.symbol)
+            .unwrap_or_else(|| kind.symbol().to_string())
+    }
+
+    /// Select the icon set used by all components (set once at startup)
+    pub fn set_icon_style(style: IconStyle) {
+        ASCII_ICONS.store(style == IconStyle::Ascii, Ordering::Relaxed);
+    }
+
+    pub fn icon_style() -> IconStyle {
+        if ASCII_ICONS.load(Ordering::Relaxed) {
+            IconStyle::Ascii
+        } else {
+            IconStyle::NerdFont
+        }
+    }
+
+    /// Select the palette used to draw all components (set once at startup)
+    pub fn set_palette(palette: Palette) {
+        PALETTE.store(palette as u8, Ordering::Relaxed);
+    }
+
+    pub fn palette() -> Palette {
+        match PALETTE.load(Ordering::Relaxed) {
+            1 => Palette::Ansi16,
+            2 => Palette::Monochrome,
+            3 => Palette::Ansi256,
+            _ => Palette::TrueColor,
+        }
+    }
+
+    /// Convert a rendered cell's colors to the active palette
+    pub fn apply_palette(cell: &mut Cell) {
+        match Self::palette() {
+            Palette::TrueColor => {}
+            Palette::Ansi16 => {
+                cell.fg = Self::ansi16(cell.fg, false);
+                cell.bg = Self::ansi16(cell.bg, true);
+            }
+            Palette::Ansi256 => {
+                cell.fg = Self::ansi256(cell.fg);
+                cell.bg = Self::ansi256(cell.bg);
+            }
+            Palette::Monochrome => Self::to_monochrome(cell),
+        }
+    }
+
+    /// Nearest ANSI color for a theme color. Backgrounds and body text use the
+    /// terminal's own colors so the UI works on both light and dark terminals.
+    fn ansi16(color: Color, background: bool) -> Color {
+        match color {
+            c if c == Self::BG || c == Self::BG_SECONDARY => {
+                // As text, BG only appears on colored backgrounds (e.g. selection)
+                if background {
+                    Color::Reset
+                } else {
+                    Color::White
+                }
+            }
+            c if c == Self::FG => {
+                if background {
+                    Color::Gray
+                } else {
+                    Color::Reset
+                }
+            }
+            c if c == Self::FG_DIM || c == Self::FG_MUTED || c == Self::BORDER => Color::DarkGray,
+            c if c == Self::PRIMARY => Color::Blue,
+            c if c == Self::SECONDARY => Color::Green,
+            c if c == Self::ACCENT => Color::Magenta,
+            c if c == Self::WARNING => Color::Yellow,
+            c if c == Self::ERROR => Color::Red,
+            Color::Rgb(r, g, b) => {
+                // Any other RGB: pick the dominant channel
+                let max = r.max(g).max(b);
+                if max < 64 {
+                    Color::Black
+                } else if r == max && g == max {
+                    Color::Yellow
+                } else if r == max && b == max {
+                    Color::Magenta
+                } else if g == max && b == max {
+                    Color::Cyan
+                } else if r == max {
+                    Color::Red
+                } else if g == max {
+                    Color::Green
+                } else {
+                    Color::Blue
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// Nearest xterm 256-color index for an RGB color: the closer of the 6x6x6
+    /// color cube entry and the gray ramp entry
+    fn ansi256(color: Color) -> Color {
+        let Color::Rgb(r, g, b) = color else {
+            return color;
+        };
+        const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        let level = |v: u8| match v {
+            0..=47 => 0,
+            48..=114 => 1,
+            _ => (v - 35) / 40,
+        };
+        let (cr, cg, cb) = (level(r), level(g), level(b));
+        let average = (r as u16 + g as u16 + b as u16) / 3;
+        let gray = if average > 238 { 23 } else { average.saturating_sub(3) / 10 } as u8;
+        let gray_value = 8 + 10 * gray;
+
+        let distance = |(x, y, z): (u8, u8, u8)| {
+            [(x, r), (y, g), (z, b)]
+                .iter()
+                .map(|&(entry, actual)| (entry as i32 - actual as i32).pow(2))
+                .sum::<i32>()
+        };
+        let cube = (CUBE[cr as usize], CUBE[cg as usize], CUBE[cb as usize]);
+        if distance((gray_value, gray_value, gray_value)) < distance(cube) {
+            Color::Indexed(232 + gray)
+        } else {
+            Color::Indexed(16 + 36 * cr + 6 * cg + cb)
+        }
+    }
+
+    /// Replace a rendered cell's colors with modifiers so that selection,
+    /// errors and dimmed text stay distinguishable without color
+    fn to_monochrome(cell: &mut Cell) {
+        let mut modifier = cell.modifier;
+        match cell.bg {
+            Color::Reset => {}
+            c if c == Self::BG || c == Self::BG_SECONDARY => {}
+            _ => modifier |= Modifier::REVERSED,
+        }
+        match cell.fg {
+            c if c == Self::ERROR => modifier |= Modifier::BOLD | Modifier::UNDERLINED,
+            c if (c == Self::FG_DIM || c == Self::FG_MUTED || c == Self::BORDER)
+                && !modifier.contains(Modifier::REVERSED) =>
+            {
+                modifier |= Modifier::DIM
+            }
+            _ => {}
+        }
+        cell.set_style(
+            Style::default()
+                .fg(Color::Reset)
+                .bg(Color::Reset)
+                .add_modifier(modifier),
+        );
+    }
+
+    /// Frame of the loading spinner for the given main-loop tick
+    pub fn spinner(tick: usize) -> &'static str {
+        const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+        const ASCII_FRAMES: [&str; 4] = ["|", "/", "-", "\\"];
+        if Self::icon_style() == IconStyle::Ascii {
+            ASCII_FRAMES[tick % ASCII_FRAMES.len()]
+        } else {
+            FRAMES[tick % FRAMES.len()]
+        }
+    }
+
+    /// Pick between a Nerd Font glyph and its ASCII fallback
+    pub fn icon(nerdfont: &'static str, ascii: &'static str) -> &'static str {
+        match Self::icon_style() {
+            IconStyle::NerdFont => nerdfont,
+            IconStyle::Ascii => ascii,
+        }
+    }
+
+    /// Parse a user-supplied color: "#rrggbb", a color name ("red", "lightblue")
+    /// or an ANSI index ("208")
+    pub fn parse_color(s: &str) -> Option<Color> {
+        s.trim().parse().ok()
+    }
+
+    /// Project icon, replacing non-ASCII icons when the ASCII icon set is active
+    pub fn project_icon(project: &Project) -> &str {
+        let icon = project.display_icon();
+        if Self::icon_style() == IconStyle::Ascii && !icon.is_ascii() {
+            "-"
+        } else {
+            icon
+        }
+    }
+
     // Styles
     pub fn default_style() -> Style {
         Style::default().fg(Self::FG).bg(Self::BG)
@@ -92,8 +392,24 @@ impl Theme {
         }
     }
 
-    pub fn kind_color(kind: &crate::models::TaskKind) -> Color {
-        use crate::models::TaskKind;
+    pub fn status_color(status: &crate::models::TaskStatus) -> Color {
+        use crate::models::TaskStatus;
+        match status {
+            TaskStatus::Inbox => Self::INFO,
+            TaskStatus::Active => Self::ACCENT,
+            TaskStatus::Scheduled => Self::WARNING,
+            TaskStatus::Completed => Self::SUCCESS,
+            TaskStatus::Cancelled => Self::FG_MUTED,
+        }
+    }
+
+    pub fn kind_color(kind: &TaskKind) -> Color {
+        if let Some(color) = Self::custom_kind(kind)
+            .and_then(|k| k.color)
+            .and_then(|c| Self::parse_color(&c))
+        {
+            return color;
+        }
         match kind {
             TaskKind::Task => Self::KIND_TASK,
             TaskKind::Bug => Self::KIND_BUG,
@@ -102,6 +418,7 @@ impl Theme {
             TaskKind::GhIssue => Self::KIND_BUG,     // Red - like bugs
             TaskKind::GhPr => Self::KIND_FEATURE,    // Green - like features
             TaskKind::GhReview => Self::KIND_CHORE,  // Orange - like chores
+            TaskKind::Custom(_) => Self::FG_DIM,
         }
     }
 
@@ -122,13 +439,20 @@ impl Theme {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SidebarItem {
     Inbox,
+    Active,
+    Scheduled,
     Today,
+    Agenda,
+    Week,
     Upcoming,
     Anytime,
+    Flagged,
+    Deferred,
     Completed,
     Review,
     GitHub,
     Toggl,
+    Stats,
     Settings,
 }
 
@@ -136,55 +460,131 @@ impl SidebarItem {
     pub fn all() -> &'static [SidebarItem] {
         &[
             SidebarItem::Inbox,
+            SidebarItem::Active,
+            SidebarItem::Scheduled,
             SidebarItem::Today,
+            SidebarItem::Agenda,
+            SidebarItem::Week,
             SidebarItem::Upcoming,
             SidebarItem::Anytime,
+            SidebarItem::Flagged,
+            SidebarItem::Deferred,
             SidebarItem::Completed,
             SidebarItem::Review,
             SidebarItem::GitHub,
             SidebarItem::Toggl,
+            SidebarItem::Stats,
             SidebarItem::Settings,
         ]
     }
 
+    /// The item named like its view in the config, e.g. "inbox" or "github"
+    pub fn from_name(name: &str) -> Option<Self> {
+        let item = match name.trim().to_lowercase().as_str() {
+            "inbox" => SidebarItem::Inbox,
+            "active" => SidebarItem::Active,
+            "scheduled" => SidebarItem::Scheduled,
+            "today" => SidebarItem::Today,
+            "agenda" => SidebarItem::Agenda,
+            "week" => SidebarItem::Week,
+            "upcoming" => SidebarItem::Upcoming,
+            "anytime" => SidebarItem::Anytime,
+            "flagged" => SidebarItem::Flagged,
+            "deferred" => SidebarItem::Deferred,
+            "completed" => SidebarItem::Completed,
+            "review" => SidebarItem::Review,
+            "github" => SidebarItem::GitHub,
+            "toggl" => SidebarItem::Toggl,
+            "stats" => SidebarItem::Stats,
+            "settings" => SidebarItem::Settings,
+            _ => return None,
+        };
+        Some(item)
+    }
+
     pub fn label(&self) -> &'static str {
         match self {
             SidebarItem::Inbox => "Inbox",
+            SidebarItem::Active => "Active",
+            SidebarItem::Scheduled => "Scheduled",
             SidebarItem::Today => "Today",
+            SidebarItem::Agenda => "Agenda",
+            SidebarItem::Week => "This Week",
             SidebarItem::Upcoming => "Upcoming",
             SidebarItem::Anytime => "Anytime",
+            SidebarItem::Flagged => "Flagged",
+            SidebarItem::Deferred => "Deferred",
             SidebarItem::Completed => "Completed",
             SidebarItem::Review => "Review",
             SidebarItem::GitHub => "GitHub",
             SidebarItem::Toggl => "Toggl",
+            SidebarItem::Stats => "Stats",
             SidebarItem::Settings => "Settings",
         }
     }
 
     pub fn icon(&self) -> &'static str {
+        if Theme::icon_style() == IconStyle::Ascii {
+            return self.ascii_icon();
+        }
         match self {
             SidebarItem::Inbox => "󰇯",
+            SidebarItem::Active => "󰐊",
+            SidebarItem::Scheduled => "󰃰",
             SidebarItem::Today => "󰃭",
+            SidebarItem::Agenda => "󰃶",
+            SidebarItem::Week => "󰸗",
             SidebarItem::Upcoming => "󰃮",
             SidebarItem::Anytime => "󰔚",
+            SidebarItem::Flagged => "󰈿",
+            SidebarItem::Deferred => "󰒲",
             SidebarItem::Completed => "󰄲",
             SidebarItem::Review => "󰑓",
             SidebarItem::GitHub => "󰊤",
             SidebarItem::Toggl => "󱎫",
+            SidebarItem::Stats => "󰄨",
             SidebarItem::Settings => "󰒓",
         }
     }
 
+    fn ascii_icon(&self) -> &'static str {
+        match self {
+            SidebarItem::Inbox => ">",
+            SidebarItem::Active => "*",
+            SidebarItem::Scheduled => "s",
+            SidebarItem::Today => "!",
+            SidebarItem::Agenda => "#",
+            SidebarItem::Week => "w",
+            SidebarItem::Upcoming => "+",
+            SidebarItem::Anytime => "~",
+            SidebarItem::Flagged => "^",
+            SidebarItem::Deferred => "z",
+            SidebarItem::Completed => "x",
+            SidebarItem::Review => "?",
+            SidebarItem::GitHub => "@",
+            SidebarItem::Toggl => "t",
+            SidebarItem::Stats => "%",
+            SidebarItem::Settings => "=",
+        }
+    }
+
     pub fn shortcut(&self) -> &'static str {
         match self {
             SidebarItem::Inbox => "1",
+            SidebarItem::Active => "0",
+            SidebarItem::Scheduled => "@",
             SidebarItem::Today => "2",
+            SidebarItem::Agenda => "A",
+            SidebarItem::Week => "K",
             SidebarItem::Upcoming => "3",
             SidebarItem::Anytime => "4",
+            SidebarItem::Flagged => "F",
+            SidebarItem::Deferred => "D",
             SidebarItem::Completed => "5",
             SidebarItem::Review => "6",
             SidebarItem::GitHub => "7",
             SidebarItem::Toggl => "8",
+            SidebarItem::Stats => "#",
             SidebarItem::Settings => "9",
         }
     }
@@ -193,3 +593,44 @@ impl SidebarItem {
         Self::all().get(index).copied()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_palette_reads_colorterm_and_term() {
+        assert_eq!(Palette::detect("truecolor", "xterm"), Palette::TrueColor);
+        assert_eq!(Palette::detect("24bit", ""), Palette::TrueColor);
+        assert_eq!(Palette::detect("truecolor", "xterm-256color"), Palette::TrueColor);
+        assert_eq!(Palette::detect("", "xterm-direct"), Palette::TrueColor);
+        assert_eq!(Palette::detect("", "xterm-256color"), Palette::Ansi256);
+        assert_eq!(Palette::detect("", "screen-256color"), Palette::Ansi256);
+        assert_eq!(Palette::detect("", "xterm"), Palette::Ansi16);
+        assert_eq!(Palette::detect("", "linux"), Palette::Ansi16);
+        assert_eq!(Palette::detect("", ""), Palette::Ansi16);
+    }
+
+    #[test]
+    fn ansi256_picks_the_nearest_cube_or_gray_entry() {
+        assert_eq!(Theme::ansi256(Color::Rgb(0, 0, 0)), Color::Indexed(16));
+        assert_eq!(Theme::ansi256(Color::Rgb(255, 255, 255)), Color::Indexed(231));
+        assert_eq!(Theme::ansi256(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+        assert_eq!(Theme::ansi256(Color::Rgb(0, 95, 175)), Color::Indexed(25));
+        assert_eq!(Theme::ansi256(Color::Rgb(128, 128, 128)), Color::Indexed(244));
+        assert_eq!(Theme::ansi256(Color::Reset), Color::Reset);
+        assert_eq!(Theme::ansi256(Color::Blue), Color::Blue);
+    }
+
+    #[test]
+    fn custom_kinds_match_names_with_surrounding_whitespace() {
+        CUSTOM_KINDS.write().unwrap().push(CustomKind {
+            name: " spike ".to_string(),
+            symbol: Some("[SPK]".to_string()),
+            color: Some("#ff0000".to_string()),
+        });
+        let spike = TaskKind::from_str("spike").unwrap();
+        assert!(Theme::kind_choices().contains(&spike));
+        assert_eq!(Theme::kind_symbol(&spike), "[SPK]");
+    }
+}
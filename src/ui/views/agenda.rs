@@ -0,0 +1,56 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    Frame,
+};
+
+use crate::models::Task;
+use crate::services::agenda_sections;
+use crate::ui::components::{render_task_detail, render_task_list, DetailOptions, TaskListState};
+
+pub struct AgendaView {
+    pub task_list: TaskListState,
+    pub detail_focused: bool,
+}
+
+impl AgendaView {
+    pub fn new() -> Self {
+        Self {
+            task_list: TaskListState::new("Agenda"),
+            detail_focused: false,
+        }
+    }
+
+    pub fn update_tasks(&mut self, all_tasks: &[Task]) {
+        let mut filtered: Vec<Task> = Vec::new();
+        let mut sections = Vec::new();
+        for (label, tasks) in agenda_sections(all_tasks) {
+            sections.push((filtered.len(), label.to_string()));
+            filtered.extend(tasks.into_iter().cloned());
+        }
+        self.task_list.set_tasks(filtered);
+        self.task_list.set_sections(sections);
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, detail: DetailOptions) {
+        let chunks = Layout::horizontal([
+            Constraint::Percentage(60),
+            Constraint::Percentage(40),
+        ])
+        .split(area);
+
+        render_task_list(frame, chunks[0], &mut self.task_list);
+        render_task_detail(
+            frame,
+            chunks[1],
+            self.task_list.selected_task(),
+            self.detail_focused,
+            detail,
+        );
+    }
+}
+
+impl Default for AgendaView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
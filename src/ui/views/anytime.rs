@@ -5,7 +5,7 @@ use ratatui::{
 
 use crate::models::Task;
 use crate::services::filter_anytime;
-use crate::ui::components::{render_task_detail, render_task_list, TaskListState};
+use crate::ui::components::{render_task_detail, render_task_list, DetailOptions, TaskListState};
 
 pub struct AnytimeView {
     pub task_list: TaskListState,
@@ -28,7 +28,7 @@ impl AnytimeView {
         self.task_list.set_tasks(filtered);
     }
 
-    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, detail: DetailOptions) {
         let chunks = Layout::horizontal([
             Constraint::Percentage(60),
             Constraint::Percentage(40),
@@ -41,12 +41,9 @@ impl AnytimeView {
             chunks[1],
             self.task_list.selected_task(),
             self.detail_focused,
+            detail,
         );
     }
-
-    pub fn selected_task(&self) -> Option<&Task> {
-        self.task_list.selected_task()
-    }
 }
 
 impl Default for AnytimeView {
@@ -3,13 +3,82 @@ use ratatui::{
     Frame,
 };
 
+use chrono::{Datelike, NaiveDate, Utc, Weekday};
+
 use crate::models::Task;
-use crate::services::filter_completed;
-use crate::ui::components::{render_task_detail, render_task_list, TaskListState};
+use crate::services::{filter_completed, filter_completed_between, group_by_project, parse_due_date};
+use crate::ui::components::{render_task_detail, render_task_list, DetailOptions, TaskListState};
+
+/// Period of completions the Completed view shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletedRange {
+    All,
+    Today,
+    ThisWeek,
+    ThisMonth,
+    /// `from..=to`
+    Between(NaiveDate, NaiveDate),
+}
+
+impl CompletedRange {
+    /// Parse `all` (or nothing), `today`, `week`, `month`, a single date or
+    /// `FROM..TO`, where the dates take anything a due date does
+    pub fn parse(input: &str, today: NaiveDate) -> Option<Self> {
+        let input = input.trim().to_lowercase();
+        match input.as_str() {
+            "" | "all" => return Some(CompletedRange::All),
+            "today" => return Some(CompletedRange::Today),
+            "week" => return Some(CompletedRange::ThisWeek),
+            "month" => return Some(CompletedRange::ThisMonth),
+            _ => {}
+        }
+        let (from, to) = match input.split_once("..") {
+            Some((from, to)) => (parse_due_date(from, today)?, parse_due_date(to, today)?),
+            None => {
+                let date = parse_due_date(&input, today)?;
+                (date, date)
+            }
+        };
+        (from <= to).then_some(CompletedRange::Between(from, to))
+    }
+
+    /// First and last day of the range, or None for all time
+    pub fn bounds(&self, today: NaiveDate, week_start: Weekday) -> Option<(NaiveDate, NaiveDate)> {
+        match *self {
+            CompletedRange::All => None,
+            CompletedRange::Today => Some((today, today)),
+            CompletedRange::ThisWeek => {
+                let week = today.week(week_start);
+                Some((week.first_day(), week.last_day()))
+            }
+            CompletedRange::ThisMonth => {
+                let first = today.with_day(1)?;
+                let next = first.checked_add_months(chrono::Months::new(1))?;
+                Some((first, next.pred_opt()?))
+            }
+            CompletedRange::Between(from, to) => Some((from, to)),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            CompletedRange::All => "all time".to_string(),
+            CompletedRange::Today => "today".to_string(),
+            CompletedRange::ThisWeek => "this week".to_string(),
+            CompletedRange::ThisMonth => "this month".to_string(),
+            CompletedRange::Between(from, to) if from == to => from.to_string(),
+            CompletedRange::Between(from, to) => format!("{}..{}", from, to),
+        }
+    }
+}
 
 pub struct CompletedView {
     pub task_list: TaskListState,
     pub detail_focused: bool,
+    /// Show tasks under project headers, most recently completed first
+    pub group_by_project: bool,
+    pub range: CompletedRange,
+    pub week_start: Weekday,
 }
 
 impl CompletedView {
@@ -17,18 +86,53 @@ impl CompletedView {
         Self {
             task_list: TaskListState::new("Completed"),
             detail_focused: false,
+            group_by_project: false,
+            range: CompletedRange::All,
+            week_start: Weekday::Mon,
         }
     }
 
     pub fn update_tasks(&mut self, all_tasks: &[Task]) {
-        let filtered: Vec<Task> = filter_completed(all_tasks)
-            .into_iter()
-            .cloned()
-            .collect();
-        self.task_list.set_tasks(filtered);
+        let today = Utc::now().date_naive();
+        let mut completed = match self.range.bounds(today, self.week_start) {
+            Some((from, to)) => filter_completed_between(all_tasks, from, to),
+            None => filter_completed(all_tasks),
+        };
+        self.task_list.title = match self.range {
+            CompletedRange::All => "Completed".to_string(),
+            range => format!("Completed · {}", range.label()),
+        };
+        if !self.group_by_project {
+            let filtered: Vec<Task> = completed.into_iter().cloned().collect();
+            self.task_list.set_tasks(filtered);
+            self.task_list.set_sections(Vec::new());
+            return;
+        }
+
+        // Groups follow the order of their latest completion
+        completed.sort_by_key(|t| std::cmp::Reverse(t.completed_at));
+        let mut grouped: Vec<Task> = Vec::new();
+        let mut sections = Vec::new();
+        let labels = &self.task_list.project_labels;
+        for (project_id, tasks) in group_by_project(completed, |id| labels.contains_key(id)) {
+            let label = project_id
+                .and_then(|id| labels.get(&id).cloned())
+                .unwrap_or_else(|| "No Project".to_string());
+            sections.push((grouped.len(), label));
+            grouped.extend(tasks.into_iter().cloned());
+        }
+        self.task_list.set_tasks(grouped);
+        self.task_list.set_sections(sections);
+    }
+
+    pub fn toggle_group_by_project(&mut self, all_tasks: &[Task]) {
+        self.group_by_project = !self.group_by_project;
+        // The project is in the header while grouped
+        self.task_list.show_project = !self.group_by_project;
+        self.update_tasks(all_tasks);
     }
 
-    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, detail: DetailOptions) {
         let chunks = Layout::horizontal([
             Constraint::Percentage(60),
             Constraint::Percentage(40),
@@ -41,12 +145,9 @@ impl CompletedView {
             chunks[1],
             self.task_list.selected_task(),
             self.detail_focused,
+            detail,
         );
     }
-
-    pub fn selected_task(&self) -> Option<&Task> {
-        self.task_list.selected_task()
-    }
 }
 
 impl Default for CompletedView {
@@ -54,3 +155,71 @@ impl Default for CompletedView {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn parse_reads_keywords_dates_and_ranges() {
+        let today = date(2024, 3, 13);
+        assert_eq!(CompletedRange::parse("", today), Some(CompletedRange::All));
+        assert_eq!(CompletedRange::parse(" Week ", today), Some(CompletedRange::ThisWeek));
+        assert_eq!(
+            CompletedRange::parse("2024-03-01", today),
+            Some(CompletedRange::Between(date(2024, 3, 1), date(2024, 3, 1)))
+        );
+        assert_eq!(
+            CompletedRange::parse("2024-03-01..today", today),
+            Some(CompletedRange::Between(date(2024, 3, 1), today))
+        );
+    }
+
+    #[test]
+    fn parse_rejects_reversed_and_malformed_ranges() {
+        let today = date(2024, 3, 13);
+        assert_eq!(CompletedRange::parse("2024-03-05..2024-03-01", today), None);
+        assert_eq!(CompletedRange::parse("2024-03-01..", today), None);
+        assert_eq!(CompletedRange::parse("soon", today), None);
+    }
+
+    #[test]
+    fn month_bounds_end_on_the_last_day_of_the_month() {
+        let month = CompletedRange::ThisMonth;
+        assert_eq!(
+            month.bounds(date(2024, 2, 29), Weekday::Mon),
+            Some((date(2024, 2, 1), date(2024, 2, 29)))
+        );
+        assert_eq!(
+            month.bounds(date(2023, 2, 10), Weekday::Mon),
+            Some((date(2023, 2, 1), date(2023, 2, 28)))
+        );
+        assert_eq!(
+            month.bounds(date(2024, 12, 31), Weekday::Mon),
+            Some((date(2024, 12, 1), date(2024, 12, 31)))
+        );
+    }
+
+    #[test]
+    fn week_bounds_follow_the_configured_week_start() {
+        // 2024-03-13 is a Wednesday
+        let today = date(2024, 3, 13);
+        let week = CompletedRange::ThisWeek;
+        assert_eq!(
+            week.bounds(today, Weekday::Mon),
+            Some((date(2024, 3, 11), date(2024, 3, 17)))
+        );
+        assert_eq!(
+            week.bounds(today, Weekday::Sun),
+            Some((date(2024, 3, 10), date(2024, 3, 16)))
+        );
+        assert_eq!(
+            week.bounds(date(2024, 3, 10), Weekday::Sun),
+            Some((date(2024, 3, 10), date(2024, 3, 16)))
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::Style,
@@ -17,13 +19,27 @@ pub enum GitHubColumn {
     AssignedIssues,
 }
 
+impl GitHubColumn {
+    /// Parse the `github_start_column` config: "review", "mine" or "assigned"
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "mine" => GitHubColumn::MyPRs,
+            "assigned" => GitHubColumn::AssignedIssues,
+            _ => GitHubColumn::ReviewPRs,
+        }
+    }
+}
+
 pub struct GitHubView {
     pub review_prs: GitHubColumnState,
     pub my_prs: GitHubColumnState,
     pub assigned_issues: GitHubColumnState,
+    /// Focused column; starts at the configured one and is kept across view switches
     pub active_column: GitHubColumn,
     pub loading: bool,
     pub error: Option<String>,
+    /// When the last fetch failed, used to avoid immediately retrying
+    pub failed_at: Option<Instant>,
 }
 
 impl GitHubView {
@@ -35,19 +51,33 @@ impl GitHubView {
             active_column: GitHubColumn::ReviewPRs,
             loading: false,
             error: None,
+            failed_at: None,
         }
     }
 
-    pub fn set_data(&mut self, data: GitHubData) {
+    pub fn set_data(&mut self, mut data: GitHubData) {
+        let also_in = data.dedup_by_url();
+        self.review_prs.also_in = also_in.clone();
+        self.my_prs.also_in = also_in.clone();
+        self.assigned_issues.also_in = also_in;
+        self.review_prs.error = data.errors.remove("review");
+        self.my_prs.error = data.errors.remove("mine");
+        self.assigned_issues.error = data.errors.remove("assigned");
+        // Most recently updated first
+        for items in [&mut data.review_prs, &mut data.my_prs, &mut data.assigned_issues] {
+            items.sort_by_key(|item| std::cmp::Reverse(item.updated_at));
+        }
         self.review_prs.set_items(data.review_prs);
         self.my_prs.set_items(data.my_prs);
         self.assigned_issues.set_items(data.assigned_issues);
         self.loading = false;
         self.error = None;
+        self.failed_at = None;
     }
 
     pub fn set_error(&mut self, error: String) {
         self.error = Some(error);
+        self.failed_at = Some(Instant::now());
         self.loading = false;
     }
 
@@ -103,12 +133,15 @@ impl GitHubView {
         }
     }
 
-    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+    /// `tick` advances the loading spinner
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, tick: usize) {
         self.update_focus();
 
         if self.loading {
-            let loading = Paragraph::new("Loading GitHub data...")
-                .style(Theme::dimmed_style());
+            let loading = Paragraph::new(Line::from(vec![
+                Span::styled(Theme::spinner(tick), Style::default().fg(Theme::PRIMARY)),
+                Span::styled(" Loading GitHub data...", Theme::dimmed_style()),
+            ]));
             frame.render_widget(loading, area);
             return;
         }
@@ -1,23 +1,37 @@
 mod inbox;
+mod active;
+mod scheduled;
 mod today;
+mod agenda;
+mod week;
 mod upcoming;
 mod anytime;
+mod flagged;
+mod deferred;
 mod completed;
 mod project;
 mod tag;
 mod review;
 mod github;
 mod toggl;
+mod stats;
 mod settings;
 
 pub use inbox::*;
+pub use active::*;
+pub use scheduled::*;
 pub use today::*;
+pub use agenda::*;
+pub use week::*;
 pub use upcoming::*;
 pub use anytime::*;
+pub use flagged::*;
+pub use deferred::*;
 pub use completed::*;
 pub use project::*;
 pub use tag::*;
 pub use review::*;
 pub use github::*;
 pub use toggl::*;
+pub use stats::*;
 pub use settings::*;
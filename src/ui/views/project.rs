@@ -1,11 +1,13 @@
+use chrono::Utc;
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     Frame,
 };
 
 use crate::models::{Project, Task};
-use crate::services::filter_by_project;
-use crate::ui::components::{render_task_detail, render_task_list, TaskListState};
+use crate::services::{filter_by_project, format_estimate};
+use crate::ui::components::{render_task_detail, render_task_list, DetailOptions, TaskListState};
+use crate::ui::theme::Theme;
 
 pub struct ProjectView {
     pub task_list: TaskListState,
@@ -15,23 +17,29 @@ pub struct ProjectView {
 
 impl ProjectView {
     pub fn new() -> Self {
+        let mut task_list = TaskListState::new("Project");
+        task_list.show_project = false;
         Self {
-            task_list: TaskListState::new("Project"),
+            task_list,
             detail_focused: false,
             project: None,
         }
     }
 
     pub fn set_project(&mut self, project: Option<Project>) {
-        let title = project
-            .as_ref()
-            .map(|p| format!("{} {}", p.display_icon(), p.name))
-            .unwrap_or_else(|| "No Project".to_string());
-        self.task_list.title = title;
         self.project = project;
+        self.task_list.title = self.title(&[], 0);
     }
 
     pub fn update_tasks(&mut self, all_tasks: &[Task]) {
+        // Logged time counts completed tasks too, unlike the list
+        let spent = self.project.as_ref().map_or(0, |project| {
+            all_tasks
+                .iter()
+                .filter(|t| !t.deleted && t.project_id.as_deref() == Some(project.id.as_str()))
+                .map(|t| t.time_spent_minutes)
+                .sum()
+        });
         let filtered: Vec<Task> = if let Some(ref project) = self.project {
             filter_by_project(all_tasks, &project.id)
                 .into_iter()
@@ -40,10 +48,35 @@ impl ProjectView {
         } else {
             Vec::new()
         };
+        self.task_list.title = self.title(&filtered, spent);
         self.task_list.set_tasks(filtered);
     }
 
-    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+    /// Project name followed by its overdue count, soonest upcoming due date and
+    /// logged time, e.g. "MyProject · 2 overdue · next due Jun 10 · 3h30m spent";
+    /// segments that don't apply are left out
+    fn title(&self, tasks: &[Task], spent: i64) -> String {
+        let Some(ref project) = self.project else {
+            return "No Project".to_string();
+        };
+        let mut title = format!("{} {}", Theme::project_icon(project), project.name);
+
+        let overdue = tasks.iter().filter(|t| t.is_overdue()).count();
+        if overdue > 0 {
+            title.push_str(&format!(" · {} overdue", overdue));
+        }
+        let today = Utc::now().date_naive();
+        let next_due = tasks.iter().filter_map(|t| t.due_date).filter(|d| *d >= today).min();
+        if let Some(due) = next_due {
+            title.push_str(&format!(" · next due {}", due.format("%b %-d")));
+        }
+        if spent > 0 {
+            title.push_str(&format!(" · {} spent", format_estimate(spent)));
+        }
+        title
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, detail: DetailOptions) {
         let chunks = Layout::horizontal([
             Constraint::Percentage(60),
             Constraint::Percentage(40),
@@ -56,12 +89,9 @@ impl ProjectView {
             chunks[1],
             self.task_list.selected_task(),
             self.detail_focused,
+            detail,
         );
     }
-
-    pub fn selected_task(&self) -> Option<&Task> {
-        self.task_list.selected_task()
-    }
 }
 
 impl Default for ProjectView {
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Local, Utc};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     Frame,
@@ -5,7 +6,7 @@ use ratatui::{
 
 use crate::models::Task;
 use crate::services::filter_review;
-use crate::ui::components::{render_task_detail, render_task_list, TaskListState};
+use crate::ui::components::{render_task_detail, render_task_list, DetailOptions, TaskListState};
 
 pub struct ReviewView {
     pub task_list: TaskListState,
@@ -20,6 +21,17 @@ impl ReviewView {
         }
     }
 
+    /// Show when the last weekly review was completed in the list title
+    pub fn set_last_review(&mut self, last_review: Option<DateTime<Utc>>) {
+        self.task_list.title = match last_review {
+            Some(at) => format!(
+                "Review (Overdue) · last weekly review {}",
+                at.with_timezone(&Local).format("%Y-%m-%d")
+            ),
+            None => "Review (Overdue) · w: start weekly review".to_string(),
+        };
+    }
+
     pub fn update_tasks(&mut self, all_tasks: &[Task]) {
         let filtered: Vec<Task> = filter_review(all_tasks)
             .into_iter()
@@ -28,7 +40,7 @@ impl ReviewView {
         self.task_list.set_tasks(filtered);
     }
 
-    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, detail: DetailOptions) {
         let chunks = Layout::horizontal([
             Constraint::Percentage(60),
             Constraint::Percentage(40),
@@ -41,12 +53,9 @@ impl ReviewView {
             chunks[1],
             self.task_list.selected_task(),
             self.detail_focused,
+            detail,
         );
     }
-
-    pub fn selected_task(&self) -> Option<&Task> {
-        self.task_list.selected_task()
-    }
 }
 
 impl Default for ReviewView {
@@ -207,7 +207,15 @@ impl SettingsView {
                 Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(": Edit | "),
                 Span::styled("s", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(": Save config"),
+                Span::raw(": Save config | "),
+                Span::styled("o", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(": Normalize order | "),
+                Span::styled("m", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(": Merge duplicate tags | "),
+                Span::styled("v", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(": Vacuum database | "),
+                Span::styled("E", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(": Edit config file"),
             ])
         };
         let help_para = Paragraph::new(help).style(Theme::muted_style());
@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate, Utc, Weekday};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::models::{TaskKind, TaskPriority, TaskStatus};
+use crate::ui::theme::Theme;
+
+/// Weeks of completions shown by the heatmap, including the current one
+const HEATMAP_WEEKS: i64 = 12;
+
+/// Composition of the open backlog: task counts by status, kind and priority,
+/// plus a heatmap of recent completions
+pub struct StatsView {
+    /// Open task counts keyed by the stored column value ("none" for no kind)
+    pub by_status: HashMap<String, i64>,
+    pub by_kind: HashMap<String, i64>,
+    pub by_priority: HashMap<String, i64>,
+    /// Completed tasks per day since `heatmap_start`
+    pub completed_by_day: HashMap<NaiveDate, i64>,
+    /// First day of the heatmap's weeks (its top row)
+    pub week_start: Weekday,
+}
+
+impl StatsView {
+    pub fn new() -> Self {
+        Self {
+            by_status: HashMap::new(),
+            by_kind: HashMap::new(),
+            by_priority: HashMap::new(),
+            completed_by_day: HashMap::new(),
+            week_start: Weekday::Mon,
+        }
+    }
+
+    /// First day of the heatmap: the start of the week `HEATMAP_WEEKS - 1` weeks ago
+    pub fn heatmap_start(&self) -> NaiveDate {
+        let today = Utc::now().date_naive();
+        today.week(self.week_start).first_day() - Duration::weeks(HEATMAP_WEEKS - 1)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let total: i64 = self.by_status.values().sum();
+        let block = Block::default()
+            .title(format!(" Stats ({} open tasks) ", total))
+            .title_style(Theme::title_style())
+            .borders(Borders::ALL)
+            .border_style(Theme::border_style(true));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let status_rows: Vec<(&str, i64, Color)> = [
+            TaskStatus::Inbox,
+            TaskStatus::Active,
+            TaskStatus::Scheduled,
+        ]
+        .iter()
+        .map(|s| (s.as_str(), count(&self.by_status, s.as_str()), Theme::status_color(s)))
+        .collect();
+
+        // Kinds are many and mostly unused; list only those with tasks, in the
+        // task form's order, with kinds no longer configured and "none" last
+        let choices = Theme::kind_choices();
+        let mut kinds: Vec<(&String, &i64)> = self.by_kind.iter().filter(|(_, n)| **n > 0).collect();
+        kinds.sort_by_key(|(name, _)| {
+            let position = choices.iter().position(|k| k.as_str() == name.as_str());
+            (name.as_str() == "none", position.unwrap_or(choices.len()), name.to_string())
+        });
+        let kind_rows: Vec<(&str, i64, Color)> = kinds
+            .into_iter()
+            .map(|(name, n)| {
+                let color = match name.as_str() {
+                    "none" => Theme::FG_MUTED,
+                    name => TaskKind::from_str(name).map_or(Theme::FG_MUTED, |k| Theme::kind_color(&k)),
+                };
+                (name.as_str(), *n, color)
+            })
+            .collect();
+
+        let priority_rows: Vec<(&str, i64, Color)> = [
+            TaskPriority::High,
+            TaskPriority::Medium,
+            TaskPriority::Low,
+            TaskPriority::None,
+        ]
+        .iter()
+        .map(|p| (p.as_str(), count(&self.by_priority, p.as_str()), Theme::priority_color(p)))
+        .collect();
+
+        let sections = [
+            ("By status", status_rows),
+            ("By kind", kind_rows),
+            ("By priority", priority_rows),
+        ];
+        let chunks = Layout::vertical(
+            sections
+                .iter()
+                .map(|(_, rows)| Constraint::Length(rows.len() as u16 + 2))
+                .chain([Constraint::Min(0)]),
+        )
+        .split(inner);
+
+        for ((title, rows), chunk) in sections.iter().zip(chunks.iter()) {
+            frame.render_widget(Paragraph::new(section_lines(title, rows, chunk.width)), *chunk);
+        }
+        frame.render_widget(Paragraph::new(self.heatmap_lines()), chunks[sections.len()]);
+    }
+
+    /// GitHub-style grid of completions: one column per week, one row per
+    /// weekday, shaded relative to the busiest day
+    fn heatmap_lines(&self) -> Vec<Line<'static>> {
+        let today = Utc::now().date_naive();
+        let start = self.heatmap_start();
+        let total: i64 = self.completed_by_day.values().sum();
+        let max = self.completed_by_day.values().copied().max().unwrap_or(0).max(1);
+        let cell = Theme::icon("■", "#");
+
+        let mut lines = vec![Line::from(Span::styled(
+            format!("Completed, last {} weeks: {}", HEATMAP_WEEKS, total),
+            Theme::dimmed_style().add_modifier(Modifier::BOLD),
+        ))];
+        if total == 0 {
+            lines.push(Line::from(Span::styled("  No completed tasks yet", Theme::muted_style())));
+            return lines;
+        }
+
+        for row in 0..7 {
+            let weekday = start + Duration::days(row);
+            // Label every other row, like GitHub
+            let label = if row % 2 == 0 { weekday.format("%a").to_string() } else { String::new() };
+            let mut spans = vec![Span::styled(format!("  {:<4}", label), Theme::muted_style())];
+            for week in 0..HEATMAP_WEEKS {
+                let date = weekday + Duration::weeks(week);
+                if date > today {
+                    break;
+                }
+                let count = self.completed_by_day.get(&date).copied().unwrap_or(0);
+                let level = if count == 0 { 0 } else { 1 + ((count - 1) * 4 / max) as usize };
+                spans.push(Span::styled(format!("{} ", cell), Style::default().fg(Theme::HEAT[level])));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        let mut legend = vec![Span::styled("  Less ", Theme::muted_style())];
+        legend.extend(Theme::HEAT.iter().map(|&c| Span::styled(cell, Style::default().fg(c))));
+        legend.push(Span::styled(" More", Theme::muted_style()));
+        lines.push(Line::from(legend));
+        lines
+    }
+}
+
+impl Default for StatsView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn count(counts: &HashMap<String, i64>, key: &str) -> i64 {
+    counts.get(key).copied().unwrap_or(0)
+}
+
+/// Section header followed by one colored bar per row, scaled to the section's
+/// largest count
+fn section_lines(title: &str, rows: &[(&str, i64, Color)], width: u16) -> Vec<Line<'static>> {
+    const LABEL_WIDTH: usize = 12;
+    let bar_width = (width as usize).saturating_sub(LABEL_WIDTH + 8).min(40);
+    let max = rows.iter().map(|(_, n, _)| *n).max().unwrap_or(0).max(1);
+
+    let mut lines = vec![Line::from(Span::styled(
+        title.to_string(),
+        Theme::dimmed_style().add_modifier(Modifier::BOLD),
+    ))];
+    for (label, n, color) in rows {
+        let filled = (*n as usize * bar_width).div_ceil(max as usize);
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<width$}", label, width = LABEL_WIDTH - 2), Style::default().fg(*color)),
+            Span::styled(Theme::icon("█", "#").repeat(filled), Style::default().fg(*color)),
+            Span::styled(format!(" {}", n), Style::default().fg(Theme::FG)),
+        ]));
+    }
+    lines
+}
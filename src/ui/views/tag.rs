@@ -5,7 +5,7 @@ use ratatui::{
 
 use crate::models::{Tag, Task};
 use crate::services::filter_by_tag;
-use crate::ui::components::{render_task_detail, render_task_list, TaskListState};
+use crate::ui::components::{render_task_detail, render_task_list, DetailOptions, TaskListState};
 
 pub struct TagView {
     pub task_list: TaskListState,
@@ -43,7 +43,7 @@ impl TagView {
         self.task_list.set_tasks(filtered);
     }
 
-    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, detail: DetailOptions) {
         let chunks = Layout::horizontal([
             Constraint::Percentage(60),
             Constraint::Percentage(40),
@@ -56,12 +56,9 @@ impl TagView {
             chunks[1],
             self.task_list.selected_task(),
             self.detail_focused,
+            detail,
         );
     }
-
-    pub fn selected_task(&self) -> Option<&Task> {
-        self.task_list.selected_task()
-    }
 }
 
 impl Default for TagView {
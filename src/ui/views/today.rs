@@ -4,12 +4,14 @@ use ratatui::{
 };
 
 use crate::models::Task;
-use crate::services::filter_today;
-use crate::ui::components::{render_task_detail, render_task_list, TaskListState};
+use crate::services::{filter_today, sort_for_today};
+use crate::ui::components::{render_task_detail, render_task_list, DetailOptions, TaskListState};
 
 pub struct TodayView {
     pub task_list: TaskListState,
     pub detail_focused: bool,
+    /// List overdue tasks here too (otherwise they only appear in Review)
+    pub include_overdue: bool,
 }
 
 impl TodayView {
@@ -17,18 +19,23 @@ impl TodayView {
         Self {
             task_list: TaskListState::new("Today"),
             detail_focused: false,
+            include_overdue: true,
         }
     }
 
+    pub fn set_include_overdue(&mut self, include_overdue: bool) {
+        self.include_overdue = include_overdue;
+        self.task_list.title = if include_overdue { "Today" } else { "Due Today" }.to_string();
+    }
+
     pub fn update_tasks(&mut self, all_tasks: &[Task]) {
-        let filtered: Vec<Task> = filter_today(all_tasks)
-            .into_iter()
-            .cloned()
-            .collect();
+        let mut filtered = filter_today(all_tasks, self.include_overdue);
+        sort_for_today(&mut filtered);
+        let filtered: Vec<Task> = filtered.into_iter().cloned().collect();
         self.task_list.set_tasks(filtered);
     }
 
-    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, detail: DetailOptions) {
         let chunks = Layout::horizontal([
             Constraint::Percentage(60),
             Constraint::Percentage(40),
@@ -41,12 +48,9 @@ impl TodayView {
             chunks[1],
             self.task_list.selected_task(),
             self.detail_focused,
+            detail,
         );
     }
-
-    pub fn selected_task(&self) -> Option<&Task> {
-        self.task_list.selected_task()
-    }
 }
 
 impl Default for TodayView {
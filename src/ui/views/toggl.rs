@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use ratatui::{
     layout::Rect,
     style::Style,
@@ -14,6 +16,8 @@ pub struct TogglView {
     pub chart_state: TogglChartState,
     pub loading: bool,
     pub error: Option<String>,
+    /// When the last fetch failed, used to avoid immediately retrying
+    pub failed_at: Option<Instant>,
 }
 
 impl TogglView {
@@ -22,6 +26,7 @@ impl TogglView {
             chart_state: TogglChartState::default(),
             loading: false,
             error: None,
+            failed_at: None,
         }
     }
 
@@ -29,10 +34,12 @@ impl TogglView {
         self.chart_state.data = data;
         self.loading = false;
         self.error = None;
+        self.failed_at = None;
     }
 
     pub fn set_error(&mut self, error: String) {
         self.error = Some(error);
+        self.failed_at = Some(Instant::now());
         self.loading = false;
     }
 
@@ -40,10 +47,13 @@ impl TogglView {
         self.loading = loading;
     }
 
-    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+    /// `tick` advances the loading spinner
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, tick: usize) {
         if self.loading {
-            let loading = Paragraph::new("Loading Toggl data...")
-                .style(Theme::dimmed_style());
+            let loading = Paragraph::new(Line::from(vec![
+                Span::styled(Theme::spinner(tick), Style::default().fg(Theme::PRIMARY)),
+                Span::styled(" Loading Toggl data...", Theme::dimmed_style()),
+            ]));
             frame.render_widget(loading, area);
             return;
         }
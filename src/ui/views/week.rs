@@ -0,0 +1,76 @@
+use chrono::{Duration, Utc};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    Frame,
+};
+
+use crate::models::Task;
+use crate::services::{filter_this_week, group_by_date};
+use crate::ui::components::{render_task_detail, render_task_list, DetailOptions, TaskListState};
+
+pub struct WeekView {
+    pub task_list: TaskListState,
+    pub detail_focused: bool,
+    /// Lead the list with overdue tasks
+    pub include_overdue: bool,
+}
+
+impl WeekView {
+    pub fn new() -> Self {
+        Self {
+            task_list: TaskListState::new("This Week"),
+            detail_focused: false,
+            include_overdue: true,
+        }
+    }
+
+    /// Tasks due in the next 7 days under a header per day; overdue tasks come
+    /// first in one "Overdue" section
+    pub fn update_tasks(&mut self, all_tasks: &[Task]) {
+        let today = Utc::now().date_naive();
+        let mut filtered: Vec<Task> = Vec::new();
+        let mut sections: Vec<(usize, String)> = Vec::new();
+        for (date, tasks) in group_by_date(filter_this_week(all_tasks, self.include_overdue)) {
+            let Some(date) = date else { continue };
+            let label = if date < today {
+                "Overdue".to_string()
+            } else if date == today {
+                "Today".to_string()
+            } else if date == today + Duration::days(1) {
+                "Tomorrow".to_string()
+            } else {
+                date.format("%A, %b %-d").to_string()
+            };
+            // Overdue dates share one section
+            if sections.last().map(|(_, last)| last) != Some(&label) {
+                sections.push((filtered.len(), label));
+            }
+            filtered.extend(tasks.into_iter().cloned());
+        }
+        self.task_list.set_tasks(filtered);
+        self.task_list.set_sections(sections);
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, detail: DetailOptions) {
+        let chunks = Layout::horizontal([
+            Constraint::Percentage(60),
+            Constraint::Percentage(40),
+        ])
+        .split(area);
+
+        render_task_list(frame, chunks[0], &mut self.task_list);
+        render_task_detail(
+            frame,
+            chunks[1],
+            self.task_list.selected_task(),
+            self.detail_focused,
+            detail,
+        );
+    }
+}
+
+impl Default for WeekView {
+    fn default() -> Self {
+        Self::new()
+    }
+}